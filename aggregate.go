@@ -0,0 +1,84 @@
+package sales
+
+import (
+	"encoding/csv"
+	"errors"
+	"fmt"
+	"io"
+	"strconv"
+)
+
+// aggregateColumns are the columns of a pre-aggregated summary, with one row
+// per product. WriteCSV writes summaries in this format.
+var aggregateColumns = []string{"Product", "Units", "Revenue"}
+
+func isAggregate(col map[string]int) bool {
+	for _, name := range aggregateColumns {
+		if _, ok := col[name]; !ok {
+			return false
+		}
+	}
+	return true
+}
+
+// AggregateSources returns the sources that were read as pre-aggregated
+// summaries. These contribute to product totals, but have no order-level
+// detail, so they're left out of order counts and timing analysis.
+func (r *Report) AggregateSources() []string {
+	return r.aggregates
+}
+
+func (r *Report) readAggregate(cr *csv.Reader, header []string, col map[string]int, source string, lenient bool) error {
+	r.aggregates = append(r.aggregates, source)
+	invalid := func(i int, value, kind string, err error) *RowError {
+		line, _ := cr.FieldPos(i)
+		return &RowError{
+			File: source,
+			Line: line,
+			Err:  &FieldError{Column: header[i], Value: value, Kind: kind, Err: err},
+		}
+	}
+	for {
+		record, err := cr.Read()
+		if err == io.EOF {
+			return nil
+		}
+		if err != nil {
+			var pe *csv.ParseError
+			if !errors.As(err, &pe) {
+				return fmt.Errorf("%s: %w", source, err)
+			}
+			if err := r.reject(&RowError{File: source, Line: pe.StartLine, Err: pe.Err}, lenient); err != nil {
+				return err
+			}
+			continue
+		}
+		if err := r.countRow(source); err != nil {
+			return err
+		}
+		i := col["Units"]
+		units, err := strconv.Atoi(record[i])
+		if err != nil {
+			if err := r.reject(invalid(i, record[i], "units", err), lenient); err != nil {
+				return err
+			}
+			continue
+		}
+		i = col["Revenue"]
+		revenue, err := ParseUSD(record[i])
+		if err != nil {
+			if err := r.reject(invalid(i, record[i], "revenue", err), lenient); err != nil {
+				return err
+			}
+			continue
+		}
+		product := r.Renames.Apply(record[col["Product"]])
+		if _, ok := r.products[r.Groups.Classify(product)]; !ok {
+			if limit := r.Limits.MaxProducts; limit > 0 && int64(len(r.products)) >= limit {
+				line, _ := cr.FieldPos(col["Product"])
+				return &RowError{File: source, Line: line, Err: &LimitError{Limit: "max_products", Max: limit}}
+			}
+		}
+		r.addTally(r.Groups.Classify(product), Origin{File: source}, "", units, revenue.Round(r.Rounding))
+	}
+}
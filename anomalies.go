@@ -0,0 +1,134 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"math"
+	"sort"
+	"time"
+)
+
+// daySlot identifies the sales of one product on one day, such as
+// "2024-03-14".
+type daySlot struct {
+	product string
+	date    string
+}
+
+// dailyRevenue returns the revenue from all products on each day with sales.
+func (r *Report) dailyRevenue() map[string]USD {
+	days := map[string]USD{}
+	for k, rev := range r.daily {
+		days[k.date] += rev
+	}
+	return days
+}
+
+// Anomaly is a day on which the revenue from a product, or from all
+// products, was unusually high or low compared with the days before.
+type Anomaly struct {
+	Date time.Time
+	// Product is the product concerned, or "" for all products.
+	Product string
+	Revenue USD
+	// Mean and StdDev describe the revenue over the trailing window,
+	// and Deviations is how many standard deviations Revenue is from
+	// Mean: positive for a spike, negative for a drop.
+	Mean       USD
+	StdDev     USD
+	Deviations float64
+}
+
+// minHistory is the fewest days of history needed to judge a day.
+const minHistory = 7
+
+// Anomalies returns the days on which revenue, in total or for a product,
+// differed from the mean of the previous window days by more than
+// threshold standard deviations, in date order. Days without sales count
+// as zero revenue, from a product's first sale up to the latest sale in the
+// report, so that a product that suddenly stops selling is caught too.
+func (r *Report) Anomalies(threshold float64, window int) []Anomaly {
+	byProduct := map[string]map[string]USD{"": r.dailyRevenue()}
+	var latest string
+	for k, rev := range r.daily {
+		if byProduct[k.product] == nil {
+			byProduct[k.product] = map[string]USD{}
+		}
+		byProduct[k.product][k.date] += rev
+		latest = max(latest, k.date)
+	}
+	end, err := time.Parse(time.DateOnly, latest)
+	if err != nil {
+		return nil
+	}
+	var anomalies []Anomaly
+	for product, days := range byProduct {
+		first := latest
+		for d := range days {
+			first = min(first, d)
+		}
+		start, _ := time.Parse(time.DateOnly, first)
+		var series []float64
+		for d := start; !d.After(end); d = d.AddDate(0, 0, 1) {
+			series = append(series, float64(days[d.Format(time.DateOnly)]))
+		}
+		for i := minHistory; i < len(series); i++ {
+			history := series[max(0, i-window):i]
+			mean, sd := meanStdDev(history)
+			if sd == 0 {
+				continue
+			}
+			if dev := (series[i] - mean) / sd; math.Abs(dev) > threshold {
+				anomalies = append(anomalies, Anomaly{
+					Date:       start.AddDate(0, 0, i),
+					Product:    product,
+					Revenue:    USD(series[i]),
+					Mean:       USD(math.Round(mean)),
+					StdDev:     USD(math.Round(sd)),
+					Deviations: dev,
+				})
+			}
+		}
+	}
+	sort.Slice(anomalies, func(i, j int) bool {
+		a, b := anomalies[i], anomalies[j]
+		if !a.Date.Equal(b.Date) {
+			return a.Date.Before(b.Date)
+		}
+		return a.Product < b.Product
+	})
+	return anomalies
+}
+
+func meanStdDev(xs []float64) (mean, sd float64) {
+	for _, x := range xs {
+		mean += x
+	}
+	mean /= float64(len(xs))
+	for _, x := range xs {
+		sd += (x - mean) * (x - mean)
+	}
+	return mean, math.Sqrt(sd / float64(len(xs)))
+}
+
+// WriteAnomalies writes the anomalies found by Anomalies as a text table.
+func (r *Report) WriteAnomalies(w io.Writer, threshold float64, window int) error {
+	anomalies := r.Anomalies(threshold, window)
+	if len(anomalies) == 0 {
+		_, err := fmt.Fprintln(w, "No anomalies found")
+		return err
+	}
+	t := &table{
+		header: []string{"Date", "Product", "Revenue", "Average", "Deviations"},
+		right:  []bool{false, false, true, true, true},
+		color:  r.Color,
+	}
+	for _, a := range anomalies {
+		product := a.Product
+		if product == "" {
+			product = "(all products)"
+		}
+		t.add(a.Date.Format(time.DateOnly), product, a.Revenue.String(), a.Mean.String(), fmt.Sprintf("%+.1f", a.Deviations))
+	}
+	return t.write(w)
+}
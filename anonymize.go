@@ -0,0 +1,126 @@
+package sales
+
+import (
+	"crypto/sha256"
+	"encoding/csv"
+	"encoding/hex"
+	"fmt"
+	"io"
+	"strings"
+)
+
+// orderTotalColumns are the columns of a Squarespace export, other than
+// those named by Columns, that hold amounts of money.
+var orderTotalColumns = []string{"Subtotal", "Shipping", "Total", "Discount Amount", "Amount Refunded"}
+
+// personalWords mark the columns that hold personal details, such as
+// "Billing Name" or "Shipping Address1", by the words in their names.
+var personalWords = []string{"name", "address", "phone", "city", "zip", "postcode", "company", "note", "reference"}
+
+// Anonymizer rewrites a CSV export so that it can be shared, for example
+// with a bug report, without giving away who bought what, or for how much.
+// Its structure, including its columns, rows, and which rows belong to the
+// same customer, is kept, so that whatever the export showed, the
+// anonymized export shows too.
+type Anonymizer struct {
+	// Columns names the export's columns, as for Report; any not set are
+	// taken from DefaultColumns.
+	Columns Columns
+	Dialect Dialect
+	// Salt is mixed into the hashes of email addresses, so that they
+	// can't be recovered by hashing likely addresses. Using the same salt
+	// gives the same hashes.
+	Salt string
+	// PriceScale, if positive, multiplies every amount of money, so that
+	// the real prices and totals aren't given away. Its value should be
+	// kept private.
+	PriceScale float64
+}
+
+// Anonymize reads a CSV export from rd and writes it to w with:
+//
+//   - the local part of each email address replaced by a salted hash, so
+//     that the domain, and which orders share a customer, are kept;
+//   - each value in a column of personal details, such as names,
+//     addresses, and phone numbers, replaced by the column's name and a
+//     number, the same for the same value;
+//   - amounts of money multiplied by PriceScale, if it's set.
+//
+// Personal details are recognised by their column names, such as "Billing
+// Name", and product names, by Columns, are kept.
+func (a Anonymizer) Anonymize(rd io.Reader, w io.Writer) error {
+	cols := a.Columns.withDefaults()
+	cr, err := a.Dialect.newReader(rd)
+	if err != nil {
+		return err
+	}
+	header, err := cr.Read()
+	if err != nil {
+		return err
+	}
+	cw := csv.NewWriter(w)
+	if a.Dialect.Delimiter != 0 {
+		cw.Comma = a.Dialect.Delimiter
+	}
+	cw.Write(header)
+	money := map[string]bool{cols.Price: true, cols.Total: true, cols.Fee: true, cols.Tax: true, cols.AffiliateFee: true}
+	for _, name := range orderTotalColumns {
+		money[name] = true
+	}
+	kept := map[string]bool{cols.Name: true, cols.Channel: true, cols.Variant: true}
+	masks := map[string]map[string]string{}
+	for {
+		row, err := cr.Read()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return err
+		}
+		for i, v := range row {
+			if v == "" || i >= len(header) {
+				continue
+			}
+			switch name := header[i]; {
+			case name == cols.Email:
+				row[i] = a.hashEmail(v)
+			case money[name] && a.PriceScale > 0:
+				if u, err := ParseUSD(v); err == nil {
+					row[i] = u.Mul(a.PriceScale).String()
+				}
+			case !kept[name] && personal(name):
+				if masks[name] == nil {
+					masks[name] = map[string]string{}
+				}
+				if _, ok := masks[name][v]; !ok {
+					masks[name][v] = fmt.Sprintf("%s %d", name, len(masks[name])+1)
+				}
+				row[i] = masks[name][v]
+			}
+		}
+		cw.Write(row)
+	}
+	cw.Flush()
+	return cw.Error()
+}
+
+// hashEmail returns email with its local part replaced by a salted hash.
+func (a Anonymizer) hashEmail(email string) string {
+	local, domain, _ := strings.Cut(strings.ToLower(strings.TrimSpace(email)), "@")
+	sum := sha256.Sum256([]byte(a.Salt + local))
+	if domain == "" {
+		return hex.EncodeToString(sum[:6])
+	}
+	return hex.EncodeToString(sum[:6]) + "@" + domain
+}
+
+// personal reports whether the column called name holds personal details.
+func personal(name string) bool {
+	name = strings.ToLower(name)
+	for _, word := range personalWords {
+		if strings.Contains(name, word) {
+			return true
+		}
+	}
+	return false
+}
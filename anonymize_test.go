@@ -0,0 +1,71 @@
+package sales_test
+
+import (
+	"encoding/csv"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+const exportToAnonymize = `Order ID,Email,Billing Name,Billing Address1,Lineitem name,Lineitem price,Lineitem quantity,Total
+1,Ann@Example.com,Ann Smith,1 High St,Go Book,10.00,1,15.00
+1,ann@example.com,Ann Smith,1 High St,Go Video,5.00,1,15.00
+2,bob@example.com,Bob Jones,,Go Book,10.00,2,20.00
+`
+
+// anonymize returns the rows of exportToAnonymize, after the header, as
+// anonymized by a.
+func anonymize(t *testing.T, a sales.Anonymizer) [][]string {
+	t.Helper()
+	var b strings.Builder
+	if err := a.Anonymize(strings.NewReader(exportToAnonymize), &b); err != nil {
+		t.Fatal(err)
+	}
+	rows, err := csv.NewReader(strings.NewReader(b.String())).ReadAll()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(rows) != 4 {
+		t.Fatalf("want header and 3 rows, got %q", rows)
+	}
+	return rows[1:]
+}
+
+func TestAnonymizeMasksPersonalDetailsButKeepsStructure(t *testing.T) {
+	t.Parallel()
+	rows := anonymize(t, sales.Anonymizer{Salt: "pepper", PriceScale: 2})
+	want := [][]string{
+		{"1", "", "Billing Name 1", "Billing Address1 1", "Go Book", "20.00", "1", "30.00"},
+		{"1", "", "Billing Name 1", "Billing Address1 1", "Go Video", "10.00", "1", "30.00"},
+		{"2", "", "Billing Name 2", "", "Go Book", "20.00", "2", "40.00"},
+	}
+	for i, row := range rows {
+		email := row[1]
+		if !strings.HasSuffix(email, "@example.com") || strings.Contains(email, "ann") || strings.Contains(email, "bob") {
+			t.Errorf("row %d: want a hashed address at example.com, got %q", i, email)
+		}
+		row[1] = ""
+		if strings.Join(row, ",") != strings.Join(want[i], ",") {
+			t.Errorf("row %d:\nwant %q\ngot  %q", i, want[i], row)
+		}
+	}
+}
+
+func TestAnonymizeHashesTheSameCustomerAlike(t *testing.T) {
+	t.Parallel()
+	rows := anonymize(t, sales.Anonymizer{Salt: "pepper"})
+	if rows[0][1] != rows[1][1] {
+		t.Errorf("want the same hash for the same customer, got %q and %q", rows[0][1], rows[1][1])
+	}
+	if rows[0][1] == rows[2][1] {
+		t.Errorf("want different hashes for different customers, got %q for both", rows[0][1])
+	}
+	if rows[0][5] != "10.00" {
+		t.Errorf("want prices unchanged without PriceScale, got %q", rows[0][5])
+	}
+	salted := anonymize(t, sales.Anonymizer{Salt: "salt"})
+	if salted[0][1] == rows[0][1] {
+		t.Errorf("want a different hash with a different salt, got %q for both", rows[0][1])
+	}
+}
@@ -0,0 +1,104 @@
+package sales
+
+import (
+	"compress/gzip"
+	"encoding/csv"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"slices"
+)
+
+// AppendArchive appends the line items kept by KeepRecords to the archive
+// at path, creating it if need be, and returns how many were appended. The
+// archive is a gzip-compressed CSV file in the canonical format written by
+// WriteNormalizedCSV, so it keeps a single history of every sale, whatever
+// platform it came from, and can be read like any other export: ReadFile
+// decompresses files ending in .gz. Orders already in the archive, by order
+// ID, are skipped, so that overlapping exports are only archived once.
+// Records without an order ID are always appended. The records are as the
+// report counted them, after any Renames and Bundles, so leave those unset
+// to archive the exports as they were.
+//
+// Each call adds a new gzip member to the end of the file, rather than
+// rewriting what's there.
+func (r *Report) AppendArchive(path string) (int, error) {
+	archived, err := archivedOrders(path)
+	if err != nil {
+		return 0, fmt.Errorf("%s: %w", path, err)
+	}
+	var rows [][]string
+	err = r.EachRecord(func(rec Record) error {
+		if rec.OrderID == "" || !archived[rec.OrderID] {
+			rows = append(rows, r.normalizedRow(rec))
+		}
+		return nil
+	})
+	if err != nil {
+		return 0, err
+	}
+	if len(rows) == 0 {
+		return 0, nil
+	}
+	if archived == nil {
+		rows = slices.Insert(rows, 0, normalizedHeader)
+	}
+	f, err := os.OpenFile(path, os.O_WRONLY|os.O_APPEND|os.O_CREATE, 0o644)
+	if err != nil {
+		return 0, err
+	}
+	zw := gzip.NewWriter(f)
+	cw := csv.NewWriter(zw)
+	cw.WriteAll(rows)
+	err = errors.Join(cw.Error(), zw.Close(), f.Close())
+	if err != nil {
+		return 0, fmt.Errorf("%s: %w", path, err)
+	}
+	if archived == nil {
+		return len(rows) - 1, nil
+	}
+	return len(rows), nil
+}
+
+// archivedOrders returns the set of order IDs in the archive at path, or
+// nil if there's no archive yet.
+func archivedOrders(path string) (map[string]bool, error) {
+	f, err := os.Open(path)
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	zr, err := gzip.NewReader(f)
+	if err == io.EOF {
+		// An empty file is an archive with nothing in it yet.
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	cr := csv.NewReader(zr)
+	header, err := cr.Read()
+	if err != nil {
+		return nil, err
+	}
+	id := slices.Index(header, NormalizedColumns.OrderID)
+	if id < 0 {
+		return nil, fmt.Errorf("not an archive: missing column %q", NormalizedColumns.OrderID)
+	}
+	orders := map[string]bool{}
+	for {
+		row, err := cr.Read()
+		if err == io.EOF {
+			return orders, nil
+		}
+		if err != nil {
+			return nil, err
+		}
+		orders[row[id]] = true
+	}
+}
@@ -0,0 +1,230 @@
+package sales
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// DefaultBulkQuantity is the number of units in an order from which B2BRules
+// take it to be a bulk purchase by a business, unless MinQuantity is set.
+const DefaultBulkQuantity = 10
+
+// UnidentifiedCompany is the company that business orders are credited to
+// when neither the billing name nor the email domain identify one, as for
+// a bulk order from a gmail.com address.
+const UnidentifiedCompany = "(unidentified)"
+
+// B2BRules tell orders from businesses apart from orders from consumers.
+// An order is a business order if it's from one of Companies or Domains,
+// if its billing name looks like a company's, such as "Acme Ltd", or if it
+// has at least MinQuantity units.
+type B2BRules struct {
+	// Domains are the email domains of known business customers.
+	Domains []string
+	// Companies are the billing names of known business customers,
+	// ignoring case.
+	Companies []string
+	// MinQuantity is the number of units in an order from which it's a
+	// bulk purchase. If zero, DefaultBulkQuantity is used.
+	MinQuantity int
+}
+
+// companyWords are words in billing names that suggest a company or other
+// organisation, rather than a person.
+var companyWords = map[string]bool{
+	"ltd": true, "limited": true, "llc": true, "llp": true, "inc": true,
+	"incorporated": true, "corp": true, "corporation": true, "company": true, "plc": true,
+	"gmbh": true, "ag": true, "bv": true, "nv": true, "sarl": true,
+	"srl": true, "pty": true, "kk": true, "university": true, "college": true,
+	"school": true, "library": true, "institute": true,
+}
+
+// looksLikeCompany reports whether a billing name contains any of the
+// companyWords, such as "Ltd" or "GmbH".
+func looksLikeCompany(name string) bool {
+	for _, word := range strings.Fields(strings.ToLower(name)) {
+		if companyWords[strings.Trim(word, ".,()")] {
+			return true
+		}
+	}
+	return false
+}
+
+// company returns the company that placed order, all of whose line items
+// share the same customer and billing name, or false if it's a consumer
+// order. The company is named by one of Companies, the billing name if it
+// looks like a company's, or else the email domain, unless that's a
+// webmail provider's.
+func (b B2BRules) company(order []Record) (string, bool) {
+	name := order[0].BillingName
+	_, domain, _ := strings.Cut(order[0].Customer, "@")
+	domain = strings.ToLower(domain)
+	for _, c := range b.Companies {
+		if strings.EqualFold(name, c) {
+			return c, true
+		}
+	}
+	business := looksLikeCompany(name)
+	for _, d := range b.Domains {
+		if strings.EqualFold(domain, d) {
+			business = true
+		}
+	}
+	bulk := b.MinQuantity
+	if bulk == 0 {
+		bulk = DefaultBulkQuantity
+	}
+	units := 0
+	for _, rec := range order {
+		units += rec.Quantity
+	}
+	if !business && units < bulk {
+		return "", false
+	}
+	switch {
+	case looksLikeCompany(name):
+		return name, true
+	case domain != "" && !IsWebmail(domain):
+		return domain, true
+	}
+	return UnidentifiedCompany, true
+}
+
+// BuyerTotal is the number of orders from one kind of buyer, and the units
+// and revenue from them.
+type BuyerTotal struct {
+	Orders int
+	Tally
+}
+
+// CompanyTotal is the orders placed by one company, and the units and
+// revenue from them.
+type CompanyTotal struct {
+	Company string
+	BuyerTotal
+}
+
+// B2BSplit divides a report's sales between business and consumer orders,
+// as told apart by B2BRules.
+type B2BSplit struct {
+	Business BuyerTotal
+	Consumer BuyerTotal
+	// Companies are the business customers, largest revenue first.
+	Companies []CompanyTotal
+}
+
+// B2B divides the sales between orders from businesses and from consumers,
+// according to rules. Line items without an order ID are orders of their
+// own. It needs KeepRecords.
+func (r *Report) B2B(rules B2BRules) (B2BSplit, error) {
+	if !r.KeepRecords {
+		return B2BSplit{}, errors.New("B2B analysis needs KeepRecords")
+	}
+	type orderKey struct {
+		source, id string
+		n          int
+	}
+	orders := map[orderKey][]Record{}
+	var keys []orderKey
+	n := 0
+	err := r.EachRecord(func(rec Record) error {
+		k := orderKey{source: rec.Source, id: rec.OrderID}
+		if rec.OrderID == "" {
+			n++
+			k.n = n
+		}
+		if _, ok := orders[k]; !ok {
+			keys = append(keys, k)
+		}
+		orders[k] = append(orders[k], rec)
+		return nil
+	})
+	if err != nil {
+		return B2BSplit{}, err
+	}
+	var split B2BSplit
+	companies := map[string]*CompanyTotal{}
+	for _, k := range keys {
+		order := orders[k]
+		var t Tally
+		for _, rec := range order {
+			t.Units += rec.Quantity
+			t.Revenue += rec.Total()
+		}
+		company, ok := rules.company(order)
+		if !ok {
+			split.Consumer.add(t)
+			continue
+		}
+		split.Business.add(t)
+		c := companies[company]
+		if c == nil {
+			c = &CompanyTotal{Company: company}
+			companies[company] = c
+		}
+		c.add(t)
+	}
+	for _, c := range companies {
+		split.Companies = append(split.Companies, *c)
+	}
+	sort.Slice(split.Companies, func(i, j int) bool {
+		a, b := split.Companies[i], split.Companies[j]
+		if a.Revenue != b.Revenue {
+			return a.Revenue > b.Revenue
+		}
+		return a.Company < b.Company
+	})
+	return split, nil
+}
+
+func (bt *BuyerTotal) add(t Tally) {
+	bt.Orders++
+	bt.Units += t.Units
+	bt.Revenue += t.Revenue
+}
+
+// WriteB2B writes the split of revenue between business and consumer
+// orders, followed by the top companies by revenue, or all of them if top
+// is zero.
+func (r *Report) WriteB2B(w io.Writer, rules B2BRules, top int) error {
+	split, err := r.B2B(rules)
+	if err != nil {
+		return err
+	}
+	total := split.Business.Revenue + split.Consumer.Revenue
+	t := &table{
+		header: []string{"Buyer", "Orders", "Units", "Revenue", "Share"},
+		right:  []bool{false, true, true, true, true},
+		color:  r.Color,
+	}
+	for _, row := range []struct {
+		name string
+		BuyerTotal
+	}{{"Business", split.Business}, {"Consumer", split.Consumer}} {
+		t.add(row.name, strconv.Itoa(row.Orders), strconv.Itoa(row.Units), row.Revenue.String(), percent(row.Revenue, total))
+	}
+	if err := t.write(w); err != nil {
+		return err
+	}
+	if len(split.Companies) == 0 {
+		return nil
+	}
+	companies := split.Companies
+	if top > 0 && len(companies) > top {
+		companies = companies[:top]
+	}
+	fmt.Fprintln(w)
+	t = &table{
+		header: []string{"Company", "Orders", "Units", "Revenue"},
+		right:  []bool{false, true, true, true},
+		color:  r.Color,
+	}
+	for _, c := range companies {
+		t.add(c.Company, strconv.Itoa(c.Orders), strconv.Itoa(c.Units), c.Revenue.String())
+	}
+	return t.write(w)
+}
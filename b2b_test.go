@@ -0,0 +1,64 @@
+package sales_test
+
+import (
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestB2BSplitsOrdersBetweenBusinessesAndConsumers(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	for _, rec := range []sales.Record{
+		{OrderID: "1", BillingName: "Acme Ltd.", Customer: "buyer@gmail.com", Product: "Book", Quantity: 1, Price: sales.NewUSD(40)},
+		{OrderID: "2", BillingName: "Jo Bloggs", Customer: "jo@example.org", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)},
+		{OrderID: "2", BillingName: "Jo Bloggs", Customer: "jo@example.org", Product: "Video", Quantity: 2, Price: sales.NewUSD(10)},
+		{OrderID: "3", BillingName: "WIDGETS CO-OP", Customer: "orders@widgets.coop", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)},
+		{OrderID: "4", BillingName: "Sam Smith", Customer: "sam@gmail.com", Product: "Book", Quantity: 5, Price: sales.NewUSD(10)},
+		{OrderID: "5", BillingName: "Pat Jones", Customer: "pat@gmail.com", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)},
+		{BillingName: "Pat Jones", Customer: "pat@gmail.com", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)},
+		{BillingName: "Pat Jones", Customer: "pat@gmail.com", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)},
+	} {
+		r.Add(rec)
+	}
+	split, err := r.B2B(sales.B2BRules{
+		Domains:     []string{"Example.org"},
+		Companies:   []string{"Widgets Co-op"},
+		MinQuantity: 5,
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	wantBusiness := sales.BuyerTotal{Orders: 4, Tally: sales.Tally{Units: 10, Revenue: sales.NewUSD(130)}}
+	if split.Business != wantBusiness {
+		t.Errorf("business: want %+v, got %+v", wantBusiness, split.Business)
+	}
+	wantConsumer := sales.BuyerTotal{Orders: 3, Tally: sales.Tally{Units: 3, Revenue: sales.NewUSD(30)}}
+	if split.Consumer != wantConsumer {
+		t.Errorf("consumer: want %+v, got %+v", wantConsumer, split.Consumer)
+	}
+	want := []sales.CompanyTotal{
+		{Company: sales.UnidentifiedCompany, BuyerTotal: sales.BuyerTotal{Orders: 1, Tally: sales.Tally{Units: 5, Revenue: sales.NewUSD(50)}}},
+		{Company: "Acme Ltd.", BuyerTotal: sales.BuyerTotal{Orders: 1, Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(40)}}},
+		{Company: "example.org", BuyerTotal: sales.BuyerTotal{Orders: 1, Tally: sales.Tally{Units: 3, Revenue: sales.NewUSD(30)}}},
+		{Company: "Widgets Co-op", BuyerTotal: sales.BuyerTotal{Orders: 1, Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(10)}}},
+	}
+	if len(split.Companies) != len(want) {
+		t.Fatalf("want %d companies, got %+v", len(want), split.Companies)
+	}
+	for i := range want {
+		if split.Companies[i] != want[i] {
+			t.Errorf("company %d: want %+v, got %+v", i, want[i], split.Companies[i])
+		}
+	}
+}
+
+func TestB2BNeedsKeepRecords(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Add(sales.Record{OrderID: "1", BillingName: "Acme Ltd", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+	if _, err := r.B2B(sales.B2BRules{}); err == nil {
+		t.Error("want error, got nil")
+	}
+}
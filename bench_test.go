@@ -0,0 +1,97 @@
+package sales_test
+
+import (
+	"bytes"
+	"io"
+	"testing"
+
+	"github.com/bitfield/sales"
+	"github.com/bitfield/sales/salestest"
+)
+
+// The benchmarks read, group, and render generated data, so that changes
+// meant to speed them up can be measured. To check a change for
+// regressions, compare runs before and after it with benchstat:
+//
+//	go test -run=^$ -bench=. -count=10 > old.txt
+//	# make the change
+//	go test -run=^$ -bench=. -count=10 > new.txt
+//	benchstat old.txt new.txt
+
+const benchRows = 10_000
+
+func benchCSV(b *testing.B) []byte {
+	b.Helper()
+	var buf bytes.Buffer
+	if err := salestest.WriteSquarespaceCSV(&buf, salestest.Records(benchRows, 1)); err != nil {
+		b.Fatal(err)
+	}
+	return buf.Bytes()
+}
+
+func BenchmarkReadCSV(b *testing.B) {
+	data := benchCSV(b)
+	b.SetBytes(int64(len(data)))
+	b.ReportAllocs()
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		r := sales.NewReport()
+		if err := r.ReadCSV(bytes.NewReader(data), "bench.csv"); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
+
+func BenchmarkReadCSVGrouped(b *testing.B) {
+	data := benchCSV(b)
+	groups := salestest.Groups()
+	b.SetBytes(int64(len(data)))
+	b.ReportAllocs()
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		r := sales.NewReport()
+		r.Groups = groups
+		if err := r.ReadCSV(bytes.NewReader(data), "bench.csv"); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
+
+func BenchmarkClassifyItem(b *testing.B) {
+	recs := salestest.Records(benchRows, 1)
+	groups := salestest.Groups()
+	b.ReportAllocs()
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		rec := recs[i%len(recs)]
+		groups.ClassifyItem(rec.Product, rec.SKU)
+	}
+}
+
+func BenchmarkWriteText(b *testing.B) {
+	r := sales.NewReport()
+	for _, rec := range salestest.Records(benchRows, 1) {
+		r.Add(rec)
+	}
+	b.ReportAllocs()
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		if err := r.WriteText(io.Discard); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
+
+func BenchmarkWriteJSON(b *testing.B) {
+	r := sales.NewReport()
+	for _, rec := range salestest.Records(benchRows, 1) {
+		r.Add(rec)
+	}
+	b.ReportAllocs()
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		if err := r.WriteJSON(io.Discard); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
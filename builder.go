@@ -0,0 +1,132 @@
+package sales
+
+import (
+	"errors"
+	"log/slog"
+	"time"
+)
+
+// ReportBuilder configures a Report step by step, then builds it, reading
+// any sources in one go. Each method returns the builder, so that calls
+// can be chained:
+//
+//	r, err := sales.NewReportBuilder().
+//		GroupsFile("groups.txt").
+//		Sort(sales.SortByRevenue).
+//		Source("orders.csv").
+//		Build()
+//
+// Errors, such as an unreadable groups file, are reported by Build.
+type ReportBuilder struct {
+	r          *Report
+	groupsFile string
+	sources    []string
+}
+
+// NewReportBuilder returns a builder for a report with the default
+// settings.
+func NewReportBuilder() *ReportBuilder {
+	return &ReportBuilder{r: NewReport()}
+}
+
+// Groups sets the product groups.
+func (b *ReportBuilder) Groups(g Groups) *ReportBuilder {
+	b.r.Groups = g
+	return b
+}
+
+// GroupsFile sets the product groups from the groups file at path.
+func (b *ReportBuilder) GroupsFile(path string) *ReportBuilder {
+	b.groupsFile = path
+	return b
+}
+
+// Config applies the settings from cfg, as Report.Configure does, and its
+// groups file, if it names one.
+func (b *ReportBuilder) Config(cfg Config) *ReportBuilder {
+	b.r.Configure(cfg)
+	if cfg.GroupsFile != "" {
+		b.groupsFile = cfg.GroupsFile
+	}
+	return b
+}
+
+// Sort sets the order of the report rows.
+func (b *ReportBuilder) Sort(key SortKey) *ReportBuilder {
+	b.r.Sort = key
+	return b
+}
+
+// Currency sets the currency that input amounts are expected to be in.
+func (b *ReportBuilder) Currency(code string) *ReportBuilder {
+	b.r.Currency = code
+	return b
+}
+
+// DateRange restricts the report to records timestamped in [from, to).
+func (b *ReportBuilder) DateRange(from, to time.Time) *ReportBuilder {
+	b.r.From, b.r.To = from, to
+	return b
+}
+
+// Filter sets a function that decides which records to include.
+func (b *ReportBuilder) Filter(f func(Record) bool) *ReportBuilder {
+	b.r.Filter = f
+	return b
+}
+
+// Logger sets the logger to which the report describes its work.
+func (b *ReportBuilder) Logger(l *slog.Logger) *ReportBuilder {
+	b.r.Logger = l
+	return b
+}
+
+// Period sets how sales are grouped over time.
+func (b *ReportBuilder) Period(p Period) *ReportBuilder {
+	b.r.Period = p
+	return b
+}
+
+// SkipBadRows skips malformed rows in the sources instead of failing.
+func (b *ReportBuilder) SkipBadRows() *ReportBuilder {
+	b.r.SkipBadRows = true
+	return b
+}
+
+// Dedupe counts each order ID only once.
+func (b *ReportBuilder) Dedupe() *ReportBuilder {
+	b.r.Dedupe = true
+	return b
+}
+
+// Source adds the export at path to the files read by Build.
+func (b *ReportBuilder) Source(path string) *ReportBuilder {
+	b.sources = append(b.sources, path)
+	return b
+}
+
+// Build loads the groups file, if any, reads the sources in the order they
+// were added, and resolves conflicts between them. It returns the report,
+// ready for more input or for output.
+func (b *ReportBuilder) Build() (*Report, error) {
+	if b.r == nil {
+		return nil, errors.New("ReportBuilder used without NewReportBuilder")
+	}
+	r := b.r
+	if b.groupsFile != "" {
+		g, err := LoadGroups(b.groupsFile)
+		if err != nil {
+			return nil, err
+		}
+		r.Groups = g
+	}
+	for _, path := range b.sources {
+		if err := r.ReadFile(path); err != nil {
+			return nil, err
+		}
+	}
+	if err := r.Resolve(); err != nil {
+		return nil, err
+	}
+	return r, nil
+}
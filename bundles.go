@@ -0,0 +1,79 @@
+package sales
+
+import (
+	"fmt"
+	"math"
+	"strconv"
+	"strings"
+)
+
+// BundlePart is one of the products in a bundle, and the fraction of the
+// bundle's price allocated to it.
+type BundlePart struct {
+	Product string
+	Share   float64
+}
+
+// Bundles maps the names of bundle products to their contents. Sales of a
+// bundle are counted as sales of each of its parts instead, with the
+// bundle's price divided among them.
+type Bundles map[string][]BundlePart
+
+// ParseBundle parses the contents of a bundle, written as products and
+// percentages of the price, such as "Book 40%, Video 60%". The percentages
+// must add up to 100.
+func ParseBundle(s string) ([]BundlePart, error) {
+	var parts []BundlePart
+	total := 0.0
+	for _, item := range strings.Split(s, ",") {
+		item = strings.TrimSpace(item)
+		i := strings.LastIndex(item, " ")
+		if i < 0 || !strings.HasSuffix(item, "%") {
+			return nil, fmt.Errorf("want product and percentage, such as \"Book 40%%\", got %q", item)
+		}
+		pct, err := strconv.ParseFloat(strings.TrimSuffix(item[i+1:], "%"), 64)
+		if err != nil || pct <= 0 {
+			return nil, fmt.Errorf("invalid percentage in %q", item)
+		}
+		parts = append(parts, BundlePart{Product: strings.TrimSpace(item[:i]), Share: pct / 100})
+		total += pct
+	}
+	if math.Abs(total-100) > 0.001 {
+		return nil, fmt.Errorf("percentages add up to %g, not 100", total)
+	}
+	return parts, nil
+}
+
+// split divides rec, a sale of a bundle, into sales of each of the bundle's
+// parts. The parts' prices are rounded to a hundredth of a cent, with any
+// difference given to the last part, so that the total is unchanged.
+func (b Bundles) split(rec Record) []Record {
+	parts, ok := b[rec.Product]
+	if !ok {
+		return []Record{rec}
+	}
+	records := make([]Record, len(parts))
+	// The amounts are shared out between the parts, with the last part
+	// taking whatever is left after rounding.
+	remaining := rec
+	for i, p := range parts {
+		records[i] = rec
+		records[i].Product = p.Product
+		if i == len(parts)-1 {
+			records[i].Price = remaining.Price
+			records[i].Fee = remaining.Fee
+			records[i].AffiliateFee = remaining.AffiliateFee
+			records[i].Tax = remaining.Tax
+			break
+		}
+		records[i].Price = rec.Price.Mul(p.Share)
+		records[i].Fee = rec.Fee.Mul(p.Share)
+		records[i].AffiliateFee = rec.AffiliateFee.Mul(p.Share)
+		records[i].Tax = rec.Tax.Mul(p.Share)
+		remaining.Price -= records[i].Price
+		remaining.Fee -= records[i].Fee
+		remaining.AffiliateFee -= records[i].AffiliateFee
+		remaining.Tax -= records[i].Tax
+	}
+	return records
+}
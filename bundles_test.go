@@ -0,0 +1,80 @@
+package sales_test
+
+import (
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestParseBundleReadsProductsAndPercentages(t *testing.T) {
+	t.Parallel()
+	got, err := sales.ParseBundle("Big Book 40%, Video 60%")
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := []sales.BundlePart{{Product: "Big Book", Share: 0.4}, {Product: "Video", Share: 0.6}}
+	if len(got) != len(want) {
+		t.Fatalf("want %+v, got %+v", want, got)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("part %d: want %+v, got %+v", i, want[i], got[i])
+		}
+	}
+}
+
+func TestParseBundleRejectsInvalidContents(t *testing.T) {
+	t.Parallel()
+	for _, s := range []string{
+		"Book",
+		"Book 40",
+		"Book x%, Video 60%",
+		"Book 0%, Video 100%",
+		"Book -10%, Video 110%",
+		"Book 40%, Video 50%",
+	} {
+		if _, err := sales.ParseBundle(s); err == nil {
+			t.Errorf("%q: want error, got nil", s)
+		}
+	}
+}
+
+func TestBundleSalesAreCountedAsTheirPartsWithTheSameTotal(t *testing.T) {
+	t.Parallel()
+	parts, err := sales.ParseBundle("Book 33.3333%, Video 66.6667%")
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Bundles = sales.Bundles{"Book and Video": parts}
+	r.Add(sales.Record{OrderID: "1", Product: "Book and Video", Quantity: 1, Price: sales.NewUSD(10), Fee: sales.NewUSD(1)})
+	r.Add(sales.Record{OrderID: "2", Product: "Book", Quantity: 1, Price: sales.NewUSD(5)})
+	revenue := map[string]sales.USD{}
+	for _, p := range r.Products() {
+		revenue[p.Name] = p.Revenue
+	}
+	if len(revenue) != 2 {
+		t.Fatalf("want Book and Video only, got %v", revenue)
+	}
+	if got, want := revenue["Book"], sales.NewUSD(8.33); got != want {
+		t.Errorf("Book: want %v, got %v", want, got)
+	}
+	if got, want := revenue["Video"], sales.NewUSD(6.67); got != want {
+		t.Errorf("Video: want %v, got %v", want, got)
+	}
+	records, err := r.Records()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var price, fee sales.USD
+	for _, rec := range records {
+		if rec.OrderID == "1" {
+			price += rec.Price
+			fee += rec.Fee
+		}
+	}
+	if price != sales.NewUSD(10) || fee != sales.NewUSD(1) {
+		t.Errorf("want the parts to add up to $10.00 price and $1.00 fee, got %v and %v", price, fee)
+	}
+}
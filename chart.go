@@ -0,0 +1,213 @@
+package sales
+
+import (
+	"bufio"
+	"fmt"
+	"html"
+	"image"
+	"image/color"
+	"image/draw"
+	"image/png"
+	"io"
+)
+
+// point is a labelled value in a chart.
+type point struct {
+	label string
+	value USD
+}
+
+// Chart dimensions, in pixels.
+const (
+	chartWidth  = 800
+	chartHeight = 400
+	chartMargin = 40
+	labelWidth  = 200
+	barHeight   = 24
+	barGap      = 6
+)
+
+var (
+	chartBackground = color.RGBA{0xff, 0xff, 0xff, 0xff}
+	chartInk        = color.RGBA{0x33, 0x33, 0x33, 0xff}
+	chartFill       = color.RGBA{0x44, 0x77, 0xaa, 0xff}
+)
+
+// WriteBarChart draws a horizontal bar chart of revenue by product, in the
+// given image format: "svg" or "png". PNG charts have no text labels, so
+// SVG is better for most purposes.
+func (r *Report) WriteBarChart(w io.Writer, format string) error {
+	var points []point
+	for _, p := range r.Products() {
+		points = append(points, point{label: p.Name, value: p.Revenue})
+	}
+	var limit USD
+	for _, p := range points {
+		limit = max(limit, p.value)
+	}
+	height := 2*chartMargin + len(points)*(barHeight+barGap)
+	c, err := newCanvas(format, chartWidth, height)
+	if err != nil {
+		return err
+	}
+	plot := chartWidth - 2*chartMargin - labelWidth
+	for i, p := range points {
+		y := chartMargin + i*(barHeight+barGap)
+		length := 0
+		if limit > 0 && p.value > 0 {
+			length = int(int64(plot) * int64(p.value) / int64(limit))
+		}
+		c.rect(chartMargin+labelWidth, y, length, barHeight, chartFill)
+		c.text(chartMargin+labelWidth-8, y+barHeight*3/4, "end", p.label)
+		c.text(chartMargin+labelWidth+length+8, y+barHeight*3/4, "start", p.value.String())
+	}
+	c.line(chartMargin+labelWidth, chartMargin, chartMargin+labelWidth, height-chartMargin)
+	return c.write(w)
+}
+
+// WriteLineChart draws a line chart of revenue by period, in the given image
+// format: "svg" or "png".
+func (r *Report) WriteLineChart(w io.Writer, format string) error {
+	var points []point
+	for _, p := range r.ByPeriod() {
+		points = append(points, point{label: p.Period, value: p.Revenue})
+	}
+	var limit USD
+	for _, p := range points {
+		limit = max(limit, p.value)
+	}
+	c, err := newCanvas(format, chartWidth, chartHeight)
+	if err != nil {
+		return err
+	}
+	left, right := chartMargin+labelWidth/2, chartWidth-chartMargin
+	top, bottom := chartMargin, chartHeight-chartMargin
+	c.line(left, top, left, bottom)
+	c.line(left, bottom, right, bottom)
+	c.text(left-8, top+4, "end", limit.String())
+	c.text(left-8, bottom, "end", "0.00")
+	x := func(i int) int {
+		if len(points) == 1 {
+			return (left + right) / 2
+		}
+		return left + i*(right-left)/(len(points)-1)
+	}
+	y := func(v USD) int {
+		if limit <= 0 || v <= 0 {
+			return bottom
+		}
+		return bottom - int(int64(bottom-top)*int64(v)/int64(limit))
+	}
+	for i, p := range points {
+		if i > 0 {
+			c.line(x(i-1), y(points[i-1].value), x(i), y(p.value))
+		}
+		c.rect(x(i)-3, y(p.value)-3, 6, 6, chartFill)
+		c.text(x(i), bottom+20, "middle", p.label)
+	}
+	return c.write(w)
+}
+
+// canvas is a drawing surface for charts, in one of the supported image
+// formats.
+type canvas interface {
+	rect(x, y, w, h int, fill color.RGBA)
+	line(x1, y1, x2, y2 int)
+	// text draws s with its baseline at y, anchored at x by its "start",
+	// "middle", or "end".
+	text(x, y int, anchor, s string)
+	write(w io.Writer) error
+}
+
+func newCanvas(format string, width, height int) (canvas, error) {
+	switch format {
+	case "svg":
+		return &svgCanvas{width: width, height: height}, nil
+	case "png":
+		img := image.NewRGBA(image.Rect(0, 0, width, height))
+		draw.Draw(img, img.Bounds(), image.NewUniform(chartBackground), image.Point{}, draw.Src)
+		return &pngCanvas{img: img}, nil
+	}
+	return nil, fmt.Errorf("unknown chart format %q (want svg or png)", format)
+}
+
+type svgCanvas struct {
+	width, height int
+	elems         []string
+}
+
+func (c *svgCanvas) rect(x, y, w, h int, fill color.RGBA) {
+	c.elems = append(c.elems, fmt.Sprintf(`<rect x="%d" y="%d" width="%d" height="%d" fill="#%02x%02x%02x"/>`, x, y, w, h, fill.R, fill.G, fill.B))
+}
+
+func (c *svgCanvas) line(x1, y1, x2, y2 int) {
+	c.elems = append(c.elems, fmt.Sprintf(`<line x1="%d" y1="%d" x2="%d" y2="%d" stroke="#%02x%02x%02x" stroke-width="2"/>`, x1, y1, x2, y2, chartInk.R, chartInk.G, chartInk.B))
+}
+
+func (c *svgCanvas) text(x, y int, anchor, s string) {
+	c.elems = append(c.elems, fmt.Sprintf(`<text x="%d" y="%d" text-anchor="%s">%s</text>`, x, y, anchor, html.EscapeString(s)))
+}
+
+func (c *svgCanvas) write(w io.Writer) error {
+	bw := bufio.NewWriter(w)
+	fmt.Fprintf(bw, `<svg xmlns="http://www.w3.org/2000/svg" width="%d" height="%d" font-family="sans-serif" font-size="12">`+"\n", c.width, c.height)
+	fmt.Fprintf(bw, `<rect width="100%%" height="100%%" fill="#%02x%02x%02x"/>`+"\n", chartBackground.R, chartBackground.G, chartBackground.B)
+	for _, e := range c.elems {
+		fmt.Fprintln(bw, e)
+	}
+	fmt.Fprintln(bw, "</svg>")
+	return bw.Flush()
+}
+
+// pngCanvas draws charts as PNG images. It has no font, so text is left
+// out.
+type pngCanvas struct {
+	img *image.RGBA
+}
+
+func (c *pngCanvas) rect(x, y, w, h int, fill color.RGBA) {
+	draw.Draw(c.img, image.Rect(x, y, x+w, y+h), image.NewUniform(fill), image.Point{}, draw.Src)
+}
+
+// line draws a two-pixel line using Bresenham's algorithm.
+func (c *pngCanvas) line(x1, y1, x2, y2 int) {
+	dx, dy := abs(x2-x1), -abs(y2-y1)
+	sx, sy := 1, 1
+	if x1 > x2 {
+		sx = -1
+	}
+	if y1 > y2 {
+		sy = -1
+	}
+	e := dx + dy
+	for {
+		c.img.SetRGBA(x1, y1, chartInk)
+		c.img.SetRGBA(x1+1, y1, chartInk)
+		c.img.SetRGBA(x1, y1+1, chartInk)
+		if x1 == x2 && y1 == y2 {
+			return
+		}
+		e2 := 2 * e
+		if e2 >= dy {
+			e += dy
+			x1 += sx
+		}
+		if e2 <= dx {
+			e += dx
+			y1 += sy
+		}
+	}
+}
+
+func (c *pngCanvas) text(x, y int, anchor, s string) {}
+
+func (c *pngCanvas) write(w io.Writer) error {
+	return png.Encode(w, c.img)
+}
+
+func abs(n int) int {
+	if n < 0 {
+		return -n
+	}
+	return n
+}
@@ -0,0 +1,86 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+)
+
+// Progress describes how far a chunked read has got.
+type Progress struct {
+	Source string
+	// Rows is the number of rows read from this input so far, and Bytes
+	// the number of bytes.
+	Rows  int64
+	Bytes int64
+	// TotalRows is the number of rows read into the report from all
+	// inputs, which is what limits.max_rows applies to.
+	TotalRows int64
+}
+
+// ReadCSVChunked is like ReadCSV, but calls progress after every chunkSize
+// rows, and once more when the input is finished. This lets programs that
+// embed the report, such as GUIs and servers, keep their event loops
+// responsive during large imports, or enforce quotas of their own, without
+// running the read on a separate goroutine. If progress returns an error,
+// the read stops and that error is returned.
+func (r *Report) ReadCSVChunked(rd io.Reader, source string, chunkSize int, progress func(Progress) error) error {
+	if chunkSize <= 0 {
+		return fmt.Errorf("chunk size must be positive, got %d", chunkSize)
+	}
+	cr := &countingReader{r: rd}
+	r.chunk = &chunker{size: chunkSize, progress: progress, input: cr, p: Progress{Source: source}}
+	defer func() { r.chunk = nil }()
+	if err := r.readCSV(cr, source, r.SkipBadRows); err != nil {
+		return err
+	}
+	return r.chunk.report(r)
+}
+
+// chunker tracks the rows of a chunked read, reporting progress at the end
+// of each chunk.
+type chunker struct {
+	size     int
+	n        int
+	progress func(Progress) error
+	input    *countingReader
+	p        Progress
+}
+
+// report calls the progress function with the state of the read so far.
+func (c *chunker) report(r *Report) error {
+	c.n = 0
+	c.p.Bytes = c.input.n
+	c.p.TotalRows = r.rows
+	if err := c.progress(c.p); err != nil {
+		return fmt.Errorf("%s: %w", c.p.Source, err)
+	}
+	return nil
+}
+
+// countRow counts a row read from source, enforcing limits.max_rows and
+// reporting the progress of a chunked read.
+func (r *Report) countRow(source string) error {
+	r.rows++
+	if limit := r.Limits.MaxRows; limit > 0 && r.rows > limit {
+		return fmt.Errorf("%s: %w", source, &LimitError{Limit: "max_rows", Max: limit})
+	}
+	if c := r.chunk; c != nil {
+		c.p.Rows++
+		c.n++
+		if c.n == c.size {
+			return c.report(r)
+		}
+	}
+	return nil
+}
+
+type countingReader struct {
+	r io.Reader
+	n int64
+}
+
+func (c *countingReader) Read(p []byte) (int, error) {
+	n, err := c.r.Read(p)
+	c.n += int64(n)
+	return n, err
+}
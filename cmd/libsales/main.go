@@ -0,0 +1,149 @@
+// Command libsales builds the report as a C shared library, so that other
+// languages, such as Python through ctypes or cffi, can use the same
+// grouping and reading logic as the sales command. Build it with:
+//
+//	go build -buildmode=c-shared -o libsales.so ./cmd/libsales
+//
+// which also writes the C header libsales.h. A report is referred to by
+// the handle returned from report_new, and must be released with
+// report_free. Functions that can fail return NULL on success, or an error
+// message, including for a handle that is invalid or already freed. Every
+// string returned by the library must be released with
+// report_free_string.
+package main
+
+/*
+#include <stdint.h>
+#include <stdlib.h>
+*/
+import "C"
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"math"
+	"regexp"
+	"runtime/cgo"
+	"unsafe"
+
+	"github.com/bitfield/sales"
+)
+
+func main() {}
+
+//export report_new
+func report_new() C.uintptr_t {
+	return C.uintptr_t(cgo.NewHandle(sales.NewReport()))
+}
+
+// report_free releases the report with handle h. Like free, it does
+// nothing with a handle that is invalid or already freed.
+//
+//export report_free
+func report_free(h C.uintptr_t) {
+	defer func() { recover() }()
+	cgo.Handle(h).Delete()
+}
+
+//export report_free_string
+func report_free_string(s *C.char) {
+	C.free(unsafe.Pointer(s))
+}
+
+// report_add_group adds pattern, a regular expression, to the group name,
+// creating the group after any existing ones if it doesn't exist yet.
+//
+//export report_add_group
+func report_add_group(h C.uintptr_t, name, pattern *C.char) (msg *C.char) {
+	defer recoverError(&msg)
+	r, err := report(h)
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	re, err := regexp.Compile(C.GoString(pattern))
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	groupName := C.GoString(name)
+	for i := range r.Groups {
+		if r.Groups[i].Name == groupName {
+			r.Groups[i].Patterns = append(r.Groups[i].Patterns, re)
+			return nil
+		}
+	}
+	r.Groups = append(r.Groups, sales.Group{Name: groupName, Patterns: []*regexp.Regexp{re}})
+	return nil
+}
+
+// report_ingest_csv_bytes reads the n bytes of CSV data at data into the
+// report, naming the source for provenance and error messages.
+//
+//export report_ingest_csv_bytes
+func report_ingest_csv_bytes(h C.uintptr_t, data *C.char, n C.size_t, source *C.char) (msg *C.char) {
+	defer recoverError(&msg)
+	r, err := report(h)
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	if data == nil && n > 0 {
+		return C.CString("data is NULL")
+	}
+	// GoBytes takes a C int, so larger inputs would be truncated.
+	if n > math.MaxInt32 {
+		return C.CString(fmt.Sprintf("%d bytes of data is more than the limit of %d", uint64(n), math.MaxInt32))
+	}
+	b := C.GoBytes(unsafe.Pointer(data), C.int(n))
+	if err := r.ReadCSV(bytes.NewReader(b), C.GoString(source)); err != nil {
+		return C.CString(err.Error())
+	}
+	return nil
+}
+
+// report_to_json stores the report in JSON format in *out, or returns an
+// error.
+//
+//export report_to_json
+func report_to_json(h C.uintptr_t, out **C.char) (msg *C.char) {
+	defer recoverError(&msg)
+	if out == nil {
+		return C.CString("out is NULL")
+	}
+	r, err := report(h)
+	if err != nil {
+		return C.CString(err.Error())
+	}
+	var buf bytes.Buffer
+	if err := r.WriteJSON(&buf); err != nil {
+		return C.CString(err.Error())
+	}
+	*out = C.CString(buf.String())
+	return nil
+}
+
+// errHandle is returned for a handle that isn't a report's.
+var errHandle = errors.New("invalid report handle")
+
+// report returns the report with handle h. Looking up a handle that was
+// never issued, or has been freed, panics, which is turned into an error.
+func report(h C.uintptr_t) (r *sales.Report, err error) {
+	defer func() {
+		if recover() != nil {
+			r, err = nil, errHandle
+		}
+	}()
+	r, ok := cgo.Handle(h).Value().(*sales.Report)
+	if !ok {
+		return nil, errHandle
+	}
+	return r, nil
+}
+
+// recoverError is deferred by each function returning an error message, so
+// that a panic becomes an error message for the caller, rather than
+// crashing its process.
+func recoverError(msg **C.char) {
+	if p := recover(); p != nil {
+		*msg = C.CString(fmt.Sprint("libsales: ", p))
+	}
+}
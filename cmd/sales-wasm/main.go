@@ -0,0 +1,75 @@
+//go:build js && wasm
+
+// Command sales-wasm runs the sales report in a web page, so that exports
+// dropped onto the page never leave the user's browser. Build it with:
+//
+//	GOOS=js GOARCH=wasm go build -o sales.wasm ./cmd/sales-wasm
+//
+// and load it with the wasm_exec.js file from the Go distribution. It
+// defines a global function:
+//
+//	salesReport(files, format, groups)
+//
+// where files is an array of {name, data} objects holding the name and text
+// of each export, format is one of "text", "json", "csv", "tidy", or
+// "html", and groups is the text of a groups file, or "". It returns an
+// object with an output property holding the report, or an error property
+// describing what went wrong.
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"strings"
+	"syscall/js"
+
+	"github.com/bitfield/sales"
+)
+
+func main() {
+	js.Global().Set("salesReport", js.FuncOf(func(this js.Value, args []js.Value) any {
+		if len(args) != 3 {
+			return map[string]any{"error": "want salesReport(files, format, groups)"}
+		}
+		out, err := report(args[0], args[1].String(), args[2].String())
+		if err != nil {
+			return map[string]any{"error": err.Error()}
+		}
+		return map[string]any{"output": out}
+	}))
+	select {}
+}
+
+func report(files js.Value, format, groups string) (string, error) {
+	r := sales.NewReport()
+	g, err := sales.ParseGroups(strings.NewReader(groups))
+	if err != nil {
+		return "", fmt.Errorf("groups: %w", err)
+	}
+	r.Groups = g
+	for i := 0; i < files.Length(); i++ {
+		f := files.Index(i)
+		if err := r.Read(strings.NewReader(f.Get("data").String()), f.Get("name").String()); err != nil {
+			return "", err
+		}
+	}
+	if err := r.Resolve(); err != nil {
+		return "", err
+	}
+	var buf bytes.Buffer
+	switch format {
+	case "text":
+		err = r.WriteText(&buf)
+	case "json":
+		err = r.WriteJSON(&buf)
+	case "csv":
+		err = r.WriteCSV(&buf)
+	case "tidy":
+		err = r.WriteTidyCSV(&buf)
+	case "html":
+		err = r.WriteHTML(&buf, sales.DefaultLocale)
+	default:
+		return "", fmt.Errorf("unknown format %q", format)
+	}
+	return buf.String(), err
+}
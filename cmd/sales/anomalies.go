@@ -0,0 +1,34 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+)
+
+func anomaliesCmd(args []string) {
+	fs := flag.NewFlagSet("anomalies", flag.ExitOnError)
+	threshold := fs.Float64("threshold", 3, "number of standard deviations from the trailing average that counts as an anomaly")
+	window := fs.Int("window", 28, "number of days in the trailing average")
+	groupsPath := fs.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := fs.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	fs.Parse(args)
+	if fs.NArg() == 0 || *window < 1 {
+		fmt.Fprintln(os.Stderr, "Usage: sales anomalies [-threshold N] [-window DAYS] [-groups FILE] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	readSales(r, fs.Args(), false, false)
+	if err := r.WriteAnomalies(os.Stdout, *threshold, *window); err != nil {
+		log.Fatal(err)
+	}
+}
@@ -0,0 +1,65 @@
+package main
+
+import (
+	"crypto/rand"
+	"encoding/hex"
+	"errors"
+	"flag"
+	"fmt"
+	"log"
+	"math/big"
+	"os"
+
+	"github.com/bitfield/sales"
+)
+
+// anonymizeCmd writes a copy of an export with the customers' details
+// hidden, for attaching to bug reports.
+func anonymizeCmd(args []string) {
+	flags := flag.NewFlagSet("anonymize", flag.ExitOnError)
+	output := flags.String("o", "", "file to write the anonymized export to")
+	salt := flags.String("salt", "", "salt for hashing email addresses, so that runs give the same hashes (default: random)")
+	scalePrices := flags.Bool("scale-prices", false, "multiply every amount of money by a random factor between 0.5 and 2, which isn't shown")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if *output == "" || flags.NArg() != 1 {
+		fmt.Fprintln(os.Stderr, "Usage: sales anonymize [-salt SALT] [-scale-prices] [-config FILE] -o OUTPUT FILE")
+		os.Exit(1)
+	}
+	cfg, err := loadConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	a := sales.Anonymizer{Columns: cfg.Columns, Dialect: cfg.Dialect, Salt: *salt}
+	if a.Salt == "" {
+		a.Salt = randomHex(16)
+	}
+	if *scalePrices {
+		n, err := rand.Int(rand.Reader, big.NewInt(1500))
+		if err != nil {
+			log.Fatal(err)
+		}
+		a.PriceScale = 0.5 + float64(n.Int64())/1000
+	}
+	in, err := os.Open(flags.Arg(0))
+	if err != nil {
+		log.Fatal(err)
+	}
+	defer in.Close()
+	out, err := os.Create(*output)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if err := errors.Join(a.Anonymize(in, out), out.Close()); err != nil {
+		log.Fatal(err)
+	}
+}
+
+// randomHex returns n random bytes as hexadecimal.
+func randomHex(n int) string {
+	b := make([]byte, n)
+	if _, err := rand.Read(b); err != nil {
+		log.Fatal(err)
+	}
+	return hex.EncodeToString(b)
+}
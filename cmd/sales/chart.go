@@ -0,0 +1,53 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+func chartCmd(args []string) {
+	fs := flag.NewFlagSet("chart", flag.ExitOnError)
+	kind := fs.String("type", "bar", "chart type: bar (revenue by product) or line (revenue by month)")
+	output := fs.String("o", "", "file to write the chart to, ending in .svg or .png")
+	groupsPath := fs.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := fs.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	fs.Parse(args)
+	if *output == "" || fs.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales chart [-type bar|line] [-groups FILE] [-config FILE] -o OUTPUT.svg|OUTPUT.png FILE...")
+		os.Exit(1)
+	}
+	format := strings.TrimPrefix(strings.ToLower(filepath.Ext(*output)), ".")
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	readSales(r, fs.Args(), false, false)
+	f, err := os.Create(*output)
+	if err != nil {
+		log.Fatal(err)
+	}
+	switch *kind {
+	case "bar":
+		err = r.WriteBarChart(f, format)
+	case "line":
+		err = r.WriteLineChart(f, format)
+	default:
+		err = fmt.Errorf("unknown chart type %q (want bar or line)", *kind)
+	}
+	if err != nil {
+		f.Close()
+		os.Remove(*output)
+		log.Fatal(err)
+	}
+	if err := f.Close(); err != nil {
+		log.Fatal(err)
+	}
+}
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+func convertCmd(args []string) {
+	flags := flag.NewFlagSet("convert", flag.ExitOnError)
+	to := flags.String("to", "", "file to write the normalized CSV to, or Parquet if it ends in .parquet")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if *to == "" || flags.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales convert -to OUTPUT [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, "")
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.KeepRecords = true
+	readSales(r, flags.Args(), false, false)
+	f, err := os.Create(*to)
+	if err != nil {
+		log.Fatal(err)
+	}
+	write := r.WriteNormalizedCSV
+	if strings.EqualFold(filepath.Ext(*to), ".parquet") {
+		write = r.WriteParquet
+	}
+	if err := write(f); err != nil {
+		log.Fatal(err)
+	}
+	if err := f.Close(); err != nil {
+		log.Fatal(err)
+	}
+}
@@ -0,0 +1,68 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+	"slices"
+
+	"github.com/bitfield/sales"
+)
+
+func diffCmd(args []string) {
+	flags := flag.NewFlagSet("diff", flag.ExitOnError)
+	oldState := flags.String("old-state", "", "state file, saved with -state, to start the old report from")
+	newState := flags.String("new-state", "", "state file, saved with -state, to start the new report from")
+	groupsPath := flags.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	// The flag package consumes a "--" that comes straight after the
+	// flags, so then every file belongs to the side without a state file.
+	oldPaths, newPaths := flags.Args(), []string(nil)
+	if i := slices.Index(oldPaths, "--"); i >= 0 {
+		oldPaths, newPaths = oldPaths[:i], oldPaths[i+1:]
+	} else if *oldState != "" {
+		oldPaths, newPaths = nil, oldPaths
+	}
+	if len(oldPaths) == 0 && *oldState == "" || len(newPaths) == 0 && *newState == "" {
+		fmt.Fprintln(os.Stderr, "Usage: sales diff [-old-state FILE] [-new-state FILE] [-groups FILE] [-config FILE] OLD... -- NEW...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	// load builds one side of the comparison, with the same settings for
+	// both.
+	load := func(statePath string, paths []string) *sales.Report {
+		r, err := newReport(cfg, *groupsPath)
+		if err != nil {
+			log.Fatal(err)
+		}
+		if statePath != "" {
+			saved, err := sales.LoadReport(statePath)
+			if err != nil {
+				log.Fatal(err)
+			}
+			saved.Configure(cfg)
+			saved.Groups = r.Groups
+			r = saved
+		}
+		r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+		readSales(r, paths, false, false)
+		return r
+	}
+	before := load(*oldState, oldPaths)
+	after := load(*newState, newPaths)
+	diffs := after.Diff(before)
+	if len(diffs) == 0 {
+		fmt.Println("No differences")
+		return
+	}
+	if err := after.WriteDiff(os.Stdout, diffs); err != nil {
+		log.Fatal(err)
+	}
+	// Like diff(1), exit with status 1 when the inputs differ.
+	os.Exit(1)
+}
@@ -0,0 +1,88 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"mime/multipart"
+	"net"
+	"net/smtp"
+	"net/textproto"
+	"os"
+	"strconv"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// emailReport sends r to the given address as an email with both text and
+// HTML parts. Settings in the [smtp] table of cfg can be overridden by the
+// environment variables SALES_SMTP_HOST, SALES_SMTP_PORT,
+// SALES_SMTP_USERNAME, and SALES_SMTP_FROM; the password is read from
+// SALES_SMTP_PASSWORD.
+func emailReport(r *sales.Report, to string, cfg sales.SMTP, loc sales.Locale) error {
+	if v := os.Getenv("SALES_SMTP_HOST"); v != "" {
+		cfg.Host = v
+	}
+	if v := os.Getenv("SALES_SMTP_PORT"); v != "" {
+		port, err := strconv.Atoi(v)
+		if err != nil {
+			return fmt.Errorf("SALES_SMTP_PORT: want a port number, got %q", v)
+		}
+		cfg.Port = port
+	}
+	if v := os.Getenv("SALES_SMTP_USERNAME"); v != "" {
+		cfg.Username = v
+	}
+	if v := os.Getenv("SALES_SMTP_FROM"); v != "" {
+		cfg.From = v
+	}
+	if cfg.Host == "" {
+		return errors.New("no SMTP host: set smtp.host in the config, or SALES_SMTP_HOST")
+	}
+	if cfg.Port == 0 {
+		cfg.Port = 587
+	}
+	if cfg.From == "" {
+		cfg.From = cfg.Username
+	}
+	// Mail clients don't understand terminal colors.
+	r.Color = false
+	var text, html bytes.Buffer
+	if err := writeReport(&text, r, "text", loc, r.Display()); err != nil {
+		return err
+	}
+	if err := r.WriteHTML(&html, loc); err != nil {
+		return err
+	}
+	var body bytes.Buffer
+	mw := multipart.NewWriter(&body)
+	for _, part := range []struct {
+		contentType string
+		content     []byte
+	}{
+		{"text/plain; charset=utf-8", text.Bytes()},
+		{"text/html; charset=utf-8", html.Bytes()},
+	} {
+		w, err := mw.CreatePart(textproto.MIMEHeader{"Content-Type": {part.contentType}})
+		if err != nil {
+			return err
+		}
+		w.Write(part.content)
+	}
+	mw.Close()
+	var msg bytes.Buffer
+	fmt.Fprintf(&msg, "From: %s\r\n", cfg.From)
+	fmt.Fprintf(&msg, "To: %s\r\n", to)
+	fmt.Fprintf(&msg, "Subject: Sales report %s\r\n", time.Now().Format(time.DateOnly))
+	fmt.Fprintf(&msg, "Date: %s\r\n", time.Now().Format(time.RFC1123Z))
+	fmt.Fprintf(&msg, "MIME-Version: 1.0\r\n")
+	fmt.Fprintf(&msg, "Content-Type: multipart/alternative; boundary=%s\r\n\r\n", mw.Boundary())
+	msg.Write(body.Bytes())
+	var auth smtp.Auth
+	if cfg.Username != "" {
+		auth = smtp.PlainAuth("", cfg.Username, os.Getenv("SALES_SMTP_PASSWORD"), cfg.Host)
+	}
+	addr := net.JoinHostPort(cfg.Host, strconv.Itoa(cfg.Port))
+	return smtp.SendMail(addr, auth, cfg.From, []string{to}, msg.Bytes())
+}
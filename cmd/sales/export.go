@@ -0,0 +1,52 @@
+package main
+
+import (
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"log"
+	"os"
+)
+
+func exportCmd(args []string) {
+	fs := flag.NewFlagSet("export", flag.ExitOnError)
+	sqlPath := fs.String("sql", "", "write an SQL script creating tables of orders, line items, products, groups, and totals")
+	ledgerPath := fs.String("ledger", "", "write a CSV ledger of the units of each SKU sold each day, and the cumulative units, for inventory tools")
+	groupsPath := fs.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := fs.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	fs.Parse(args)
+	if *sqlPath == "" && *ledgerPath == "" || fs.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales export [-sql OUTPUT] [-ledger OUTPUT] [-groups FILE] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.KeepRecords = true
+	readSales(r, fs.Args(), false, false)
+	exports := []struct {
+		path  string
+		write func(io.Writer) error
+	}{
+		{*sqlPath, r.WriteSQL},
+		{*ledgerPath, r.WriteUnitsLedger},
+	}
+	for _, e := range exports {
+		if e.path == "" {
+			continue
+		}
+		f, err := os.Create(e.path)
+		if err != nil {
+			log.Fatal(err)
+		}
+		if err := errors.Join(e.write(f), f.Close()); err != nil {
+			log.Fatal(err)
+		}
+	}
+}
@@ -0,0 +1,56 @@
+package main
+
+import (
+	"context"
+	"flag"
+	"fmt"
+	"log"
+	"os"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func fetchCmd(args []string) {
+	if len(args) == 0 || args[0] != "stripe" {
+		fetchUsage()
+	}
+	fs := flag.NewFlagSet("fetch stripe", flag.ExitOnError)
+	since := fs.String("since", "", "fetch charges made on or after this date, such as 2024-01-01")
+	format := fs.String("format", envOr("SALES_FORMAT", "text"), "output format: text, json, csv, tidy, html, excel, or parquet (or $SALES_FORMAT)")
+	groupsPath := fs.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := fs.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	fs.Parse(args[1:])
+	if *since == "" {
+		fetchUsage()
+	}
+	from, err := time.Parse(time.DateOnly, *since)
+	if err != nil {
+		log.Fatalf("-since: want a date such as 2024-01-01, got %q", *since)
+	}
+	key := os.Getenv("STRIPE_API_KEY")
+	if key == "" {
+		log.Fatal("STRIPE_API_KEY is not set")
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if err := r.Ingest(sales.NewStripeSource(context.Background(), key, from)); err != nil {
+		log.Fatal(err)
+	}
+	readSales(r, fs.Args(), false, false)
+	if err := writeReport(os.Stdout, r, *format, sales.DefaultLocale, r.Display()); err != nil {
+		log.Fatal(err)
+	}
+}
+
+func fetchUsage() {
+	fmt.Fprintln(os.Stderr, "Usage: sales fetch stripe -since DATE [-format FORMAT] [-groups FILE] [-config FILE] [FILE...]")
+	fmt.Fprintln(os.Stderr, "The API key is read from the STRIPE_API_KEY environment variable.")
+	os.Exit(1)
+}
@@ -0,0 +1,61 @@
+package main
+
+import (
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"log"
+	"os"
+
+	"github.com/bitfield/sales"
+	"github.com/bitfield/sales/salestest"
+)
+
+// generateCmd writes a fake sales export, for trying out the other
+// commands, or showing a bug, without real sales data.
+func generateCmd(args []string) {
+	flags := flag.NewFlagSet("generate", flag.ExitOnError)
+	rows := flags.Int("rows", 1000, "number of line items to generate")
+	seed := flags.Int64("seed", 1, "seed for the generator: the same seed always gives the same data")
+	format := flags.String("format", "squarespace", "export format: squarespace, normalized (the canonical CSV), or parquet")
+	output := flags.String("o", "", "file to write the export to, instead of standard output")
+	flags.Parse(args)
+	if flags.NArg() != 0 || *rows < 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales generate [-rows N] [-seed N] [-format squarespace|normalized|parquet] [-o OUTPUT]")
+		os.Exit(1)
+	}
+	recs := salestest.Records(*rows, *seed)
+	var write func(io.Writer) error
+	switch *format {
+	case "squarespace":
+		write = func(w io.Writer) error {
+			return salestest.WriteSquarespaceCSV(w, recs)
+		}
+	case "normalized", "parquet":
+		r := sales.NewReport()
+		r.KeepRecords = true
+		for _, rec := range recs {
+			r.Add(rec)
+		}
+		write = r.WriteNormalizedCSV
+		if *format == "parquet" {
+			write = r.WriteParquet
+		}
+	default:
+		log.Fatalf("unknown format %q (want squarespace, normalized, or parquet)", *format)
+	}
+	if *output == "" {
+		if err := write(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	f, err := os.Create(*output)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if err := errors.Join(write(f), f.Close()); err != nil {
+		log.Fatal(err)
+	}
+}
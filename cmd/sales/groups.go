@@ -0,0 +1,54 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+
+	"github.com/bitfield/sales"
+)
+
+func groupsCmd(args []string) {
+	if len(args) == 0 || args[0] != "diff" {
+		groupsUsage()
+	}
+	flags := flag.NewFlagSet("groups diff", flag.ExitOnError)
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args[1:])
+	if flags.NArg() < 3 {
+		groupsUsage()
+	}
+	before, err := sales.LoadGroups(flags.Arg(0))
+	if err != nil {
+		log.Fatal(err)
+	}
+	after, err := sales.LoadGroups(flags.Arg(1))
+	if err != nil {
+		log.Fatal(err)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	// The groups being compared are given explicitly, so the config's
+	// groups file doesn't matter.
+	r := sales.NewReport()
+	r.Configure(cfg)
+	// The records give each product's SKUs, for the groups' SKU rules.
+	r.KeepRecords = true
+	r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	readSales(r, flags.Args()[2:], false, false)
+	moves, err := sales.DiffGroups(r, before, after)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if err := r.WriteGroupMoves(os.Stdout, moves); err != nil {
+		log.Fatal(err)
+	}
+}
+
+func groupsUsage() {
+	fmt.Fprintln(os.Stderr, "Usage: sales groups diff [-config FILE] OLD NEW FILE...")
+	os.Exit(1)
+}
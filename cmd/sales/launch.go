@@ -0,0 +1,63 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// launchCmd reports a product's sales in the first hours and days after
+// its launch, compared with the period before.
+func launchCmd(args []string) {
+	flags := flag.NewFlagSet("launch", flag.ExitOnError)
+	product := flags.String("product", "", "report row to analyse: a product name, or a group's")
+	launchDate := flags.String("launch-date", "", "when the product launched, such as 2024-03-14 or 2024-03-14T09:00:00Z")
+	windows := flags.String("windows", "24h,7d,30d", "comma-separated lengths of the windows from the launch, such as 24h, 7d, or 2w")
+	baseline := flags.String("baseline", "28d", "length of the period before the launch to compare the windows with")
+	groupsPath := flags.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if *product == "" || *launchDate == "" || flags.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales launch -product NAME -launch-date DATE [-windows LIST] [-baseline LENGTH] [-groups FILE] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.KeepRecords = true
+	r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	at, err := r.Timestamps.Parse(*launchDate)
+	if err != nil {
+		log.Fatalf("-launch-date: %v", err)
+	}
+	var lengths []time.Duration
+	for _, s := range strings.Split(*windows, ",") {
+		d, err := sales.ParseWindow(strings.TrimSpace(s))
+		if err != nil {
+			log.Fatalf("-windows: %v", err)
+		}
+		lengths = append(lengths, d)
+	}
+	base, err := sales.ParseWindow(*baseline)
+	if err != nil {
+		log.Fatalf("-baseline: %v", err)
+	}
+	readSales(r, flags.Args(), false, false)
+	l, err := r.Launch(*product, at, lengths, base)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if err := r.WriteLaunch(os.Stdout, l); err != nil {
+		log.Fatal(err)
+	}
+}
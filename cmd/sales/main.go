@@ -0,0 +1,761 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"io/fs"
+	"log"
+	"log/slog"
+	"net/http"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func main() {
+	if len(os.Args) > 1 {
+		switch os.Args[1] {
+		case "groups":
+			groupsCmd(os.Args[2:])
+			return
+		case "export":
+			exportCmd(os.Args[2:])
+			return
+		case "watch":
+			watchCmd(os.Args[2:])
+			return
+		case "chart":
+			chartCmd(os.Args[2:])
+			return
+		case "anomalies":
+			anomaliesCmd(os.Args[2:])
+			return
+		case "fetch":
+			fetchCmd(os.Args[2:])
+			return
+		case "reconcile":
+			reconcileCmd(os.Args[2:])
+			return
+		case "convert":
+			convertCmd(os.Args[2:])
+			return
+		case "diff":
+			diffCmd(os.Args[2:])
+			return
+		case "tui":
+			tuiCmd(os.Args[2:])
+			return
+		case "query":
+			queryCmd(os.Args[2:])
+			return
+		case "launch":
+			launchCmd(os.Args[2:])
+			return
+		case "price-change":
+			priceChangeCmd(os.Args[2:])
+			return
+		case "tax-report":
+			taxReportCmd(os.Args[2:])
+			return
+		case "serve":
+			serveCmd(os.Args[2:])
+			return
+		case "anonymize":
+			anonymizeCmd(os.Args[2:])
+			return
+		case "generate":
+			generateCmd(os.Args[2:])
+			return
+		case "schema":
+			fmt.Print(sales.JSONSchemaV1)
+			return
+		}
+	}
+	format := flag.String("format", envOr("SALES_FORMAT", "text"), "output format: text, json, csv, tidy, html, excel, or parquet (or $SALES_FORMAT)")
+	templatePath := flag.String("template", "", "render the report with this text/template file, instead of in -format")
+	localeTag := flag.String("locale", envOr("SALES_LOCALE", "en-US"), "locale for currency amounts in html and excel output (or $SALES_LOCALE)")
+	configPath := flag.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	groupsPath := flag.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	sortBy := flag.String("sort", "", "order of the report rows: name, revenue, or units (default name)")
+	currency := flag.String("currency", "", "currency that input amounts are in, such as EUR; orders in others are warned about (default USD)")
+	mixedCurrencies := flag.String("mixed-currencies", "", "what to do with orders in another currency: warn, reject, convert at the rates in the config's [exchange_rates] table, or separate to report each currency's sales on their own")
+	rates := flag.String("rates", "", "CSV file of exchange rates by date (date, currency, rate) for -mixed-currencies convert, or 'ecb' to download the European Central Bank's")
+	statePath := flag.String("state", "", "file of saved report data: the input is added to it, and the result saved back")
+	skipBadRows := flag.Bool("skip-bad-rows", false, "skip malformed rows instead of stopping, and list them afterwards")
+	warnings := flag.Bool("warnings", false, "list suspicious input, such as zero prices or duplicate orders, after the report")
+	nameColumn := flag.String("name-column", "", "name of the CSV column holding product names")
+	priceColumn := flag.String("price-column", "", "name of the CSV column holding unit prices")
+	qtyColumn := flag.String("qty-column", "", "name of the CSV column holding quantities")
+	totalColumn := flag.String("total-column", "", "name of the CSV column holding line item totals, used when the price is zero")
+	delimiter := flag.String("delimiter", "", "CSV field delimiter, such as ';' or 'tab' (default ',')")
+	encoding := flag.String("encoding", "", "character encoding of input without a byte order mark: utf-8, utf-16le, utf-16be, windows-1252, or latin1")
+	lazyQuotes := flag.Bool("lazy-quotes", false, "tolerate stray quotes in CSV fields")
+	logLevel := flag.String("log", "", "log reading, skipped rows, and rendering to standard error at this level: debug, info, warn, or error")
+	columns := flag.String("columns", "", "comma-separated columns to show after the product name in text, CSV, and JSON output: units, revenue, avg, percent, free, cost, profit, or margin")
+	totalsOnly := flag.Bool("totals-only", false, "show only the total units, revenue, orders, and fees")
+	top := flag.Int("top", 0, "show only this many products in the text report, combining the rest into an Other row")
+	otherLabel := flag.String("other-label", "", "label of the row combining the products after the -top ones; %d is replaced by their number (default \"Other (%d products)\")")
+	noTotals := flag.Bool("no-totals", false, "leave out the row of totals from the text report")
+	archive := flag.String("archive", "", "append the orders read to this gzip-compressed CSV archive, skipping orders already in it")
+	progress := flag.Bool("progress", false, "print the rows read and skipped, and the time taken, for each file as it's read")
+	comps := flag.Bool("comps", false, "count zero-price sales in a separate Free column, instead of as paid units")
+	dedupe := flag.Bool("dedupe", false, "count each order ID only once, so that overlapping exports aren't double counted")
+	conflicts := flag.String("conflicts", "", "how to reconcile sources reporting the same sales: prefer-source, max, or error")
+	prefer := flag.String("prefer", "", "comma-separated source files in order of trust, for -conflicts prefer-source")
+	chart := flag.String("chart", "", "show a bar beside each product, scaled to its revenue or units")
+	noColor := flag.Bool("no-color", false, "don't color text output (also set by the NO_COLOR environment variable)")
+	period := flag.String("period", "", "length of the periods in -by-period and tidy output: week, month, quarter, or year (default month)")
+	yearStart := flag.Int("year-start", 0, "first month (1-12) of the financial year, for quarters and years (default 1)")
+	rounding := flag.String("rounding", "", "how to round each line item to whole cents: half-up or half-even (default half-up)")
+	zone := flag.String("tz", "", "time zone to report sales in, such as Europe/London or UTC (default: as recorded)")
+	email := flag.String("email", "", "send the report to this address as text and HTML, instead of printing it, using the [smtp] config settings")
+	forecast := flag.Bool("forecast", false, "project the revenue for the whole of the latest month, after the text report")
+	runRate := flag.Bool("run-rate", false, "show the revenue per day, the best day, and the current run rate, after the text report")
+	cohorts := flag.Bool("cohorts", false, "show the revenue from customers grouped by the month of their first purchase")
+	timeline := flag.Bool("timeline", false, "show each product's first and last sale dates, longest idle first")
+	priceHistogram := flag.Bool("price-histogram", false, "show the distribution of prices paid for each product")
+	renewals := flag.Bool("renewals", false, "show subscribers, new and churned subscribers, MRR, and net revenue retention per month")
+	subscriptions := flag.Bool("subscriptions", false, "show one-time and subscription revenue and MRR per month instead of per product")
+	variants := flag.Bool("variants", false, "show each product's units and revenue broken down by variant")
+	byPeriod := flag.Bool("by-period", false, "show units and revenue per period instead of per product")
+	byReferrer := flag.Bool("by-referrer", false, "show units, revenue, and affiliate fees per referrer and affiliate instead of per product")
+	bySource := flag.Bool("by-source", false, "show units and revenue per source instead of per product")
+	filter := flag.String("filter", "", `include only records matching this expression, such as 'price > 0 && name ~ "Go"'`)
+	roundingAudit := flag.Bool("rounding-audit", false, "show the revenue of each product in each period before and after rounding, and the rounded total shared out among them")
+	groupStats := flag.Bool("group-stats", false, "show the line items, products, units, and revenue matched by each group, and the groups matching nothing")
+	ungrouped := flag.Bool("ungrouped", false, "list the products that matched no group, largest revenue first")
+	b2b := flag.Bool("b2b", false, "show the split of revenue between business and consumer orders, and the top companies (see [b2b] in the config file)")
+	byEmailDomain := flag.Bool("by-email-domain", false, "show customers and revenue per email domain, webmail or business, combining domains with fewer than 3 customers")
+	pareto := flag.Bool("pareto", false, "rank products by revenue into A, B, and C tiers, and show the share of revenue from the top 20%")
+	byTime := flag.Bool("by-time", false, "show orders and revenue by hour of day and day of week")
+	heatmap := flag.Bool("heatmap", false, "show a grid of orders by hour of day and day of week, shaded by how busy each hour is")
+	failIfEmpty := flag.Bool("fail-if-empty", false, "exit with status 2, instead of writing the report, if there are no sales")
+	failUnderRevenue := flag.String("fail-under-revenue", "", "after writing the report, exit with status 3 if the total revenue is less than this many dollars")
+	failUnderUnits := flag.Int("fail-under-units", 0, "after writing the report, exit with status 3 if fewer than this many units were sold")
+	flag.Parse()
+	if flag.NArg() == 0 && *statePath == "" {
+		fmt.Fprintln(os.Stderr, "Usage: sales [-config FILE] [-groups FILE] [-state FILE] [-sort name|revenue|units] [-currency CODE] [-mixed-currencies warn|reject|convert|separate] [-rates FILE|ecb] [-format text|json|csv|tidy|html|excel|parquet] [-locale TAG] [-by-source|-by-referrer|-by-email-domain|-b2b|-by-time|-heatmap|-by-period|-variants|-price-histogram|-subscriptions|-renewals|-cohorts|-timeline|-group-stats|-ungrouped|-pareto|-rounding-audit] [-totals-only] [-columns LIST] [-top N] [-other-label LABEL] [-no-totals] [-dedupe] [-comps] [-skip-bad-rows] [-warnings] [-progress] [-archive FILE] [-log LEVEL] [-fail-if-empty] [-fail-under-revenue DOLLARS] [-fail-under-units N] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales groups diff [-config FILE] OLD NEW FILE...")
+		fmt.Fprintln(os.Stderr, "       sales export [-sql OUTPUT] [-ledger OUTPUT] [-groups FILE] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales watch [-interval DURATION] [-o OUTPUT] [-format FORMAT] [-groups FILE] [-config FILE] DIR")
+		fmt.Fprintln(os.Stderr, "       sales chart [-type bar|line] [-groups FILE] [-config FILE] -o OUTPUT.svg|OUTPUT.png FILE...")
+		fmt.Fprintln(os.Stderr, "       sales anomalies [-threshold N] [-window DAYS] [-groups FILE] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales reconcile -payouts FILE [-lag DURATION] [-tolerance DOLLARS] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales convert -to OUTPUT [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales launch -product NAME -launch-date DATE [-windows LIST] [-baseline LENGTH] [-groups FILE] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales price-change -product NAME -date DATE [-window DAYS] [-groups FILE] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales diff [-old-state FILE] [-new-state FILE] [-groups FILE] [-config FILE] OLD... -- NEW...")
+		fmt.Fprintln(os.Stderr, "       sales query [-duckdb PATH] [-groups FILE] [-config FILE] SQL FILE...")
+		fmt.Fprintln(os.Stderr, "       sales tui [-groups FILE] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales tax-report [-format text|csv] [-config FILE] FILE...")
+		fmt.Fprintln(os.Stderr, "       sales serve [-addr ADDR] [-metrics ADDR] [-interval DURATION] [-groups FILE] [-locale TAG] [-ingest-ttl DURATION] [-config FILE] [DIR]")
+		fmt.Fprintln(os.Stderr, "       sales anonymize [-salt SALT] [-scale-prices] [-config FILE] -o OUTPUT FILE")
+		fmt.Fprintln(os.Stderr, "       sales generate [-rows N] [-seed N] [-format squarespace|normalized|parquet] [-o OUTPUT]")
+		fmt.Fprintln(os.Stderr, "       sales schema")
+		fmt.Fprintln(os.Stderr, "       sales fetch stripe -since DATE [-format FORMAT] [-groups FILE] [-config FILE] [FILE...]")
+		os.Exit(1)
+	}
+	cfg, err := loadConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	loc, err := sales.LookupLocale(*localeTag)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r := sales.NewReport()
+	if *statePath != "" {
+		r, err = sales.LoadReport(*statePath)
+		if errors.Is(err, fs.ErrNotExist) {
+			r = sales.NewReport()
+		} else if err != nil {
+			log.Fatal(err)
+		}
+	}
+	r.Configure(cfg)
+	if *nameColumn != "" {
+		r.Columns.Name = *nameColumn
+	}
+	if *priceColumn != "" {
+		r.Columns.Price = *priceColumn
+	}
+	if *qtyColumn != "" {
+		r.Columns.Quantity = *qtyColumn
+	}
+	if *totalColumn != "" {
+		r.Columns.Total = *totalColumn
+	}
+	if *delimiter != "" {
+		r.Dialect.Delimiter, err = sales.ParseDelimiter(*delimiter)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *encoding != "" {
+		r.Dialect.Encoding = *encoding
+	}
+	if *lazyQuotes {
+		r.Dialect.LazyQuotes = true
+	}
+	r.SkipBadRows = *skipBadRows
+	r.Dedupe = *dedupe
+	if *period != "" {
+		r.Period.Unit, err = sales.ParsePeriodUnit(*period)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *yearStart != 0 {
+		if *yearStart < 1 || *yearStart > 12 {
+			log.Fatalf("-year-start must be a month from 1 to 12, not %d", *yearStart)
+		}
+		r.Period.YearStart = time.Month(*yearStart)
+	}
+	if *rounding != "" {
+		r.Rounding, err = sales.ParseRounding(*rounding)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	r.Comps = r.Comps || *comps
+	if *sortBy != "" {
+		r.Sort, err = sales.ParseSortKey(*sortBy)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *currency != "" {
+		r.Currency = strings.ToUpper(*currency)
+	}
+	if *mixedCurrencies != "" {
+		r.CurrencyPolicy.Mode, err = sales.ParseCurrencyMode(*mixedCurrencies)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *rates == "" {
+		*rates = cfg.RatesFile
+	}
+	if *rates != "" {
+		r.CurrencyPolicy.History, err = loadRates(*rates, r.Currency)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *archive != "" || *b2b {
+		r.KeepRecords = true
+	}
+	if *logLevel != "" {
+		var level slog.Level
+		if err := level.UnmarshalText([]byte(*logLevel)); err != nil {
+			log.Fatalf("-log: %v", err)
+		}
+		r.Logger = slog.New(slog.NewTextHandler(os.Stderr, &slog.HandlerOptions{Level: level}))
+	}
+	if *zone != "" {
+		r.Timestamps.Zone, err = time.LoadLocation(*zone)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	r.Chart, err = sales.ParseChart(*chart)
+	if err != nil {
+		log.Fatal(err)
+	}
+	var minRevenue sales.USD
+	if *failUnderRevenue != "" {
+		minRevenue, err = sales.ParseUSD(*failUnderRevenue)
+		if err != nil {
+			log.Fatalf("-fail-under-revenue: %v", err)
+		}
+	}
+	var outputColumns []sales.OutputColumn
+	if *columns != "" {
+		outputColumns, err = sales.ParseOutputColumns(*columns)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	r.Color = !*noColor && os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	if *groupsPath == "" {
+		*groupsPath = cfg.GroupsFile
+	}
+	if *groupsPath != "" {
+		r.Groups, err = sales.LoadGroups(*groupsPath)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *filter != "" {
+		r.Filter, err = sales.ParseFilter(*filter)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *conflicts != "" {
+		r.ConflictPolicy.Mode, err = sales.ParseConflictMode(*conflicts)
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *prefer != "" {
+		r.ConflictPolicy.Prefer = strings.Split(*prefer, ",")
+	}
+	if r.ConflictPolicy.Mode != "" {
+		// Resolve rebuilds the report from its records.
+		r.KeepRecords = true
+	}
+	readSales(r, flag.Args(), *progress, *warnings)
+	if *archive != "" {
+		n, err := r.AppendArchive(*archive)
+		if err != nil {
+			log.Fatal(err)
+		}
+		fmt.Fprintf(os.Stderr, "Archived %d new records to %s\n", n, *archive)
+	}
+	if *statePath != "" {
+		if err := r.Save(*statePath); err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *failIfEmpty && r.IsEmpty() {
+		fmt.Fprintln(os.Stderr, "No sales in the selected range")
+		os.Exit(2)
+	}
+	// The thresholds are checked once the report has been written, so
+	// that a cron job's output still includes it.
+	var below []string
+	if total := r.TotalRevenue(); *failUnderRevenue != "" && total < minRevenue {
+		below = append(below, fmt.Sprintf("revenue %s is under %s", total, minRevenue))
+	}
+	if units := r.TotalUnits(); units < *failUnderUnits {
+		below = append(below, fmt.Sprintf("units %d are under %d", units, *failUnderUnits))
+	}
+	if len(below) > 0 {
+		defer func() {
+			fmt.Fprintf(os.Stderr, "Sales below threshold: %s\n", strings.Join(below, ", "))
+			os.Exit(3)
+		}()
+	}
+	if *byTime {
+		if *format != "text" {
+			log.Fatal("-by-time is only supported with text output")
+		}
+		if err := r.WriteTiming(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *heatmap {
+		if *format != "text" {
+			log.Fatal("-heatmap is only supported with text output")
+		}
+		if err := r.WriteHeatmap(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *timeline {
+		if *format != "text" {
+			log.Fatal("-timeline is only supported with text output")
+		}
+		if err := r.WriteTimeline(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *cohorts {
+		if *format != "text" {
+			log.Fatal("-cohorts is only supported with text output")
+		}
+		if err := r.WriteCohorts(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *variants {
+		if *format != "text" {
+			log.Fatal("-variants is only supported with text output")
+		}
+		if err := r.WriteVariants(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *groupStats {
+		if *format != "text" {
+			log.Fatal("-group-stats is only supported with text output")
+		}
+		if err := r.WriteGroupStats(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *ungrouped {
+		if *format != "text" {
+			log.Fatal("-ungrouped is only supported with text output")
+		}
+		if err := r.WriteUngrouped(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *b2b {
+		if *format != "text" {
+			log.Fatal("-b2b is only supported with text output")
+		}
+		n := *top
+		if n == 0 {
+			n = cfg.Top
+		}
+		if err := r.WriteB2B(os.Stdout, cfg.B2B, n); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *byEmailDomain {
+		if *format != "text" {
+			log.Fatal("-by-email-domain is only supported with text output")
+		}
+		if err := r.WriteEmailDomains(os.Stdout, minDomainCustomers); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *pareto {
+		if *format != "text" {
+			log.Fatal("-pareto is only supported with text output")
+		}
+		if err := r.WritePareto(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *roundingAudit {
+		if *format != "text" {
+			log.Fatal("-rounding-audit is only supported with text output")
+		}
+		if err := r.WriteRoundingAudit(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *byPeriod {
+		if *format != "text" {
+			log.Fatal("-by-period is only supported with text output")
+		}
+		if err := r.WritePeriods(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *totalsOnly {
+		switch *format {
+		case "text":
+			err = r.WriteTotals(os.Stdout)
+		case "json":
+			err = json.NewEncoder(os.Stdout).Encode(r.Totals())
+		default:
+			log.Fatal("-totals-only is only supported with text or JSON output")
+		}
+		if err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *priceHistogram {
+		if *format != "text" {
+			log.Fatal("-price-histogram is only supported with text output")
+		}
+		if err := r.WritePriceHistograms(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *renewals {
+		if *format != "text" {
+			log.Fatal("-renewals is only supported with text output")
+		}
+		if err := r.WriteRenewals(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *subscriptions {
+		if *format != "text" {
+			log.Fatal("-subscriptions is only supported with text output")
+		}
+		if err := r.WriteSubscriptions(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *byReferrer {
+		if *format != "text" {
+			log.Fatal("-by-referrer is only supported with text output")
+		}
+		if err := r.WriteReferrers(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *bySource {
+		if *format != "text" {
+			log.Fatal("-by-source is only supported with text output")
+		}
+		if err := r.WriteSources(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *templatePath != "" {
+		t, err := sales.ParseTemplate(*templatePath, loc)
+		if err != nil {
+			log.Fatal(err)
+		}
+		if err := r.WriteTemplate(os.Stdout, t); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *email != "" {
+		if err := emailReport(r, *email, cfg.SMTP, loc); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if r.CurrencyPolicy.Mode == "separate" {
+		if *format != "text" {
+			log.Fatal("-mixed-currencies separate is only supported with text output")
+		}
+		if err := r.WriteCurrencies(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+		return
+	}
+	if *forecast && *format != "text" {
+		log.Fatal("-forecast is only supported with text output")
+	}
+	if *runRate && *format != "text" {
+		log.Fatal("-run-rate is only supported with text output")
+	}
+	opts := r.Display()
+	opts.Top = *top
+	if opts.Top == 0 {
+		opts.Top = cfg.Top
+	}
+	opts.OtherLabel = cfg.OtherLabel
+	if *otherLabel != "" {
+		opts.OtherLabel = *otherLabel
+	}
+	opts.HideTotals = *noTotals
+	opts.Columns = outputColumns
+	if err := writeReport(os.Stdout, r, *format, loc, opts); err != nil {
+		log.Fatal(err)
+	}
+	if *forecast {
+		if err := r.WriteForecast(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+	}
+	if *runRate {
+		if err := r.WriteRunRate(os.Stdout); err != nil {
+			log.Fatal(err)
+		}
+	}
+}
+
+// minDomainCustomers is the fewest customers an email domain must have to
+// be shown on its own by -by-email-domain, so that no one customer's
+// spending can be picked out.
+const minDomainCustomers = 3
+
+// isTerminal reports whether f is a terminal, rather than a file or pipe.
+func isTerminal(f *os.File) bool {
+	info, err := f.Stat()
+	return err == nil && info.Mode()&os.ModeCharDevice != 0
+}
+
+// loadConfig returns the settings in the user's global config file, if
+// there is one, overlaid with those in the config file at path, and then
+// with those set by environment variables. A missing default sales.toml is
+// fine; one named explicitly is not.
+func loadConfig(path string) (sales.Config, error) {
+	if path != "sales.toml" {
+		if _, err := os.Stat(path); err != nil {
+			return sales.Config{}, err
+		}
+	}
+	return sales.LoadConfigsWithEnv(sales.GlobalConfigPath(), path)
+}
+
+// loadReportConfig is loadConfig for commands that read sales: it also
+// loads the exchange rates file named in the config, if any, into the
+// currency policy.
+func loadReportConfig(path string) (sales.Config, error) {
+	cfg, err := loadConfig(path)
+	if err != nil || cfg.RatesFile == "" {
+		return cfg, err
+	}
+	cfg.CurrencyPolicy.History, err = loadRates(cfg.RatesFile, cfg.Currency)
+	return cfg, err
+}
+
+// newReport returns a report with the settings in cfg, and the product
+// groups in the file at groupsPath, or else in the groups file that cfg
+// names, if any. Every command that reads sales builds its report this
+// way, and reads into it with readSales, so that the same files give the
+// same numbers whichever command reads them.
+func newReport(cfg sales.Config, groupsPath string) (*sales.Report, error) {
+	r := sales.NewReport()
+	r.Configure(cfg)
+	if groupsPath == "" {
+		groupsPath = cfg.GroupsFile
+	}
+	if groupsPath != "" {
+		groups, err := sales.LoadGroups(groupsPath)
+		if err != nil {
+			return nil, err
+		}
+		r.Groups = groups
+	}
+	return r, nil
+}
+
+// loadRates returns the exchange rates into currency from the rates file
+// at path, or, if path is "ecb", from the European Central Bank.
+func loadRates(path, currency string) (sales.RateHistory, error) {
+	if path != "ecb" {
+		return sales.LoadRates(path)
+	}
+	if currency == "" {
+		currency = "USD"
+	}
+	return sales.FetchECBRates(context.Background(), currency)
+}
+
+// envOr returns the value of the environment variable name, or def if it
+// isn't set.
+func envOr(name, def string) string {
+	if v, ok := os.LookupEnv(name); ok {
+		return v
+	}
+	return def
+}
+
+// writeReport writes r to w in the named output format, laying out text
+// output according to opts.
+func writeReport(w io.Writer, r *sales.Report, format string, loc sales.Locale, opts sales.RenderOptions) error {
+	switch format {
+	case "text":
+		if err := r.RenderTo(w, opts); err != nil {
+			return err
+		}
+		return r.WriteConflicts(w)
+	case "json":
+		return r.WriteJSONColumns(w, opts.Columns)
+	case "csv":
+		if opts.Columns == nil {
+			return r.WriteCSV(w)
+		}
+		return r.WriteCSVColumns(w, opts.Columns)
+	case "tidy":
+		return r.WriteTidyCSV(w)
+	case "html":
+		return r.WriteHTML(w, loc)
+	case "excel":
+		return r.WriteExcel(w, loc)
+	case "parquet":
+		return r.WriteParquetSummary(w)
+	}
+	return fmt.Errorf("unknown format %q", format)
+}
+
+func reportProblems(r *sales.Report, warnings bool) {
+	if n := r.Duplicates(); n > 0 {
+		fmt.Fprintf(os.Stderr, "Skipped %d duplicate orders\n", n)
+	}
+	if excluded := r.Excluded(); len(excluded) > 0 {
+		total := 0
+		var reasons []string
+		for _, reason := range []string{sales.ExcludedZeroPrice, sales.ExcludedEmail, sales.ExcludedTest} {
+			if n := excluded[reason]; n > 0 {
+				total += n
+				reasons = append(reasons, fmt.Sprintf("%d %s", n, reason))
+			}
+		}
+		fmt.Fprintf(os.Stderr, "Excluded %d records (%s)\n", total, strings.Join(reasons, ", "))
+	}
+	if skipped := r.Skipped(); len(skipped) > 0 {
+		fmt.Fprintf(os.Stderr, "Skipped %d bad rows:\n", len(skipped))
+		for _, e := range skipped {
+			fmt.Fprintln(os.Stderr, e)
+		}
+	}
+	if !warnings {
+		return
+	}
+	if ws := r.Warnings(); len(ws) > 0 {
+		fmt.Fprintf(os.Stderr, "%d warnings:\n", len(ws))
+		for _, w := range ws {
+			fmt.Fprintln(os.Stderr, w)
+		}
+	}
+}
+
+// readSales reads each of paths into r, resolves any conflicts between the
+// sources by r's ConflictPolicy, and then reports any rows skipped or
+// excluded, and, if warnings is set, any warnings, to standard error. If
+// progress is set, a line is written as each file is finished. Every
+// command that reports on sales reads them this way, so that the same
+// files give the same numbers whichever command reads them.
+func readSales(r *sales.Report, paths []string, progress, warnings bool) {
+	if progress {
+		readFilesProgress(r, paths)
+	} else {
+		readFiles(r, paths)
+	}
+	if err := r.Resolve(); err != nil {
+		log.Fatal(err)
+	}
+	reportProblems(r, warnings)
+}
+
+// readFilesProgress is like readFiles, but writes a line to standard error
+// as each file is finished, counting the files and giving the rows read and
+// skipped and the time taken.
+func readFilesProgress(r *sales.Report, paths []string) {
+	for i, path := range paths {
+		readFiles(r, []string{path})
+		inputs := r.Inputs()
+		in := inputs[len(inputs)-1]
+		fmt.Fprintf(os.Stderr, "[%d/%d] %s: %d rows, %d skipped, %v\n", i+1, len(paths), in.Source, in.Rows, in.Skipped, in.Elapsed.Round(time.Millisecond))
+	}
+}
+
+// readFiles reads each of paths into r. Paths starting with http:// or
+// https:// are downloaded, sending the contents of the SALES_AUTHORIZATION
+// environment variable, if set, as the Authorization header.
+func readFiles(r *sales.Report, paths []string) {
+	for _, path := range paths {
+		var err error
+		if strings.HasPrefix(path, "http://") || strings.HasPrefix(path, "https://") {
+			header := http.Header{}
+			if auth := os.Getenv("SALES_AUTHORIZATION"); auth != "" {
+				header.Set("Authorization", auth)
+			}
+			err = r.ReadURL(context.Background(), path, header)
+		} else {
+			err = r.ReadFile(path)
+		}
+		if err != nil {
+			log.Fatal(err)
+		}
+	}
+}
@@ -0,0 +1,46 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+)
+
+// priceChangeCmd compares a product's sales before and after its price was
+// changed.
+func priceChangeCmd(args []string) {
+	flags := flag.NewFlagSet("price-change", flag.ExitOnError)
+	product := flags.String("product", "", "report row to analyse: a product name, or a group's")
+	date := flags.String("date", "", "when the price changed, such as 2024-03-14 or 2024-03-14T09:00:00Z")
+	window := flags.Int("window", 0, "number of days to compare on each side of the change (default: all)")
+	groupsPath := flags.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if *product == "" || *date == "" || flags.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales price-change -product NAME -date DATE [-window DAYS] [-groups FILE] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.KeepRecords = true
+	r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	at, err := r.Timestamps.Parse(*date)
+	if err != nil {
+		log.Fatalf("-date: %v", err)
+	}
+	readSales(r, flags.Args(), false, false)
+	pc, err := r.PriceChange(*product, at, *window)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if err := r.WritePriceChange(os.Stdout, pc); err != nil {
+		log.Fatal(err)
+	}
+}
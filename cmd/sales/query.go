@@ -0,0 +1,94 @@
+package main
+
+import (
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"log"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+
+	"github.com/bitfield/sales"
+)
+
+// queryCmd runs an SQL query over the line items and product totals read
+// from the files, using the DuckDB command-line tool. The data is handed
+// over as Parquet files in a temporary directory, so DuckDB needn't be
+// linked into the sales binary: it's only needed to use this command.
+func queryCmd(args []string) {
+	flags := flag.NewFlagSet("query", flag.ExitOnError)
+	duckdb := flags.String("duckdb", "duckdb", "DuckDB command to run the query with")
+	groupsPath := flags.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if flags.NArg() < 2 {
+		fmt.Fprintln(os.Stderr, "Usage: sales query [-duckdb PATH] [-groups FILE] [-config FILE] SQL FILE...")
+		fmt.Fprintln(os.Stderr, "The query can use the views line_items (date, order_id, sku, name, qty, price, currency, fee, tax) and totals (Product, Units, Revenue).")
+		os.Exit(1)
+	}
+	path, err := exec.LookPath(*duckdb)
+	if err != nil {
+		log.Fatalf("sales query needs DuckDB (https://duckdb.org): %v", err)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.KeepRecords = true
+	readSales(r, flags.Args()[1:], false, false)
+	code, err := runQuery(path, flags.Arg(0), r)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if code != 0 {
+		os.Exit(code)
+	}
+}
+
+// runQuery writes the report's line items and totals as Parquet files in a
+// temporary directory, runs query over them with the DuckDB command at
+// path, and returns its exit status. The directory is removed before it
+// returns, whether or not anything fails.
+func runQuery(path, query string, r *sales.Report) (int, error) {
+	dir, err := os.MkdirTemp("", "sales-query")
+	if err != nil {
+		return 0, err
+	}
+	defer os.RemoveAll(dir)
+	views := []struct {
+		name  string
+		write func(io.Writer) error
+	}{
+		{"line_items", r.WriteParquet},
+		{"totals", r.WriteParquetSummary},
+	}
+	var script strings.Builder
+	for _, v := range views {
+		file := filepath.Join(dir, v.name+".parquet")
+		f, err := os.Create(file)
+		if err != nil {
+			return 0, err
+		}
+		if err := errors.Join(v.write(f), f.Close()); err != nil {
+			return 0, err
+		}
+		fmt.Fprintf(&script, "CREATE VIEW %s AS SELECT * FROM read_parquet('%s');\n", v.name, strings.ReplaceAll(file, "'", "''"))
+	}
+	script.WriteString(query)
+	cmd := exec.Command(path, "-c", script.String())
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	err = cmd.Run()
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) {
+		return exitErr.ExitCode(), nil
+	}
+	return 0, err
+}
@@ -0,0 +1,35 @@
+package main
+
+import (
+	"os"
+	"os/exec"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestRunQueryRemovesItsFilesWhenTheQueryFails(t *testing.T) {
+	path, err := exec.LookPath("false")
+	if err != nil {
+		t.Skip("no false command")
+	}
+	tmp := t.TempDir()
+	t.Setenv("TMPDIR", tmp)
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+	code, err := runQuery(path, "SELECT * FROM totals", r)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if code != 1 {
+		t.Errorf("want exit status 1, got %d", code)
+	}
+	left, err := os.ReadDir(tmp)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(left) != 0 {
+		t.Errorf("want temporary files removed, got %d left", len(left))
+	}
+}
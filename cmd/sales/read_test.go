@@ -0,0 +1,44 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestCommandsResolveConflictingSourcesAlike(t *testing.T) {
+	dir := t.TempDir()
+	// Leave out any global config file of the user running the tests.
+	t.Setenv("XDG_CONFIG_HOME", dir)
+	write := func(name, data string) string {
+		t.Helper()
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte(data), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		return path
+	}
+	config := write("sales.toml", "[conflicts]\npolicy = \"max\"\n")
+	header := "Order ID,Lineitem name,Lineitem price,Lineitem quantity,Created at\n"
+	a := write("a.csv", header+"A1,Book,10.00,1,2024-03-01 12:00:00 +0000\n")
+	b := write("b.csv", header+"B1,Book,10.00,2,2024-03-01 12:00:00 +0000\n")
+	converted := filepath.Join(dir, "converted.csv")
+	exported := filepath.Join(dir, "exported.sql")
+	convertCmd([]string{"-config", config, "-to", converted, a, b})
+	exportCmd([]string{"-config", config, "-sql", exported, a, b})
+	for _, out := range []string{converted, exported} {
+		r := sales.NewReport()
+		if err := r.ReadFile(out); err != nil {
+			t.Fatal(err)
+		}
+		// The max policy keeps b.csv's sales of Book alone.
+		if got := r.TotalUnits(); got != 2 {
+			t.Errorf("%s: want 2 units, got %d", filepath.Base(out), got)
+		}
+		if want, got := sales.NewUSD(20), r.TotalRevenue(); want != got {
+			t.Errorf("%s: want revenue %v, got %v", filepath.Base(out), want, got)
+		}
+	}
+}
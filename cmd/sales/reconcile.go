@@ -0,0 +1,48 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+
+	"github.com/bitfield/sales"
+)
+
+func reconcileCmd(args []string) {
+	flags := flag.NewFlagSet("reconcile", flag.ExitOnError)
+	payoutsPath := flags.String("payouts", "", "CSV file of payouts from the platform")
+	dateColumn := flags.String("date-column", "Date", "name of the payouts column holding each payout's date")
+	amountColumn := flags.String("amount-column", "Amount", "name of the payouts column holding each payout's amount")
+	lag := flags.Duration("lag", 0, "how long after a sale it's paid out, such as 48h, so that payouts are matched with the right month")
+	tolerance := flags.Float64("tolerance", 0.01, "largest difference, in dollars, not flagged as unexplained")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if *payoutsPath == "" || flags.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales reconcile -payouts FILE [-date-column NAME] [-amount-column NAME] [-lag DURATION] [-tolerance DOLLARS] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, "")
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	readSales(r, flags.Args(), false, false)
+	f, err := os.Open(*payoutsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	defer f.Close()
+	payouts, err := sales.ReadPayouts(f, *payoutsPath, *dateColumn, *amountColumn, r.Timestamps)
+	if err != nil {
+		log.Fatal(err)
+	}
+	recs := r.Reconcile(payouts, *lag)
+	if err := r.WriteReconciliation(os.Stdout, recs, sales.NewUSD(*tolerance)); err != nil {
+		log.Fatal(err)
+	}
+}
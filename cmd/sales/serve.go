@@ -0,0 +1,307 @@
+package main
+
+import (
+	"bytes"
+	"flag"
+	"fmt"
+	"io"
+	"log"
+	"net/http"
+	"os"
+	"sync"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// maxIngestSize is the largest CSV body accepted by POST /ingest.
+const maxIngestSize = 32 << 20
+
+// maxPostedSize is the most posted data the server holds at once. Posts
+// beyond it are refused until older ones expire.
+const maxPostedSize = 8 * maxIngestSize
+
+// server holds the latest report, built from a directory of exports (if
+// any) together with the CSV data posted to it.
+type server struct {
+	mu     sync.Mutex
+	cfg    sales.Config
+	groups sales.Groups
+	loc    sales.Locale
+	paths  []string
+	// posts are the bodies received by POST /ingest, oldest first, kept
+	// until they expire so that the report can be rebuilt when the
+	// exports or groups change.
+	posts []post
+	// posted is the size of the posts kept, and ingested the number
+	// of posts ever accepted.
+	posted    int
+	ingested  int
+	ttl       time.Duration
+	report    *sales.Report
+	refreshes int
+	failures  int
+}
+
+// post is a CSV body received by POST /ingest.
+type post struct {
+	source string
+	data   []byte
+	at     time.Time
+}
+
+func serveCmd(args []string) {
+	fs := flag.NewFlagSet("serve", flag.ExitOnError)
+	addr := fs.String("addr", "", "address to serve the report on, such as :8080")
+	metricsAddr := fs.String("metrics", "", "address to serve Prometheus metrics on, such as :9100")
+	interval := fs.Duration("interval", 10*time.Second, "how often to check the directory for new or changed exports")
+	groupsPath := fs.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	localeTag := fs.String("locale", "en-US", "locale for currency amounts in /report.html")
+	ttl := fs.Duration("ingest-ttl", 24*time.Hour, "how long data posted to /ingest counts towards the report")
+	configPath := fs.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	fs.Parse(args)
+	if (*addr == "" && *metricsAddr == "") || fs.NArg() > 1 {
+		fmt.Fprintln(os.Stderr, "Usage: sales serve [-addr ADDR] [-metrics ADDR] [-interval DURATION] [-groups FILE] [-locale TAG] [-ingest-ttl DURATION] [-config FILE] [DIR]")
+		os.Exit(1)
+	}
+	loc, err := sales.LookupLocale(*localeTag)
+	if err != nil {
+		log.Fatal(err)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if *groupsPath == "" {
+		*groupsPath = cfg.GroupsFile
+	}
+	gw := &groupsWatcher{path: *groupsPath}
+	if err := gw.load(); err != nil {
+		log.Fatal(err)
+	}
+	s := &server{cfg: cfg, groups: gw.groups, loc: loc, ttl: *ttl}
+	s.report = s.newReport()
+	if fs.NArg() == 1 {
+		go s.watch(fs.Arg(0), *interval)
+	}
+	go s.expire(*interval)
+	if *groupsPath != "" {
+		go s.watchGroups(gw, *interval)
+	}
+	if *metricsAddr != "" {
+		mux := http.NewServeMux()
+		mux.HandleFunc("/metrics", s.metrics)
+		go func() {
+			log.Fatal(http.ListenAndServe(*metricsAddr, mux))
+		}()
+	}
+	if *addr == "" {
+		select {}
+	}
+	mux := http.NewServeMux()
+	mux.HandleFunc("/report.json", s.reportJSON)
+	mux.HandleFunc("/report.html", s.reportHTML)
+	mux.HandleFunc("/ingest", s.ingest)
+	mux.HandleFunc("/metrics", s.metrics)
+	log.Fatal(http.ListenAndServe(*addr, mux))
+}
+
+// newReport returns an empty report with the server's settings.
+func (s *server) newReport() *sales.Report {
+	r := sales.NewReport()
+	r.Configure(s.cfg)
+	r.Groups = s.groups
+	return r
+}
+
+// build makes a new report from the exports at paths and the posted data.
+func (s *server) build(paths []string, posts []post) (*sales.Report, error) {
+	r := s.newReport()
+	for _, path := range paths {
+		if err := r.ReadFile(path); err != nil {
+			return nil, err
+		}
+	}
+	for _, p := range posts {
+		if err := r.ReadCSV(bytes.NewReader(p.data), p.source); err != nil {
+			return nil, err
+		}
+	}
+	return r, r.Resolve()
+}
+
+// watch rebuilds the report whenever the exports in dir change.
+func (s *server) watch(dir string, interval time.Duration) {
+	var last string
+	for ; ; time.Sleep(interval) {
+		paths, snapshot, err := scanExports(dir, "")
+		if err != nil {
+			log.Print(err)
+			continue
+		}
+		if snapshot == last {
+			continue
+		}
+		s.mu.Lock()
+		r, err := s.build(paths, s.posts)
+		if err != nil {
+			s.failures++
+			log.Print(err)
+		} else {
+			s.paths = paths
+			s.report = r
+			s.refreshes++
+			last = snapshot
+		}
+		s.mu.Unlock()
+	}
+}
+
+// watchGroups regroups the report whenever the groups file changes, by
+// rebuilding it from the exports and posted data already read.
+func (s *server) watchGroups(gw *groupsWatcher, interval time.Duration) {
+	for ; ; time.Sleep(interval) {
+		if !gw.reload() {
+			continue
+		}
+		s.mu.Lock()
+		s.groups = gw.groups
+		r, err := s.build(s.paths, s.posts)
+		if err != nil {
+			s.failures++
+			log.Print(err)
+		} else {
+			s.report = r
+			s.refreshes++
+		}
+		s.mu.Unlock()
+	}
+}
+
+// expire drops posted data once it's older than the server's ttl.
+func (s *server) expire(interval time.Duration) {
+	for ; ; time.Sleep(interval) {
+		s.mu.Lock()
+		s.dropExpired(time.Now())
+		s.mu.Unlock()
+	}
+}
+
+// dropExpired drops the posts older than the server's ttl at now, if any,
+// and rebuilds the report without them. The server must be locked.
+func (s *server) dropExpired(now time.Time) {
+	n := 0
+	for n < len(s.posts) && now.Sub(s.posts[n].at) > s.ttl {
+		s.posted -= len(s.posts[n].data)
+		n++
+	}
+	if n == 0 {
+		return
+	}
+	s.posts = append([]post(nil), s.posts[n:]...)
+	r, err := s.build(s.paths, s.posts)
+	if err != nil {
+		s.failures++
+		log.Print(err)
+		return
+	}
+	s.report = r
+	s.refreshes++
+}
+
+// ingest adds the CSV data in the request body to the report. The source
+// name for the data can be given with the source query parameter. Data
+// that can't be read is rejected as a whole, leaving the report unchanged.
+// The data is read into the existing report, rather than rebuilding it;
+// it's kept until it expires, for when the report must be rebuilt.
+func (s *server) ingest(w http.ResponseWriter, req *http.Request) {
+	if req.Method != http.MethodPost {
+		w.Header().Set("Allow", http.MethodPost)
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+	data, err := io.ReadAll(http.MaxBytesReader(w, req.Body, maxIngestSize))
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusRequestEntityTooLarge)
+		return
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.posted+len(data) > maxPostedSize {
+		http.Error(w, "too much posted data; try again once older posts expire", http.StatusInsufficientStorage)
+		return
+	}
+	source := req.URL.Query().Get("source")
+	if source == "" {
+		source = fmt.Sprintf("ingest-%d", s.ingested+1)
+	}
+	// Reading the data into a scratch report first catches bad rows
+	// without disturbing the real one.
+	scratch := s.newReport()
+	if err := scratch.ReadCSV(bytes.NewReader(data), source); err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	err = s.report.ReadCSV(bytes.NewReader(data), source)
+	if err == nil {
+		err = s.report.Resolve()
+	}
+	if err != nil {
+		// Only checks that depend on what's already in the report,
+		// such as max_products or a conflict with another source, can
+		// fail here, so go back to the data kept before this post.
+		if r, rerr := s.build(s.paths, s.posts); rerr == nil {
+			s.report = r
+		}
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	s.posts = append(s.posts, post{source: source, data: data, at: time.Now()})
+	s.posted += len(data)
+	s.ingested++
+	w.WriteHeader(http.StatusNoContent)
+}
+
+func (s *server) reportJSON(w http.ResponseWriter, req *http.Request) {
+	s.render(w, req, "application/json", func(w io.Writer) error {
+		return s.report.WriteJSON(w)
+	})
+}
+
+func (s *server) reportHTML(w http.ResponseWriter, req *http.Request) {
+	s.render(w, req, "text/html; charset=utf-8", func(w io.Writer) error {
+		return s.report.WriteHTML(w, s.loc)
+	})
+}
+
+// render serves the output of write, which is called with the server
+// locked.
+func (s *server) render(w http.ResponseWriter, req *http.Request, contentType string, write func(io.Writer) error) {
+	if req.Method != http.MethodGet && req.Method != http.MethodHead {
+		w.Header().Set("Allow", "GET, HEAD")
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+	var buf bytes.Buffer
+	s.mu.Lock()
+	err := write(&buf)
+	s.mu.Unlock()
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusInternalServerError)
+		return
+	}
+	w.Header().Set("Content-Type", contentType)
+	w.Write(buf.Bytes())
+}
+
+func (s *server) metrics(w http.ResponseWriter, req *http.Request) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	w.Header().Set("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
+	if err := s.report.WriteMetrics(w); err != nil {
+		log.Print(err)
+		return
+	}
+	fmt.Fprintf(w, "# HELP sales_refreshes_total Times the report was rebuilt from the directory.\n# TYPE sales_refreshes_total counter\nsales_refreshes_total %d\n", s.refreshes)
+	fmt.Fprintf(w, "# HELP sales_refresh_failures_total Times the report could not be rebuilt.\n# TYPE sales_refresh_failures_total counter\nsales_refresh_failures_total %d\n", s.failures)
+}
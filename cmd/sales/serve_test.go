@@ -0,0 +1,98 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+)
+
+func newTestServer(t *testing.T) *server {
+	t.Helper()
+	s := &server{ttl: time.Hour}
+	s.report = s.newReport()
+	return s
+}
+
+func postIngest(t *testing.T, s *server, body string) int {
+	t.Helper()
+	req := httptest.NewRequest(http.MethodPost, "/ingest", strings.NewReader(body))
+	rec := httptest.NewRecorder()
+	s.ingest(rec, req)
+	return rec.Code
+}
+
+const ingestCSV = "Lineitem name,Lineitem price,Lineitem quantity\nBook,10.00,2\nVideo,20.00,1\n"
+
+func TestIngestAddsPostedDataToReport(t *testing.T) {
+	t.Parallel()
+	s := newTestServer(t)
+	if code := postIngest(t, s, ingestCSV); code != http.StatusNoContent {
+		t.Fatalf("want status %d, got %d", http.StatusNoContent, code)
+	}
+	if code := postIngest(t, s, ingestCSV); code != http.StatusNoContent {
+		t.Fatalf("want status %d, got %d", http.StatusNoContent, code)
+	}
+	if got := s.report.TotalUnits(); got != 6 {
+		t.Errorf("want 6 units, got %d", got)
+	}
+	if len(s.posts) != 2 {
+		t.Errorf("want 2 posts kept, got %d", len(s.posts))
+	}
+}
+
+func TestIngestRejectsBadDataLeavingReportUnchanged(t *testing.T) {
+	t.Parallel()
+	s := newTestServer(t)
+	postIngest(t, s, ingestCSV)
+	bad := "Lineitem name,Lineitem price,Lineitem quantity\nBook,10.00,1\nVideo,bogus,1\n"
+	if code := postIngest(t, s, bad); code != http.StatusBadRequest {
+		t.Fatalf("want status %d, got %d", http.StatusBadRequest, code)
+	}
+	if got := s.report.TotalUnits(); got != 3 {
+		t.Errorf("want 3 units, got %d", got)
+	}
+	if len(s.posts) != 1 {
+		t.Errorf("want 1 post kept, got %d", len(s.posts))
+	}
+}
+
+func TestIngestAppliesConfiguredLimits(t *testing.T) {
+	t.Parallel()
+	s := newTestServer(t)
+	s.cfg.Limits.MaxProducts = 1
+	s.report = s.newReport()
+	if code := postIngest(t, s, ingestCSV); code != http.StatusBadRequest {
+		t.Fatalf("want status %d, got %d", http.StatusBadRequest, code)
+	}
+	if !s.report.IsEmpty() {
+		t.Error("want empty report after rejected post")
+	}
+}
+
+func TestIngestRefusesDataBeyondRetainedLimit(t *testing.T) {
+	t.Parallel()
+	s := newTestServer(t)
+	s.posted = maxPostedSize
+	if code := postIngest(t, s, ingestCSV); code != http.StatusInsufficientStorage {
+		t.Fatalf("want status %d, got %d", http.StatusInsufficientStorage, code)
+	}
+}
+
+func TestDropExpiredRemovesOldPostsFromReport(t *testing.T) {
+	t.Parallel()
+	s := newTestServer(t)
+	postIngest(t, s, ingestCSV)
+	s.dropExpired(time.Now().Add(30 * time.Minute))
+	if got := s.report.TotalUnits(); got != 3 {
+		t.Fatalf("want 3 units before the post expires, got %d", got)
+	}
+	s.dropExpired(time.Now().Add(2 * time.Hour))
+	if got := s.report.TotalUnits(); got != 0 {
+		t.Errorf("want 0 units after the post expires, got %d", got)
+	}
+	if len(s.posts) != 0 || s.posted != 0 {
+		t.Errorf("want no posts kept, got %d (%d bytes)", len(s.posts), s.posted)
+	}
+}
@@ -0,0 +1,37 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"log"
+	"os"
+)
+
+func taxReportCmd(args []string) {
+	flags := flag.NewFlagSet("tax-report", flag.ExitOnError)
+	format := flags.String("format", "text", "output format: text or csv")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if flags.NArg() == 0 || (*format != "text" && *format != "csv") {
+		fmt.Fprintln(os.Stderr, "Usage: sales tax-report [-format text|csv] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, "")
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.Color = os.Getenv("NO_COLOR") == "" && isTerminal(os.Stdout)
+	readSales(r, flags.Args(), false, false)
+	if *format == "csv" {
+		err = r.WriteTaxCSV(os.Stdout)
+	} else {
+		err = r.WriteTaxReport(os.Stdout)
+	}
+	if err != nil {
+		log.Fatal(err)
+	}
+}
@@ -0,0 +1,452 @@
+package main
+
+import (
+	"bufio"
+	"flag"
+	"fmt"
+	"io"
+	"log"
+	"os"
+	"os/exec"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+	"unicode/utf8"
+
+	"github.com/bitfield/sales"
+)
+
+// chartRows is the height of the dashboard's time-series panel, in rows of
+// bars.
+const chartRows = 6
+
+// sortNames names the orders the dashboard's product table can be sorted
+// in, which the s key cycles through.
+var sortNames = map[sales.SortKey]string{
+	sales.SortByRevenue: "revenue",
+	sales.SortByUnits:   "units",
+	sales.SortByName:    "name",
+}
+
+// tuiCmd shows an interactive dashboard of the sales in the files, in the
+// terminal. It needs no terminal library: the terminal is put in raw mode
+// with stty, and drawn with ANSI escape sequences.
+func tuiCmd(args []string) {
+	flags := flag.NewFlagSet("tui", flag.ExitOnError)
+	groupsPath := flags.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := flags.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	flags.Parse(args)
+	if flags.NArg() == 0 {
+		fmt.Fprintln(os.Stderr, "Usage: sales tui [-groups FILE] [-config FILE] FILE...")
+		os.Exit(1)
+	}
+	if !isTerminal(os.Stdin) || !isTerminal(os.Stdout) {
+		log.Fatal("sales tui needs a terminal")
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r, err := newReport(cfg, *groupsPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	r.KeepRecords = true
+	readSales(r, flags.Args(), false, false)
+	d, err := newDashboard(r)
+	if err != nil {
+		log.Fatal(err)
+	}
+	saved, err := stty("-g")
+	if err != nil {
+		log.Fatal(err)
+	}
+	if _, err := stty("raw", "-echo"); err != nil {
+		log.Fatal(err)
+	}
+	// Switch to the alternate screen, so that the shell's output is
+	// back as it was afterwards, and hide the cursor.
+	fmt.Print("\x1b[?1049h\x1b[?25l")
+	err = d.run(bufio.NewReader(os.Stdin), os.Stdout)
+	fmt.Print("\x1b[?25h\x1b[?1049l")
+	stty(saved)
+	if err != nil && err != io.EOF {
+		log.Fatal(err)
+	}
+}
+
+// stty runs the stty command on the terminal, returning its output.
+func stty(args ...string) (string, error) {
+	cmd := exec.Command("stty", args...)
+	cmd.Stdin = os.Stdin
+	out, err := cmd.Output()
+	return strings.TrimSpace(string(out)), err
+}
+
+// terminalSize returns the number of rows and columns of the terminal,
+// assuming 24 by 80 if stty can't say.
+func terminalSize() (rows, cols int) {
+	out, err := stty("size")
+	if err == nil {
+		if _, err := fmt.Sscan(out, &rows, &cols); err == nil && rows > 0 && cols > 0 {
+			return rows, cols
+		}
+	}
+	return 24, 80
+}
+
+// dashboard is the state of the interactive dashboard: a table of the
+// report's products, with the selected product's sales per month charted
+// below it, or the line items of one product.
+type dashboard struct {
+	r *sales.Report
+	// records holds each report row's line items, by time.
+	records map[string][]sales.Record
+	// months are the months from the first sale to the last.
+	months []string
+	sort   sales.SortKey
+	units  bool
+	filter string
+	// editing is set while the filter is being typed.
+	editing bool
+	cursor  int
+	offset  int
+	// detail is the product whose line items are shown, if any.
+	detail       string
+	detailOffset int
+}
+
+func newDashboard(r *sales.Report) (*dashboard, error) {
+	d := &dashboard{r: r, records: map[string][]sales.Record{}, sort: sales.SortByRevenue}
+	records, err := r.Records()
+	if err != nil {
+		return nil, err
+	}
+	var first, last time.Time
+	for _, rec := range records {
+		name := r.Groups.ClassifyItem(rec.Product, rec.SKU)
+		d.records[name] = append(d.records[name], rec)
+		if rec.Time.IsZero() {
+			continue
+		}
+		if first.IsZero() || rec.Time.Before(first) {
+			first = rec.Time
+		}
+		if rec.Time.After(last) {
+			last = rec.Time
+		}
+	}
+	for _, recs := range d.records {
+		sort.SliceStable(recs, func(i, j int) bool {
+			return recs[i].Time.Before(recs[j].Time)
+		})
+	}
+	if !first.IsZero() {
+		m := time.Date(first.Year(), first.Month(), 1, 0, 0, 0, 0, time.UTC)
+		for ; m.Format("2006-01") <= last.Format("2006-01"); m = m.AddDate(0, 1, 0) {
+			d.months = append(d.months, m.Format("2006-01"))
+		}
+	}
+	return d, nil
+}
+
+// run draws the dashboard and handles keys from in until the user quits.
+func (d *dashboard) run(in *bufio.Reader, out io.Writer) error {
+	for {
+		rows, cols := terminalSize()
+		if _, err := io.WriteString(out, "\x1b[H\x1b[2J"+strings.Join(d.lines(rows, cols), "\r\n")); err != nil {
+			return err
+		}
+		key, err := readKey(in)
+		if err != nil {
+			return err
+		}
+		if !d.handle(key, rows) {
+			return nil
+		}
+	}
+}
+
+// readKey reads a keypress, returning arrow and page keys as "up", "down",
+// "pgup", and "pgdn", and other special keys as "enter", "esc", and
+// "backspace".
+func readKey(in *bufio.Reader) (string, error) {
+	b, err := in.ReadByte()
+	if err != nil {
+		return "", err
+	}
+	switch b {
+	case '\r', '\n':
+		return "enter", nil
+	case 127, 8:
+		return "backspace", nil
+	case 3:
+		// Ctrl-C doesn't interrupt in raw mode.
+		return "q", nil
+	case 0x1b:
+		if in.Buffered() == 0 {
+			return "esc", nil
+		}
+		seq := make([]byte, 0, 4)
+		for in.Buffered() > 0 && len(seq) < 4 {
+			c, _ := in.ReadByte()
+			seq = append(seq, c)
+			// The first byte, [ or O, introduces the sequence; the O
+			// of "\x1bOA" doesn't end it.
+			if len(seq) > 1 && (c >= 'A' && c <= 'Z' || c == '~') {
+				break
+			}
+		}
+		switch string(seq) {
+		case "[A", "OA":
+			return "up", nil
+		case "[B", "OB":
+			return "down", nil
+		case "[5~":
+			return "pgup", nil
+		case "[6~":
+			return "pgdn", nil
+		}
+		return "", nil
+	}
+	in.UnreadByte()
+	r, _, err := in.ReadRune()
+	return string(r), err
+}
+
+// products returns the rows of the product table, in the chosen order,
+// that match the filter.
+func (d *dashboard) products() []*sales.Product {
+	d.r.Sort = d.sort
+	filter := strings.ToLower(d.filter)
+	var products []*sales.Product
+	for _, p := range d.r.Products() {
+		if strings.Contains(strings.ToLower(p.Name), filter) {
+			products = append(products, p)
+		}
+	}
+	return products
+}
+
+// tableRows returns how many product rows fit on a screen of the given
+// height, above the chart.
+func tableRows(height int) int {
+	return max(height-chartRows-8, 1)
+}
+
+// handle updates the dashboard for key, returning false if it's time to
+// quit.
+func (d *dashboard) handle(key string, height int) bool {
+	if d.editing {
+		switch key {
+		case "enter", "esc":
+			d.editing = false
+		case "backspace":
+			_, size := utf8.DecodeLastRuneInString(d.filter)
+			d.filter = d.filter[:len(d.filter)-size]
+		case "up", "down", "pgup", "pgdn", "":
+		default:
+			d.filter += key
+		}
+		d.cursor, d.offset = 0, 0
+		return true
+	}
+	if d.detail != "" {
+		page := max(height-4, 1)
+		switch key {
+		case "q":
+			return false
+		case "esc", "backspace", "enter":
+			d.detail = ""
+		case "up", "k":
+			d.detailOffset = max(d.detailOffset-1, 0)
+		case "down", "j":
+			d.detailOffset = min(d.detailOffset+1, max(len(d.records[d.detail])-page, 0))
+		case "pgup":
+			d.detailOffset = max(d.detailOffset-page, 0)
+		case "pgdn":
+			d.detailOffset = min(d.detailOffset+page, max(len(d.records[d.detail])-page, 0))
+		}
+		return true
+	}
+	products := d.products()
+	page := tableRows(height)
+	switch key {
+	case "q":
+		return false
+	case "up", "k":
+		d.cursor--
+	case "down", "j":
+		d.cursor++
+	case "pgup":
+		d.cursor -= page
+	case "pgdn":
+		d.cursor += page
+	case "s":
+		d.sort = map[sales.SortKey]sales.SortKey{
+			sales.SortByRevenue: sales.SortByUnits,
+			sales.SortByUnits:   sales.SortByName,
+			sales.SortByName:    sales.SortByRevenue,
+		}[d.sort]
+		d.cursor, d.offset = 0, 0
+	case "u":
+		d.units = !d.units
+	case "/":
+		d.editing = true
+	case "esc":
+		d.filter = ""
+		d.cursor, d.offset = 0, 0
+	case "enter":
+		if d.cursor < len(products) {
+			d.detail = products[d.cursor].Name
+			d.detailOffset = 0
+		}
+	}
+	d.cursor = max(min(d.cursor, len(products)-1), 0)
+	if d.cursor < d.offset {
+		d.offset = d.cursor
+	}
+	if d.cursor >= d.offset+page {
+		d.offset = d.cursor - page + 1
+	}
+	return true
+}
+
+// lines returns the screen's lines, for a terminal of the given size.
+func (d *dashboard) lines(height, width int) []string {
+	var lines []string
+	if d.detail != "" {
+		lines = d.detailLines(height)
+	} else {
+		lines = d.mainLines(height, width)
+	}
+	for i, line := range lines {
+		lines[i] = truncate(line, width)
+	}
+	// The selected row is highlighted after truncating, so that the
+	// escape sequences aren't counted.
+	if d.detail == "" && len(d.products()) > 0 {
+		i := 3 + d.cursor - d.offset
+		lines[i] = "\x1b[7m" + lines[i] + strings.Repeat(" ", max(width-utf8.RuneCountInString(lines[i]), 0)) + "\x1b[0m"
+	}
+	return lines
+}
+
+func (d *dashboard) mainLines(height, width int) []string {
+	products := d.products()
+	metric := "revenue"
+	if d.units {
+		metric = "units"
+	}
+	title := fmt.Sprintf("%d products, %d units, %s revenue   sort: %s   chart: %s", len(d.r.Products()), d.r.TotalUnits(), d.r.TotalRevenue(), sortNames[d.sort], metric)
+	if d.filter != "" || d.editing {
+		title += "   filter: " + d.filter
+		if d.editing {
+			title += "_"
+		}
+	}
+	lines := []string{title, "", fmt.Sprintf("%-40s %8s %14s", "Product", "Units", "Revenue")}
+	page := tableRows(height)
+	for i := d.offset; i < d.offset+page; i++ {
+		if i >= len(products) {
+			lines = append(lines, "")
+			continue
+		}
+		p := products[i]
+		lines = append(lines, fmt.Sprintf("%-40s %8d %14s", truncate(p.Name, 40), p.Units, p.Revenue))
+	}
+	lines = append(lines, "")
+	if len(products) == 0 {
+		lines = append(lines, "No products match")
+	} else {
+		lines = append(lines, d.chart(products[d.cursor].Name, width)...)
+	}
+	lines = append(lines, "", "↑/↓ move  s sort  u units/revenue  / filter  esc clear  enter line items  q quit")
+	return lines
+}
+
+// chart returns the lines of a bar chart of the product's units or
+// revenue in each month, with a column for each month, as many of the
+// latest months as fit in width.
+func (d *dashboard) chart(product string, width int) []string {
+	metric := "Revenue"
+	if d.units {
+		metric = "Units"
+	}
+	if len(d.months) == 0 {
+		return []string{metric + " by month: no dated sales"}
+	}
+	months := d.months[max(len(d.months)-width/2, 0):]
+	values := map[string]int64{}
+	for _, rec := range d.records[product] {
+		if rec.Time.IsZero() {
+			continue
+		}
+		month := rec.Time.Format("2006-01")
+		if d.units {
+			values[month] += int64(rec.Quantity)
+		} else {
+			values[month] += int64(rec.Total())
+		}
+	}
+	var most int64
+	for _, v := range values {
+		most = max(most, v)
+	}
+	format := func(v int64) string {
+		if d.units {
+			return strconv.FormatInt(v, 10)
+		}
+		return sales.USD(v).String()
+	}
+	lines := []string{fmt.Sprintf("%s by month: %s (best month %s)", metric, product, format(most))}
+	// Each row of bars is drawn in eighths of a character cell.
+	blocks := []rune(" ▁▂▃▄▅▆▇█")
+	for row := chartRows - 1; row >= 0; row-- {
+		var line strings.Builder
+		for _, m := range months {
+			eighths := 0
+			if most > 0 {
+				eighths = int(max(values[m], 0)*chartRows*8/most) - row*8
+			}
+			line.WriteRune(blocks[max(min(eighths, 8), 0)])
+			line.WriteRune(' ')
+		}
+		lines = append(lines, line.String())
+	}
+	axis := months[0]
+	if len(months) > 1 {
+		axis += strings.Repeat(" ", max(len(months)*2-len(axis)-len(months[len(months)-1]), 1)) + months[len(months)-1]
+	}
+	return append(lines, axis)
+}
+
+func (d *dashboard) detailLines(height int) []string {
+	records := d.records[d.detail]
+	lines := []string{
+		fmt.Sprintf("%s: %d line items", d.detail, len(records)),
+		fmt.Sprintf("%-20s %-16s %5s %12s  %s", "Date", "Order", "Qty", "Price", "Source"),
+	}
+	page := max(height-4, 1)
+	for i := d.detailOffset; i < d.detailOffset+page && i < len(records); i++ {
+		rec := records[i]
+		date := ""
+		if !rec.Time.IsZero() {
+			date = rec.Time.Format("2006-01-02 15:04")
+		}
+		lines = append(lines, fmt.Sprintf("%-20s %-16s %5d %12s  %s", date, truncate(rec.OrderID, 16), rec.Quantity, rec.Price, rec.Source))
+	}
+	for len(lines) < page+2 {
+		lines = append(lines, "")
+	}
+	return append(lines, "", "↑/↓ scroll  esc back  q quit")
+}
+
+// truncate shortens s to at most width characters.
+func truncate(s string, width int) string {
+	if utf8.RuneCountInString(s) <= width {
+		return s
+	}
+	return string([]rune(s)[:max(width, 0)])
+}
@@ -0,0 +1,126 @@
+package main
+
+import (
+	"bufio"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestReadKeyNamesSpecialKeys(t *testing.T) {
+	t.Parallel()
+	in := bufio.NewReader(strings.NewReader("\x1b[Aj\x1bOB\x1b[5~\x1b[6~\r\x7fé\x03\x1b"))
+	for _, want := range []string{"up", "j", "down", "pgup", "pgdn", "enter", "backspace", "é", "q", "esc"} {
+		got, err := readKey(in)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if got != want {
+			t.Errorf("want key %q, got %q", want, got)
+		}
+	}
+}
+
+// newTestDashboard returns a dashboard of three products: Course, with
+// the most revenue, Book, with the most units, and Video.
+func newTestDashboard(t *testing.T) *dashboard {
+	t.Helper()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Add(sales.Record{Product: "Book", Quantity: 3, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Course", Quantity: 1, Price: sales.NewUSD(100)})
+	r.Add(sales.Record{Product: "Video", Quantity: 1, Price: sales.NewUSD(20)})
+	d, err := newDashboard(r)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return d
+}
+
+// selected returns the name of the product under the cursor.
+func selected(d *dashboard) string {
+	return d.products()[d.cursor].Name
+}
+
+func TestDashboardKeysMoveTheCursorWithinTheTable(t *testing.T) {
+	t.Parallel()
+	d := newTestDashboard(t)
+	for _, tc := range []struct {
+		key  string
+		want string
+	}{
+		{"down", "Book"},
+		{"j", "Video"},
+		{"down", "Video"},
+		{"k", "Book"},
+		{"pgup", "Course"},
+		{"pgdn", "Video"},
+	} {
+		d.handle(tc.key, 40)
+		if got := selected(d); got != tc.want {
+			t.Errorf("after %q: want %q selected, got %q", tc.key, tc.want, got)
+		}
+	}
+}
+
+func TestDashboardSortKeyCyclesOrders(t *testing.T) {
+	t.Parallel()
+	d := newTestDashboard(t)
+	for _, want := range []string{"Book", "Book", "Course"} {
+		d.handle("s", 40)
+		if got := selected(d); got != want {
+			t.Errorf("sorted by %s: want %q first, got %q", sortNames[d.sort], want, got)
+		}
+	}
+	if d.sort != sales.SortByRevenue {
+		t.Errorf("want sort back to revenue, got %s", sortNames[d.sort])
+	}
+}
+
+func TestDashboardFilterNarrowsTheTable(t *testing.T) {
+	t.Parallel()
+	d := newTestDashboard(t)
+	for _, key := range []string{"/", "v", "x", "backspace", "i", "enter"} {
+		d.handle(key, 40)
+	}
+	if d.editing {
+		t.Error("want filter editing finished")
+	}
+	if d.filter != "vi" {
+		t.Errorf("want filter %q, got %q", "vi", d.filter)
+	}
+	if products := d.products(); len(products) != 1 || products[0].Name != "Video" {
+		t.Errorf("want only Video shown, got %d products", len(products))
+	}
+	// Keys typed while editing are part of the filter, even q.
+	d.handle("/", 40)
+	if !d.handle("q", 40) {
+		t.Error("want q typed into the filter, not quitting")
+	}
+	d.handle("enter", 40)
+	d.handle("esc", 40)
+	if d.filter != "" || len(d.products()) != 3 {
+		t.Errorf("want filter cleared by esc, got %q", d.filter)
+	}
+}
+
+func TestDashboardEnterShowsLineItemsUntilEsc(t *testing.T) {
+	t.Parallel()
+	d := newTestDashboard(t)
+	d.handle("down", 40)
+	d.handle("enter", 40)
+	if d.detail != "Book" {
+		t.Fatalf("want Book's line items shown, got %q", d.detail)
+	}
+	if len(d.records["Book"]) != 1 {
+		t.Errorf("want 1 line item for Book, got %d", len(d.records["Book"]))
+	}
+	d.handle("esc", 40)
+	if d.detail != "" {
+		t.Errorf("want the table back after esc, got %q", d.detail)
+	}
+	if d.handle("q", 40) {
+		t.Error("want q to quit")
+	}
+}
@@ -0,0 +1,192 @@
+package main
+
+import (
+	"bytes"
+	"flag"
+	"fmt"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// inputExts are the extensions of files that watch treats as exports,
+// compressed with gzip or not; see isExport.
+var inputExts = map[string]bool{".csv": true, ".json": true, ".ndjson": true, ".jsonl": true, ".parquet": true, ".sql": true}
+
+// isExport reports whether the file called name is an export, by its
+// extension. A file ending in .gz is an export if the extension before
+// that is, as in "sales.csv.gz", since that's how it will be read.
+func isExport(name string) bool {
+	ext := strings.ToLower(filepath.Ext(name))
+	if ext == ".gz" {
+		name = strings.TrimSuffix(name, filepath.Ext(name))
+		ext = strings.ToLower(filepath.Ext(name))
+	}
+	return inputExts[ext]
+}
+
+func watchCmd(args []string) {
+	fs := flag.NewFlagSet("watch", flag.ExitOnError)
+	interval := fs.Duration("interval", 2*time.Second, "how often to check the directory for new or changed exports")
+	output := fs.String("o", "", "file to write the report to, instead of standard output")
+	format := fs.String("format", envOr("SALES_FORMAT", "text"), "output format: text, json, csv, tidy, html, excel, or parquet (or $SALES_FORMAT)")
+	localeTag := fs.String("locale", envOr("SALES_LOCALE", "en-US"), "locale for currency amounts in html and excel output (or $SALES_LOCALE)")
+	groupsPath := fs.String("groups", os.Getenv("SALES_GROUPS"), "file of product groups (or $SALES_GROUPS)")
+	configPath := fs.String("config", envOr("SALES_CONFIG", "sales.toml"), "configuration file (or $SALES_CONFIG)")
+	fs.Parse(args)
+	if fs.NArg() != 1 {
+		fmt.Fprintln(os.Stderr, "Usage: sales watch [-interval DURATION] [-o OUTPUT] [-format FORMAT] [-groups FILE] [-config FILE] DIR")
+		os.Exit(1)
+	}
+	dir := fs.Arg(0)
+	loc, err := sales.LookupLocale(*localeTag)
+	if err != nil {
+		log.Fatal(err)
+	}
+	cfg, err := loadReportConfig(*configPath)
+	if err != nil {
+		log.Fatal(err)
+	}
+	if *groupsPath == "" {
+		*groupsPath = cfg.GroupsFile
+	}
+	gw := &groupsWatcher{path: *groupsPath}
+	if err := gw.load(); err != nil {
+		log.Fatal(err)
+	}
+	var last string
+	for ; ; time.Sleep(*interval) {
+		paths, snapshot, err := scanExports(dir, *output)
+		if err != nil {
+			log.Fatal(err)
+		}
+		if gw.reload() {
+			last = ""
+		}
+		if snapshot == last {
+			continue
+		}
+		// An export that is still downloading may fail to read; it
+		// will be retried once it changes again.
+		r := sales.NewReport()
+		r.Configure(cfg)
+		r.Groups = gw.groups
+		if err := readAll(r, paths); err != nil {
+			log.Print(err)
+			continue
+		}
+		last = snapshot
+		var buf bytes.Buffer
+		if err := writeReport(&buf, r, *format, loc, r.Display()); err != nil {
+			log.Fatal(err)
+		}
+		if *output == "" {
+			fmt.Printf("== %s (%d files) ==\n", time.Now().Format(time.TimeOnly), len(paths))
+			os.Stdout.Write(buf.Bytes())
+			continue
+		}
+		if err := os.WriteFile(*output, buf.Bytes(), 0o644); err != nil {
+			log.Fatal(err)
+		}
+		log.Printf("wrote %s from %d files", *output, len(paths))
+	}
+}
+
+// scanExports lists the exports in dir, and returns them along with a
+// snapshot of their names, sizes, and modification times, which changes
+// whenever an export is added, removed, or rewritten. The file skip, which
+// is the report being written, isn't an export even if it's in dir.
+func scanExports(dir, skip string) ([]string, string, error) {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return nil, "", err
+	}
+	var paths []string
+	var snapshot strings.Builder
+	for _, e := range entries {
+		if e.IsDir() || !isExport(e.Name()) {
+			continue
+		}
+		path := filepath.Join(dir, e.Name())
+		if skip != "" && sameFile(path, skip) {
+			continue
+		}
+		info, err := e.Info()
+		if err != nil {
+			// Removed since the directory was read.
+			continue
+		}
+		paths = append(paths, path)
+		fmt.Fprintf(&snapshot, "%s %d %d\n", e.Name(), info.Size(), info.ModTime().UnixNano())
+	}
+	return paths, snapshot.String(), nil
+}
+
+// groupsWatcher holds the groups read from a groups file, and reloads them
+// whenever the file changes, so that the rules can be tweaked without
+// restarting a long-running command.
+type groupsWatcher struct {
+	path   string
+	stamp  string
+	groups sales.Groups
+}
+
+// load reads the groups file, if there is one.
+func (gw *groupsWatcher) load() error {
+	if gw.path == "" {
+		return nil
+	}
+	gw.stamp = fileStamp(gw.path)
+	groups, err := sales.LoadGroups(gw.path)
+	if err != nil {
+		return err
+	}
+	gw.groups = groups
+	return nil
+}
+
+// reload reads the groups file again if it has changed since it was last
+// read, and reports whether the groups were replaced. If the new groups
+// can't be read, the error is logged and the old ones kept.
+func (gw *groupsWatcher) reload() bool {
+	if gw.path == "" || fileStamp(gw.path) == gw.stamp {
+		return false
+	}
+	if err := gw.load(); err != nil {
+		log.Printf("keeping previous groups: %v", err)
+		return false
+	}
+	log.Printf("reloaded groups from %s", gw.path)
+	return true
+}
+
+// fileStamp returns a string that changes whenever the file at path is
+// rewritten, or "" if it can't be read.
+func fileStamp(path string) string {
+	info, err := os.Stat(path)
+	if err != nil {
+		return ""
+	}
+	return fmt.Sprintf("%d %d", info.Size(), info.ModTime().UnixNano())
+}
+
+func readAll(r *sales.Report, paths []string) error {
+	for _, path := range paths {
+		if err := r.ReadFile(path); err != nil {
+			return err
+		}
+	}
+	return r.Resolve()
+}
+
+// sameFile reports whether the paths a and b name the same file, whether or
+// not it exists yet.
+func sameFile(a, b string) bool {
+	a, errA := filepath.Abs(a)
+	b, errB := filepath.Abs(b)
+	return errA == nil && errB == nil && a == b
+}
@@ -0,0 +1,30 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestScanExportsIncludesCompressedExportsOnly(t *testing.T) {
+	t.Parallel()
+	dir := t.TempDir()
+	for _, name := range []string{"a.csv", "b.CSV.GZ", "c.jsonl.gz", "notes.txt", "backup.gz", "d.txt.gz"} {
+		if err := os.WriteFile(filepath.Join(dir, name), nil, 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	paths, _, err := scanExports(dir, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := []string{"a.csv", "b.CSV.GZ", "c.jsonl.gz"}
+	if len(paths) != len(want) {
+		t.Fatalf("want %q, got %q", want, paths)
+	}
+	for i, name := range want {
+		if got := filepath.Base(paths[i]); got != name {
+			t.Errorf("export %d: want %q, got %q", i, name, got)
+		}
+	}
+}
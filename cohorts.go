@@ -0,0 +1,109 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"time"
+)
+
+// addCustomer counts revenue from customer in month, such as "2024-03".
+func (r *Report) addCustomer(customer, month string, revenue USD) {
+	months := r.customers[customer]
+	if months == nil {
+		months = map[string]USD{}
+		r.customers[customer] = months
+	}
+	months[month] += revenue
+}
+
+// Cohort is the customers who first bought in a given month, and the
+// revenue they have brought in since.
+type Cohort struct {
+	// Month is the month of the customers' first purchase, such as
+	// "2024-03".
+	Month     string
+	Customers int
+	// Revenue is the cohort's revenue in each month since Month, starting
+	// with Month itself, up to the month of the latest sale in the report.
+	Revenue []USD
+}
+
+// Cohorts groups customers by the month of their first purchase, in month
+// order. Customers are identified by their email addresses, so records
+// without one aren't counted.
+func (r *Report) Cohorts() []Cohort {
+	var latest string
+	for _, months := range r.customers {
+		for m := range months {
+			latest = max(latest, m)
+		}
+	}
+	end, err := time.Parse("2006-01", latest)
+	if err != nil {
+		return nil
+	}
+	cohorts := map[string]*Cohort{}
+	for _, months := range r.customers {
+		first := latest
+		for m := range months {
+			first = min(first, m)
+		}
+		start, _ := time.Parse("2006-01", first)
+		c := cohorts[first]
+		if c == nil {
+			c = &Cohort{Month: first, Revenue: make([]USD, monthsBetween(start, end)+1)}
+			cohorts[first] = c
+		}
+		c.Customers++
+		for m, rev := range months {
+			t, _ := time.Parse("2006-01", m)
+			c.Revenue[monthsBetween(start, t)] += rev
+		}
+	}
+	list := make([]Cohort, 0, len(cohorts))
+	for _, c := range cohorts {
+		list = append(list, *c)
+	}
+	sort.Slice(list, func(i, j int) bool {
+		return list[i].Month < list[j].Month
+	})
+	return list
+}
+
+// monthsBetween returns the number of calendar months from a to b.
+func monthsBetween(a, b time.Time) int {
+	return (b.Year()-a.Year())*12 + int(b.Month()-a.Month())
+}
+
+// WriteCohorts writes the cohorts as a text table, with a column for the
+// revenue from each cohort in each month after its first.
+func (r *Report) WriteCohorts(w io.Writer) error {
+	cohorts := r.Cohorts()
+	if len(cohorts) == 0 {
+		_, err := fmt.Fprintln(w, "No customer emails found")
+		return err
+	}
+	t := &table{
+		header: []string{"Cohort", "Customers"},
+		right:  []bool{false, true},
+		color:  r.Color,
+	}
+	for i := range cohorts[0].Revenue {
+		t.header = append(t.header, fmt.Sprintf("+%d", i))
+		t.right = append(t.right, true)
+	}
+	for _, c := range cohorts {
+		row := []string{c.Month, strconv.Itoa(c.Customers)}
+		for i := range cohorts[0].Revenue {
+			if i < len(c.Revenue) {
+				row = append(row, c.Revenue[i].String())
+			} else {
+				row = append(row, "")
+			}
+		}
+		t.add(row...)
+	}
+	return t.write(w)
+}
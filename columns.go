@@ -0,0 +1,131 @@
+package sales
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// OutputColumn is a column of the report that can be chosen for output,
+// after the product name; see RenderOptions.Columns.
+type OutputColumn string
+
+const (
+	// ColumnUnits is the number of units sold.
+	ColumnUnits OutputColumn = "units"
+	// ColumnRevenue is the revenue.
+	ColumnRevenue OutputColumn = "revenue"
+	// ColumnAverage is the average price per unit.
+	ColumnAverage OutputColumn = "avg"
+	// ColumnPercent is the share of the report's total revenue.
+	ColumnPercent OutputColumn = "percent"
+	// ColumnFree is the number of units given away; see Report.Comps.
+	ColumnFree OutputColumn = "free"
+	// ColumnCost, ColumnProfit, and ColumnMargin are the cost of goods,
+	// the gross profit, and the margin; see Report.Costs.
+	ColumnCost   OutputColumn = "cost"
+	ColumnProfit OutputColumn = "profit"
+	ColumnMargin OutputColumn = "margin"
+)
+
+// outputColumns are the output columns, in the order listed in errors.
+var outputColumns = []OutputColumn{ColumnUnits, ColumnRevenue, ColumnAverage, ColumnPercent, ColumnFree, ColumnCost, ColumnProfit, ColumnMargin}
+
+// ParseOutputColumns parses a comma-separated list of output columns, such
+// as "units,revenue,avg,percent".
+func ParseOutputColumns(s string) ([]OutputColumn, error) {
+	var cols []OutputColumn
+	for _, name := range strings.Split(s, ",") {
+		col := OutputColumn(strings.ToLower(strings.TrimSpace(name)))
+		if col.title() == "" {
+			names := make([]string, len(outputColumns))
+			for i, c := range outputColumns {
+				names[i] = string(c)
+			}
+			return nil, fmt.Errorf("unknown column %q (want one of %s)", name, strings.Join(names, ", "))
+		}
+		cols = append(cols, col)
+	}
+	return cols, nil
+}
+
+// title returns the heading of the column, or "" if it isn't one of the
+// output columns.
+func (c OutputColumn) title() string {
+	switch c {
+	case ColumnUnits:
+		return "Units"
+	case ColumnRevenue:
+		return "Revenue"
+	case ColumnAverage:
+		return "Avg price"
+	case ColumnPercent:
+		return "Share"
+	case ColumnFree:
+		return "Free"
+	case ColumnCost:
+		return "Cost"
+	case ColumnProfit:
+		return "Profit"
+	case ColumnMargin:
+		return "Margin"
+	}
+	return ""
+}
+
+// defaultColumns returns the columns shown if none are chosen: units and
+// revenue, with the free units if Comps is set, and the costs if Costs are
+// set.
+func (r *Report) defaultColumns() []OutputColumn {
+	cols := []OutputColumn{ColumnUnits, ColumnRevenue}
+	if r.Comps {
+		cols = append(cols, ColumnFree)
+	}
+	if len(r.Costs) > 0 {
+		cols = append(cols, ColumnCost, ColumnProfit, ColumnMargin)
+	}
+	return cols
+}
+
+// rowCost is the cost of goods for a row of output, if known, and the
+// revenue that its profit is worked out from.
+type rowCost struct {
+	known   bool
+	cost    USD
+	revenue USD
+}
+
+// cells returns the cells in columns cols for the row p, formatting
+// amounts with money. Its share is of total revenue.
+func cells(cols []OutputColumn, p Product, c rowCost, total USD, money func(USD) string) []string {
+	cells := make([]string, len(cols))
+	for i, col := range cols {
+		switch col {
+		case ColumnUnits:
+			cells[i] = strconv.Itoa(p.Units)
+		case ColumnRevenue:
+			cells[i] = money(p.Revenue)
+		case ColumnAverage:
+			if p.Units != 0 {
+				cells[i] = money(p.Revenue / USD(p.Units))
+			}
+		case ColumnPercent:
+			cells[i] = percent(p.Revenue, total)
+		case ColumnFree:
+			cells[i] = strconv.Itoa(p.Comps)
+		case ColumnCost:
+			if c.known {
+				cells[i] = money(c.cost)
+			}
+		case ColumnProfit:
+			if c.known {
+				cells[i] = money(c.revenue - c.cost)
+			}
+		case ColumnMargin:
+			if c.known {
+				cells[i] = percent(c.revenue-c.cost, c.revenue)
+			}
+		}
+	}
+	return cells
+}
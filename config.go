@@ -0,0 +1,795 @@
+package sales
+
+import (
+	"bufio"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Config holds the settings read from a sales.toml file.
+type Config struct {
+	Limits         Limits
+	Columns        Columns
+	Dialect        Dialect
+	ConflictPolicy ConflictPolicy
+	CurrencyPolicy CurrencyPolicy
+	Period         Period
+	Timestamps     Timestamps
+	Bundles        Bundles
+	Renames        Renames
+	Costs          Costs
+	Subscriptions  Subscriptions
+	Exclude        Exclusions
+	B2B            B2BRules
+	Comps          bool
+	SourceColumns  []SourceColumns
+	SMTP           SMTP
+	Rounding       Rounding
+	GroupsFile     string
+	Currency       string
+	Sort           SortKey
+	RatesFile      string
+	Top            int
+	OtherLabel     string
+}
+
+// Configure applies the settings in cfg that affect how r reads and counts
+// sales: the limits, columns, CSV dialect, conflict and currency policies,
+// periods, timestamp settings, rounding, bundles, renames, costs,
+// subscription rules, exclusions, comps, currency, and sort order. The
+// groups and rates files that cfg names are left for the caller to load.
+func (r *Report) Configure(cfg Config) {
+	r.Limits = cfg.Limits
+	r.Columns = cfg.Columns
+	r.SourceColumns = cfg.SourceColumns
+	r.Dialect = cfg.Dialect
+	r.ConflictPolicy = cfg.ConflictPolicy
+	if cfg.ConflictPolicy.Mode != "" {
+		// Resolve rebuilds the report from its records.
+		r.KeepRecords = true
+	}
+	r.CurrencyPolicy = cfg.CurrencyPolicy
+	r.Period = cfg.Period
+	r.Timestamps = cfg.Timestamps
+	r.Rounding = cfg.Rounding
+	r.Bundles = cfg.Bundles
+	r.Renames = cfg.Renames
+	r.Costs = cfg.Costs
+	r.Subscriptions = cfg.Subscriptions
+	r.Exclude = cfg.Exclude
+	r.Comps = cfg.Comps
+	r.Currency = cfg.Currency
+	r.Sort = cfg.Sort
+}
+
+// SMTP holds the settings for sending reports by email. The password is
+// not kept in the configuration file; see the sales command's -email flag.
+type SMTP struct {
+	Host     string
+	Port     int
+	Username string
+	From     string
+}
+
+// LoadConfig reads the configuration file at path.
+func LoadConfig(path string) (Config, error) {
+	tables, err := loadTables(path)
+	if err != nil {
+		return Config{}, err
+	}
+	cfg, err := configFromTables(tables)
+	if err != nil {
+		return Config{}, fmt.Errorf("%s: %w", path, err)
+	}
+	return cfg, nil
+}
+
+// GlobalConfigPath returns the path of the user's own configuration file,
+// whose settings apply wherever the sales command is run:
+// $XDG_CONFIG_HOME/sales/config.toml, or ~/.config/sales/config.toml. It
+// returns "" if the home directory isn't known.
+func GlobalConfigPath() string {
+	dir := os.Getenv("XDG_CONFIG_HOME")
+	if dir == "" {
+		home, err := os.UserHomeDir()
+		if err != nil {
+			return ""
+		}
+		dir = filepath.Join(home, ".config")
+	}
+	return filepath.Join(dir, "sales", "config.toml")
+}
+
+// LoadConfigs reads the configuration files at paths, such as
+// GlobalConfigPath and then a project's sales.toml, and layers them: each
+// setting comes from the last file that gives it, so later files take
+// precedence. Files that don't exist, and empty paths, are skipped.
+func LoadConfigs(paths ...string) (Config, error) {
+	merged, origins, err := loadLayers(paths)
+	if err != nil {
+		return Config{}, err
+	}
+	return configFromLayers(merged, origins)
+}
+
+// loadLayers reads the tables of the configuration files at paths, as
+// LoadConfigs does, and merges them. It also returns the file that gave
+// each setting, keyed by "table.key".
+func loadLayers(paths []string) (map[string]map[string]string, map[string]string, error) {
+	merged := map[string]map[string]string{}
+	origins := map[string]string{}
+	for _, path := range paths {
+		if path == "" {
+			continue
+		}
+		tables, err := loadTables(path)
+		if errors.Is(err, fs.ErrNotExist) {
+			continue
+		}
+		if err != nil {
+			return nil, nil, err
+		}
+		// Check each file's settings on their own, so that errors say
+		// which file they're in. Settings that depend on each other may
+		// be given in different files, so are checked once merged.
+		if _, err := parseTables(tables); err != nil {
+			return nil, nil, fmt.Errorf("%s: %w", path, err)
+		}
+		for table, values := range tables {
+			if merged[table] == nil {
+				merged[table] = map[string]string{}
+			}
+			for key, v := range values {
+				merged[table][key] = v
+				origins[table+"."+key] = path
+			}
+		}
+	}
+	return merged, origins, nil
+}
+
+// configFromLayers returns the configuration given by the merged tables of
+// several layers, naming the file or variable in origins that gave the
+// setting at fault if settings that depend on each other disagree.
+func configFromLayers(merged map[string]map[string]string, origins map[string]string) (Config, error) {
+	cfg, err := parseTables(merged)
+	if err != nil {
+		return Config{}, err
+	}
+	if setting, err := cfg.check(); err != nil {
+		if origin := origins[setting]; origin != "" {
+			return Config{}, fmt.Errorf("%s: %w", origin, err)
+		}
+		return Config{}, err
+	}
+	return cfg, nil
+}
+
+// EnvSettings maps the environment variables that LoadConfigsWithEnv and
+// ConfigFromEnv read to the configuration settings they give, as
+// "table.key". They let containers and cron jobs configure reports without
+// a configuration file.
+var EnvSettings = map[string]string{
+	"SALES_GROUPS":   "defaults.groups",
+	"SALES_CURRENCY": "defaults.currency",
+	"SALES_SORT":     "defaults.sort",
+	"SALES_ROUNDING": "money.rounding",
+	"SALES_ZONE":     "time.zone",
+	"SALES_RATES":    "currency.rates",
+}
+
+// LoadConfigsWithEnv is like LoadConfigs, but the environment variables in
+// EnvSettings that are set take precedence over the files.
+func LoadConfigsWithEnv(paths ...string) (Config, error) {
+	merged, origins, err := loadLayers(paths)
+	if err != nil {
+		return Config{}, err
+	}
+	for name, setting := range EnvSettings {
+		v, ok := os.LookupEnv(name)
+		if !ok {
+			continue
+		}
+		table, key, _ := strings.Cut(setting, ".")
+		// Check each variable on its own, so that errors say which
+		// variable is wrong.
+		if _, err := parseTables(map[string]map[string]string{table: {key: v}}); err != nil {
+			return Config{}, fmt.Errorf("%s: %w", name, err)
+		}
+		if merged[table] == nil {
+			merged[table] = map[string]string{}
+		}
+		merged[table][key] = v
+		origins[setting] = name
+	}
+	return configFromLayers(merged, origins)
+}
+
+// ConfigFromEnv returns the configuration given by the environment
+// variables in EnvSettings alone.
+func ConfigFromEnv() (Config, error) {
+	return LoadConfigsWithEnv()
+}
+
+// loadTables reads the tables of the configuration file at path. Relative
+// paths to the groups and rates files are made relative to the directory
+// that the configuration file is in.
+func loadTables(path string) (map[string]map[string]string, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	tables, err := parseTOML(f)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	for _, setting := range [][2]string{{"defaults", "groups"}, {"currency", "rates"}} {
+		table, key := setting[0], setting[1]
+		if file := tables[table][key]; file != "" && file != "ecb" && !filepath.IsAbs(file) {
+			tables[table][key] = filepath.Join(filepath.Dir(path), file)
+		}
+	}
+	return tables, nil
+}
+
+// ParseConfig reads configuration in a simple subset of TOML: tables,
+// comments, and key/value pairs whose values are integers, booleans,
+// double-quoted strings, or arrays of strings. The [rename] table maps
+// quoted regular expressions to replacements, and the [subscriptions] table
+// maps them to recurrences, such as "monthly"; the rules in each are tried
+// in alphabetical order of pattern. In the [exclude] table, zero_price can
+// be "comps" to count zero-price sales as comps rather than exclude them.
+// The [currency] table's mixed setting is a CurrencyPolicy mode, and the
+// [exchange_rates] table maps currency codes to their rates for it, such as
+// EUR = 1.08. Its rates setting names a file of rates by date, for
+// LoadRates, or is "ecb" for FetchECBRates. The [defaults] table sets
+// defaults for the sales command: the groups file, the currency, the sort
+// order ("name", "revenue", or "units"), the number of top rows to show,
+// and the label of the row combining the rest, such as "Everything else
+// (%d)", where %d is the number of products combined.
+func ParseConfig(rd io.Reader) (Config, error) {
+	tables, err := parseTOML(rd)
+	if err != nil {
+		return Config{}, err
+	}
+	return configFromTables(tables)
+}
+
+// configFromTables returns the configuration given by the parsed tables of
+// a configuration file.
+func configFromTables(tables map[string]map[string]string) (Config, error) {
+	cfg, err := parseTables(tables)
+	if err != nil {
+		return Config{}, err
+	}
+	if _, err := cfg.check(); err != nil {
+		return Config{}, err
+	}
+	return cfg, nil
+}
+
+// check returns an error if settings in cfg that depend on each other
+// disagree, along with the setting at fault, as "table.key".
+func (cfg Config) check() (setting string, err error) {
+	if len(cfg.Period.Calendar) > 0 && len(cfg.Period.YearStarts) == 0 {
+		return "periods.calendar", errors.New("periods.calendar needs periods.year_starts")
+	}
+	return "", nil
+}
+
+// parseTables returns the settings given by the parsed tables of a
+// configuration file, checking each on its own.
+func parseTables(tables map[string]map[string]string) (Config, error) {
+	var cfg Config
+	for table, values := range tables {
+		switch table {
+		case "limits":
+			for key, v := range values {
+				var dst *int64
+				switch key {
+				case "max_file_size":
+					dst = &cfg.Limits.MaxFileSize
+				case "max_rows":
+					dst = &cfg.Limits.MaxRows
+				case "max_products":
+					dst = &cfg.Limits.MaxProducts
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+				n, err := strconv.ParseInt(v, 10, 64)
+				if err != nil || n < 0 {
+					return Config{}, fmt.Errorf("%s.%s: want a non-negative integer, got %q", table, key, v)
+				}
+				*dst = n
+			}
+		case "columns":
+			if err := parseColumns(&cfg.Columns, table, values); err != nil {
+				return Config{}, err
+			}
+		case "csv":
+			for key, v := range values {
+				switch key {
+				case "delimiter":
+					d, err := ParseDelimiter(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Dialect.Delimiter = d
+				case "lazy_quotes":
+					b, err := strconv.ParseBool(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: want true or false, got %q", table, key, v)
+					}
+					cfg.Dialect.LazyQuotes = b
+				case "encoding":
+					cfg.Dialect.Encoding = v
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "conflicts":
+			for key, v := range values {
+				switch key {
+				case "policy":
+					mode, err := ParseConflictMode(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.ConflictPolicy.Mode = mode
+				case "prefer":
+					list, err := parseStringArray(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.ConflictPolicy.Prefer = list
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "currency":
+			for key, v := range values {
+				switch key {
+				case "mixed":
+					mode, err := ParseCurrencyMode(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.CurrencyPolicy.Mode = mode
+				case "rates":
+					cfg.RatesFile = v
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "exchange_rates":
+			cfg.CurrencyPolicy.Rates = map[string]float64{}
+			for code, v := range values {
+				rate, err := strconv.ParseFloat(v, 64)
+				if err != nil || rate <= 0 {
+					return Config{}, fmt.Errorf("%s.%s: want a positive exchange rate, got %q", table, code, v)
+				}
+				cfg.CurrencyPolicy.Rates[strings.ToUpper(code)] = rate
+			}
+		case "periods":
+			for key, v := range values {
+				switch key {
+				case "unit":
+					unit, err := ParsePeriodUnit(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Period.Unit = unit
+				case "year_start":
+					n, err := strconv.Atoi(v)
+					if err != nil || n < 1 || n > 12 {
+						return Config{}, fmt.Errorf("%s.%s: want a month from 1 to 12, got %q", table, key, v)
+					}
+					cfg.Period.YearStart = time.Month(n)
+				case "calendar":
+					weeks, err := ParseCalendar(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Period.Calendar = weeks
+				case "year_starts":
+					dates, err := parseStringArray(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					for _, d := range dates {
+						t, err := time.Parse(time.DateOnly, d)
+						if err != nil {
+							return Config{}, fmt.Errorf("%s.%s: want dates such as \"2024-02-04\", got %q", table, key, d)
+						}
+						if n := len(cfg.Period.YearStarts); n > 0 && !t.After(cfg.Period.YearStarts[n-1]) {
+							return Config{}, fmt.Errorf("%s.%s: dates must be in order", table, key)
+						}
+						cfg.Period.YearStarts = append(cfg.Period.YearStarts, t)
+					}
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "smtp":
+			for key, v := range values {
+				switch key {
+				case "host":
+					cfg.SMTP.Host = v
+				case "port":
+					n, err := strconv.Atoi(v)
+					if err != nil || n < 1 || n > 65535 {
+						return Config{}, fmt.Errorf("%s.%s: want a port number, got %q", table, key, v)
+					}
+					cfg.SMTP.Port = n
+				case "username":
+					cfg.SMTP.Username = v
+				case "from":
+					cfg.SMTP.From = v
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "money":
+			for key, v := range values {
+				switch key {
+				case "rounding":
+					mode, err := ParseRounding(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Rounding = mode
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "defaults":
+			for key, v := range values {
+				switch key {
+				case "groups":
+					cfg.GroupsFile = v
+				case "currency":
+					if len(v) != 3 {
+						return Config{}, fmt.Errorf("%s.%s: want a three-letter currency code, such as \"EUR\", got %q", table, key, v)
+					}
+					cfg.Currency = strings.ToUpper(v)
+				case "sort":
+					order, err := ParseSortKey(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Sort = order
+				case "top":
+					n, err := strconv.Atoi(v)
+					if err != nil || n < 0 {
+						return Config{}, fmt.Errorf("%s.%s: want a number of rows, got %q", table, key, v)
+					}
+					cfg.Top = n
+				case "other":
+					if n := strings.Count(v, "%"); n > 1 || n == 1 && !strings.Contains(v, "%d") {
+						return Config{}, fmt.Errorf("%s.%s: want a label with at most one %%d, got %q", table, key, v)
+					}
+					cfg.OtherLabel = v
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "exclude":
+			for key, v := range values {
+				switch key {
+				case "zero_price", "test":
+					if key == "zero_price" && v == "comps" {
+						cfg.Comps = true
+						continue
+					}
+					b, err := strconv.ParseBool(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: want true or false, got %q", table, key, v)
+					}
+					if key == "zero_price" {
+						cfg.Exclude.ZeroPrice = b
+					} else {
+						cfg.Exclude.Test = b
+					}
+				case "email_domains":
+					domains, err := parseStringArray(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Exclude.EmailDomains = domains
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "b2b":
+			for key, v := range values {
+				switch key {
+				case "domains", "companies":
+					list, err := parseStringArray(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					if key == "domains" {
+						cfg.B2B.Domains = list
+					} else {
+						cfg.B2B.Companies = list
+					}
+				case "min_quantity":
+					n, err := strconv.Atoi(v)
+					if err != nil || n < 1 {
+						return Config{}, fmt.Errorf("%s.%s: want a number of units, got %q", table, key, v)
+					}
+					cfg.B2B.MinQuantity = n
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		case "bundles":
+			cfg.Bundles = Bundles{}
+			for name, v := range values {
+				parts, err := ParseBundle(v)
+				if err != nil {
+					return Config{}, fmt.Errorf("bundles.%q: %w", name, err)
+				}
+				cfg.Bundles[name] = parts
+			}
+		case "costs":
+			cfg.Costs = Costs{}
+			for name, v := range values {
+				c, err := ParseCost(v)
+				if err != nil {
+					return Config{}, fmt.Errorf("costs.%q: %w", name, err)
+				}
+				cfg.Costs[name] = c
+			}
+		case "rename":
+			cfg.Renames = nil
+			for pattern, v := range values {
+				re, err := regexp.Compile(pattern)
+				if err != nil {
+					return Config{}, fmt.Errorf("rename.%q: %w", pattern, err)
+				}
+				cfg.Renames = append(cfg.Renames, Rename{Pattern: re, Replacement: v})
+			}
+			sort.Slice(cfg.Renames, func(i, j int) bool {
+				return cfg.Renames[i].Pattern.String() < cfg.Renames[j].Pattern.String()
+			})
+		case "subscriptions":
+			cfg.Subscriptions = nil
+			for pattern, v := range values {
+				re, err := regexp.Compile(pattern)
+				if err != nil {
+					return Config{}, fmt.Errorf("subscriptions.%q: %w", pattern, err)
+				}
+				months, err := ParseRecurrence(v)
+				if err == nil && months == 0 {
+					err = fmt.Errorf("want a recurrence such as \"monthly\", got %q", v)
+				}
+				if err != nil {
+					return Config{}, fmt.Errorf("subscriptions.%q: %w", pattern, err)
+				}
+				cfg.Subscriptions = append(cfg.Subscriptions, SubscriptionRule{Pattern: re, Months: months})
+			}
+			sort.Slice(cfg.Subscriptions, func(i, j int) bool {
+				return cfg.Subscriptions[i].Pattern.String() < cfg.Subscriptions[j].Pattern.String()
+			})
+		case "time":
+			for key, v := range values {
+				switch key {
+				case "formats":
+					layouts, err := parseStringArray(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					cfg.Timestamps.Layouts = layouts
+				case "zone", "input_zone":
+					loc, err := time.LoadLocation(v)
+					if err != nil {
+						return Config{}, fmt.Errorf("%s.%s: %w", table, key, err)
+					}
+					if key == "zone" {
+						cfg.Timestamps.Zone = loc
+					} else {
+						cfg.Timestamps.InputZone = loc
+					}
+				default:
+					return Config{}, fmt.Errorf("unknown setting %s.%s", table, key)
+				}
+			}
+		default:
+			// Tables such as [columns."gumroad-*.csv"] set the
+			// columns for particular sources.
+			if pattern, ok := strings.CutPrefix(table, "columns."); ok {
+				pattern, err := strconv.Unquote(pattern)
+				if err != nil {
+					return Config{}, fmt.Errorf("table [%s]: want a quoted file name pattern", table)
+				}
+				if _, err := path.Match(pattern, ""); err != nil {
+					return Config{}, fmt.Errorf("table [%s]: %w", table, err)
+				}
+				sc := SourceColumns{Pattern: pattern}
+				if err := parseColumns(&sc.Columns, table, values); err != nil {
+					return Config{}, err
+				}
+				cfg.SourceColumns = append(cfg.SourceColumns, sc)
+				sort.Slice(cfg.SourceColumns, func(i, j int) bool {
+					return cfg.SourceColumns[i].Pattern < cfg.SourceColumns[j].Pattern
+				})
+				continue
+			}
+			return Config{}, fmt.Errorf("unknown table [%s]", table)
+		}
+	}
+	return cfg, nil
+}
+
+// parseColumns sets the column names in c from the settings in a columns
+// table.
+func parseColumns(c *Columns, table string, values map[string]string) error {
+	for key, v := range values {
+		var dst *string
+		switch key {
+		case "order_id":
+			dst = &c.OrderID
+		case "name":
+			dst = &c.Name
+		case "price":
+			dst = &c.Price
+		case "quantity":
+			dst = &c.Quantity
+		case "total":
+			dst = &c.Total
+		case "channel":
+			dst = &c.Channel
+		case "created":
+			dst = &c.Created
+		case "currency":
+			dst = &c.Currency
+		case "email":
+			dst = &c.Email
+		case "billing_name":
+			dst = &c.BillingName
+		case "sku":
+			dst = &c.SKU
+		case "variant":
+			dst = &c.Variant
+		case "country":
+			dst = &c.Country
+		case "region":
+			dst = &c.Region
+		case "fee":
+			dst = &c.Fee
+		case "referrer":
+			dst = &c.Referrer
+		case "affiliate":
+			dst = &c.Affiliate
+		case "affiliate_fee":
+			dst = &c.AffiliateFee
+		case "recurrence":
+			dst = &c.Recurrence
+		case "test":
+			dst = &c.Test
+		case "tax":
+			dst = &c.Tax
+		default:
+			return fmt.Errorf("unknown setting %s.%s", table, key)
+		}
+		*dst = v
+	}
+	return nil
+}
+
+func parseTOML(rd io.Reader) (map[string]map[string]string, error) {
+	tables := map[string]map[string]string{}
+	table := ""
+	scanner := bufio.NewScanner(rd)
+	line := 0
+	for scanner.Scan() {
+		line++
+		text := strings.TrimSpace(scanner.Text())
+		if text == "" || strings.HasPrefix(text, "#") {
+			continue
+		}
+		if strings.HasPrefix(text, "[") && strings.HasSuffix(text, "]") {
+			table = strings.TrimSpace(text[1 : len(text)-1])
+			continue
+		}
+		var key, value string
+		if strings.HasPrefix(text, `"`) {
+			// A quoted key, such as a product name, may contain any
+			// characters.
+			q, err := strconv.QuotedPrefix(text)
+			if err != nil {
+				return nil, fmt.Errorf("line %d: bad key in %q", line, text)
+			}
+			key, _ = strconv.Unquote(q)
+			rest := strings.TrimSpace(text[len(q):])
+			if !strings.HasPrefix(rest, "=") {
+				return nil, fmt.Errorf("line %d: want key = value, got %q", line, text)
+			}
+			value = rest[1:]
+		} else {
+			var ok bool
+			key, value, ok = strings.Cut(text, "=")
+			if !ok {
+				return nil, fmt.Errorf("line %d: want key = value, got %q", line, text)
+			}
+		}
+		key = strings.TrimSpace(key)
+		value = stripComment(value)
+		if strings.HasPrefix(value, `"`) {
+			s, err := strconv.Unquote(value)
+			if err != nil {
+				return nil, fmt.Errorf("line %d: bad string %s", line, value)
+			}
+			value = s
+		}
+		if tables[table] == nil {
+			tables[table] = map[string]string{}
+		}
+		tables[table][key] = value
+	}
+	return tables, scanner.Err()
+}
+
+// stripComment returns a TOML value without any comment after it, and
+// without surrounding space. A # inside a quoted string, including in an
+// array, is part of the string.
+func stripComment(value string) string {
+	quoted := false
+	for i := 0; i < len(value); i++ {
+		switch value[i] {
+		case '\\':
+			if quoted {
+				i++
+			}
+		case '"':
+			quoted = !quoted
+		case '#':
+			if !quoted {
+				return strings.TrimSpace(value[:i])
+			}
+		}
+	}
+	return strings.TrimSpace(value)
+}
+
+// parseStringArray parses a TOML array of strings, such as ["a", "b"].
+// The strings may contain commas and escaped quotes, as in
+// ["Jan 2, 2006", "Acme \"West\""].
+func parseStringArray(v string) ([]string, error) {
+	if !strings.HasPrefix(v, "[") || !strings.HasSuffix(v, "]") {
+		return nil, fmt.Errorf("want an array of strings, got %q", v)
+	}
+	rest := strings.TrimSpace(v[1 : len(v)-1])
+	var list []string
+	for rest != "" {
+		q, err := strconv.QuotedPrefix(rest)
+		if err != nil || !strings.HasPrefix(q, `"`) {
+			return nil, fmt.Errorf("want an array of strings, got %q", v)
+		}
+		s, _ := strconv.Unquote(q)
+		list = append(list, s)
+		rest = strings.TrimSpace(rest[len(q):])
+		if rest == "" {
+			break
+		}
+		after, ok := strings.CutPrefix(rest, ",")
+		if !ok {
+			return nil, fmt.Errorf("want an array of strings, got %q", v)
+		}
+		// A trailing comma is allowed, as in TOML.
+		rest = strings.TrimSpace(after)
+	}
+	return list, nil
+}
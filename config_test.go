@@ -0,0 +1,143 @@
+package sales_test
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestConfigureAppliesSettingsFromConfig(t *testing.T) {
+	t.Parallel()
+	cfg, err := sales.ParseConfig(strings.NewReader(`
+[columns]
+name = "Item"
+
+[defaults]
+currency = "eur"
+sort = "revenue"
+
+[exclude]
+test = true
+`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.Configure(cfg)
+	if r.Columns.Name != "Item" {
+		t.Errorf("Columns.Name: want %q, got %q", "Item", r.Columns.Name)
+	}
+	if r.Currency != "EUR" {
+		t.Errorf("Currency: want EUR, got %q", r.Currency)
+	}
+	if r.Sort != sales.SortByRevenue {
+		t.Errorf("Sort: want SortByRevenue, got %v", r.Sort)
+	}
+	if !r.Exclude.Test {
+		t.Error("Exclude.Test: want true, got false")
+	}
+}
+
+func TestParseConfigStripsCommentsOutsideQuotesOnly(t *testing.T) {
+	t.Parallel()
+	cfg, err := sales.ParseConfig(strings.NewReader(`
+[columns]
+name = "Item # (name)" # the product column
+price = "Price"# no space before the comment
+
+[defaults]
+currency = eur # euros
+
+[conflicts]
+prefer = ["shop#1.csv", "shop \"#2\".csv"] # most trusted first
+`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if want := "Item # (name)"; cfg.Columns.Name != want {
+		t.Errorf("Columns.Name: want %q, got %q", want, cfg.Columns.Name)
+	}
+	if want := "Price"; cfg.Columns.Price != want {
+		t.Errorf("Columns.Price: want %q, got %q", want, cfg.Columns.Price)
+	}
+	if want := "EUR"; cfg.Currency != want {
+		t.Errorf("Currency: want %q, got %q", want, cfg.Currency)
+	}
+	want := []string{"shop#1.csv", `shop "#2".csv`}
+	got := cfg.ConflictPolicy.Prefer
+	if len(got) != len(want) || got[0] != want[0] || got[1] != want[1] {
+		t.Errorf("ConflictPolicy.Prefer: want %q, got %q", want, got)
+	}
+}
+
+func TestParseConfigReadsArraysOfStringsWithCommasAndQuotes(t *testing.T) {
+	t.Parallel()
+	cfg, err := sales.ParseConfig(strings.NewReader(`
+[time]
+formats = ["Jan 2, 2006", "2006-01-02",]
+
+[b2b]
+companies = ["Acme, Inc.", "The \"Best\" Widgets, Ltd"]
+`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, tc := range []struct {
+		name      string
+		want, got []string
+	}{
+		{"Timestamps.Layouts", []string{"Jan 2, 2006", "2006-01-02"}, cfg.Timestamps.Layouts},
+		{"B2B.Companies", []string{"Acme, Inc.", `The "Best" Widgets, Ltd`}, cfg.B2B.Companies},
+	} {
+		if strings.Join(tc.got, "|") != strings.Join(tc.want, "|") || len(tc.got) != len(tc.want) {
+			t.Errorf("%s: want %q, got %q", tc.name, tc.want, tc.got)
+		}
+	}
+}
+
+func TestParseConfigRejectsMalformedArraysOfStrings(t *testing.T) {
+	t.Parallel()
+	for _, v := range []string{
+		`["a" "b"]`,
+		`["a", b]`,
+		`["a", "b`,
+		`[1, 2]`,
+		`[,]`,
+	} {
+		_, err := sales.ParseConfig(strings.NewReader("[b2b]\ncompanies = " + v + "\n"))
+		if err == nil {
+			t.Errorf("%s: want error, got nil", v)
+		}
+	}
+}
+
+func TestLoadConfigsChecksSettingsFromDifferentFilesTogether(t *testing.T) {
+	t.Parallel()
+	dir := t.TempDir()
+	write := func(name, data string) string {
+		t.Helper()
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte(data), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		return path
+	}
+	calendar := write("calendar.toml", "[periods]\ncalendar = \"4-4-5\"\n")
+	starts := write("starts.toml", "[periods]\nyear_starts = [\"2024-02-04\"]\n")
+	for _, paths := range [][]string{{calendar, starts}, {starts, calendar}} {
+		cfg, err := sales.LoadConfigs(paths...)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if len(cfg.Period.Calendar) != 3 || len(cfg.Period.YearStarts) != 1 {
+			t.Errorf("want calendar and year starts from both files, got %+v", cfg.Period)
+		}
+	}
+	_, err := sales.LoadConfigs(calendar)
+	if err == nil || !strings.Contains(err.Error(), calendar) {
+		t.Errorf("want error naming %s, got %v", calendar, err)
+	}
+}
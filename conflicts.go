@@ -0,0 +1,271 @@
+package sales
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"slices"
+	"sort"
+)
+
+// ConflictPolicy says how to reconcile sources that report the same sales,
+// such as a platform's order export and its payment processor's export.
+type ConflictPolicy struct {
+	// Mode is one of:
+	//
+	//   - "" (the default): sources are independent, and their sales are
+	//     added together.
+	//   - "prefer-source": where several sources report a product for the
+	//     same period, use the one listed first in Prefer.
+	//   - "max": use the source reporting the most revenue.
+	//   - "error": fail if sources report different numbers.
+	Mode string
+	// Prefer lists source files in order of trust.
+	Prefer []string
+}
+
+// ParseConflictMode checks that mode is a valid ConflictPolicy mode.
+func ParseConflictMode(mode string) (string, error) {
+	switch mode {
+	case "", "prefer-source", "max", "error":
+		return mode, nil
+	}
+	return "", fmt.Errorf("unknown conflict policy %q (want prefer-source, max, or error)", mode)
+}
+
+// Conflict describes sources that disagree about a product's sales in some
+// period.
+type Conflict struct {
+	Product string
+	Period  string
+	Reports map[string]Tally
+	Chosen  string
+}
+
+// ConflictError is returned by Resolve when the policy mode is "error" and
+// sources disagree.
+type ConflictError struct {
+	Conflicts []Conflict
+}
+
+func (e *ConflictError) Error() string {
+	c := e.Conflicts[0]
+	msg := fmt.Sprintf("sources disagree about %q", c.Product)
+	if c.Period != "" {
+		msg += " in " + c.Period
+	}
+	if len(e.Conflicts) > 1 {
+		msg += fmt.Sprintf(" (and %d other conflicts)", len(e.Conflicts)-1)
+	}
+	return msg
+}
+
+// slot identifies the sales of one product, in one period, from one origin.
+type slot struct {
+	product string
+	period  string
+	origin  Origin
+}
+
+// Resolve reconciles sources that report the same product for the same
+// period, according to the ConflictPolicy, so that their sales are counted
+// only once. Call it after reading all input. Differences between sources
+// are listed by Conflicts.
+//
+// Everything counted from the sources passed over, not just their product
+// totals, is taken out by rebuilding the report from the records kept from
+// the others, so this needs KeepRecords, unless only summary-only sources
+// are passed over.
+func (r *Report) Resolve() error {
+	if r.ConflictPolicy.Mode == "" {
+		return nil
+	}
+	type key struct{ product, period string }
+	reports := map[key]map[string]Tally{}
+	for s, t := range r.slots {
+		k := key{s.product, s.period}
+		if reports[k] == nil {
+			reports[k] = map[string]Tally{}
+		}
+		sum := reports[k][s.origin.File]
+		sum.Units += t.Units
+		sum.Revenue += t.Revenue
+		reports[k][s.origin.File] = sum
+	}
+	var conflicts []Conflict
+	losers := map[key][]string{}
+	for k, byFile := range reports {
+		if len(byFile) < 2 {
+			continue
+		}
+		files := make([]string, 0, len(byFile))
+		for f := range byFile {
+			files = append(files, f)
+		}
+		sort.Strings(files)
+		chosen := r.ConflictPolicy.choose(files, byFile)
+		for _, f := range files {
+			if byFile[f] != byFile[chosen] {
+				conflicts = append(conflicts, Conflict{
+					Product: k.product,
+					Period:  k.period,
+					Reports: byFile,
+					Chosen:  chosen,
+				})
+				break
+			}
+		}
+		for _, f := range files {
+			if f != chosen {
+				losers[k] = append(losers[k], f)
+			}
+		}
+	}
+	sort.Slice(conflicts, func(i, j int) bool {
+		if conflicts[i].Product != conflicts[j].Product {
+			return conflicts[i].Product < conflicts[j].Product
+		}
+		return conflicts[i].Period < conflicts[j].Period
+	})
+	if r.ConflictPolicy.Mode == "error" && len(conflicts) > 0 {
+		return &ConflictError{Conflicts: conflicts}
+	}
+	r.conflicts = append(r.conflicts, conflicts...)
+	if len(losers) == 0 {
+		return nil
+	}
+	lost := func(product, period, file string) bool {
+		return slices.Contains(losers[key{product, period}], file)
+	}
+	summaries := map[string]bool{}
+	for _, source := range r.aggregates {
+		summaries[source] = true
+	}
+	if !r.KeepRecords {
+		for _, files := range losers {
+			for _, f := range files {
+				if !summaries[f] {
+					return errors.New("resolving conflicts needs KeepRecords")
+				}
+			}
+		}
+		// Summaries count only towards products and slots.
+		r.dropSlots(func(s slot) bool { return lost(s.product, s.period, s.origin.File) })
+		return nil
+	}
+	nr, err := r.Reaggregate(AggregateOptions{
+		Groups: r.Groups,
+		Filter: func(rec Record) bool {
+			var period string
+			if !rec.Time.IsZero() {
+				period = r.Period.Label(rec.Time)
+			}
+			return !lost(r.Groups.ClassifyItem(rec.Product, rec.SKU), period, rec.Source)
+		},
+		Sort:   r.Sort,
+		Period: r.Period,
+		From:   r.From,
+		To:     r.To,
+	})
+	if err != nil {
+		return err
+	}
+	nr.Filter = r.Filter
+	for s, t := range r.slots {
+		if summaries[s.origin.File] && !lost(s.product, s.period, s.origin.File) {
+			nr.addTally(s.product, s.origin, s.period, t.Units, t.Revenue)
+		}
+	}
+	return r.replaceTotals(nr)
+}
+
+// dropSlots takes the sales in each slot for which drop returns true out
+// of the product totals.
+func (r *Report) dropSlots(drop func(slot) bool) {
+	for s, t := range r.slots {
+		if !drop(s) {
+			continue
+		}
+		p := r.products[s.product]
+		p.Units -= t.Units
+		p.Revenue -= t.Revenue
+		o := p.origins[s.origin]
+		o.Units -= t.Units
+		o.Revenue -= t.Revenue
+		if o == (Tally{}) {
+			delete(p.origins, s.origin)
+		} else {
+			p.origins[s.origin] = o
+		}
+		delete(r.slots, s)
+	}
+}
+
+// replaceTotals replaces everything counted from the records added to r
+// with what was counted by nr, which also takes over the kept records.
+// Settings, and what was found while reading, such as warnings, skipped
+// rows, and summary-only sources, stay as they were.
+func (r *Report) replaceTotals(nr *Report) error {
+	err := r.removeSpill()
+	r.products, r.slots, r.items = nr.products, nr.slots, nr.items
+	r.orders, r.orderCount, r.timing = nr.orders, nr.orderCount, nr.timing
+	r.daily, r.customers, r.fees = nr.daily, nr.customers, nr.fees
+	r.variants, r.taxes, r.referrers = nr.variants, nr.taxes, nr.referrers
+	r.splits, r.mrr, r.prices = nr.splits, nr.mrr, nr.prices
+	r.residues, r.currencies = nr.residues, nr.currencies
+	r.records, r.recordBytes = nr.records, nr.recordBytes
+	r.spill, r.spillErr = nr.spill, nr.spillErr
+	return err
+}
+
+func (cp ConflictPolicy) choose(files []string, reports map[string]Tally) string {
+	if cp.Mode == "prefer-source" {
+		for _, pref := range cp.Prefer {
+			if _, ok := reports[pref]; ok {
+				return pref
+			}
+		}
+	}
+	if cp.Mode == "max" {
+		best := files[0]
+		for _, f := range files[1:] {
+			if reports[f].Revenue > reports[best].Revenue {
+				best = f
+			}
+		}
+		return best
+	}
+	return files[0]
+}
+
+// Conflicts returns the disagreements between sources found by Resolve.
+func (r *Report) Conflicts() []Conflict {
+	return r.conflicts
+}
+
+// WriteConflicts writes the conflicts found by Resolve, if any, as a text
+// list.
+func (r *Report) WriteConflicts(w io.Writer) error {
+	if len(r.conflicts) == 0 {
+		return nil
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintln(w, "Conflicts:")
+	for _, c := range r.conflicts {
+		period := c.Period
+		if period == "" {
+			period = "all time"
+		}
+		fmt.Fprintf(w, "%s (%s): using %s\n", c.Product, period, c.Chosen)
+		files := make([]string, 0, len(c.Reports))
+		for f := range c.Reports {
+			files = append(files, f)
+		}
+		sort.Strings(files)
+		for _, f := range files {
+			t := c.Reports[f]
+			fmt.Fprintf(w, "  %s: %d units, revenue %.2f\n", f, t.Units, t.Revenue.Dollars())
+		}
+	}
+	return nil
+}
@@ -0,0 +1,122 @@
+package sales_test
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// addTwice adds the same sales to r as if from two exports, a.csv and
+// b.csv, with b.csv reporting an extra unit.
+func addTwice(r *sales.Report) {
+	when := time.Date(2024, time.March, 1, 12, 0, 0, 0, time.UTC)
+	for _, source := range []string{"a.csv", "b.csv"} {
+		r.Add(sales.Record{Source: source, OrderID: "1", Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Fee: sales.NewUSD(1), Time: when})
+		r.Add(sales.Record{Source: source, OrderID: "2", Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Fee: sales.NewUSD(1), Time: when})
+	}
+	r.Add(sales.Record{Source: "b.csv", OrderID: "3", Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Fee: sales.NewUSD(1), Time: when})
+}
+
+func TestResolveCountsEverythingFromTheChosenSourceOnly(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.ConflictPolicy = sales.ConflictPolicy{Mode: "prefer-source", Prefer: []string{"a.csv"}}
+	addTwice(r)
+	if err := r.Resolve(); err != nil {
+		t.Fatal(err)
+	}
+	want := sales.Totals{Units: 2, Revenue: sales.NewUSD(20), Orders: 2, Fees: sales.NewUSD(2)}
+	if got := r.Totals(); got != want {
+		t.Errorf("want totals %+v, got %+v", want, got)
+	}
+	if got := r.ByHour()[12].Orders; got != 2 {
+		t.Errorf("want 2 orders at noon, got %d", got)
+	}
+	records, err := r.Records()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got := len(records); got != 2 {
+		t.Errorf("want 2 records kept, got %d", got)
+	}
+	conflicts := r.Conflicts()
+	if len(conflicts) != 1 || conflicts[0].Chosen != "a.csv" {
+		t.Errorf("want one conflict, resolved with a.csv, got %+v", conflicts)
+	}
+}
+
+func TestResolveMaxChoosesTheSourceWithMostRevenue(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.ConflictPolicy = sales.ConflictPolicy{Mode: "max"}
+	addTwice(r)
+	if err := r.Resolve(); err != nil {
+		t.Fatal(err)
+	}
+	want := sales.Totals{Units: 3, Revenue: sales.NewUSD(30), Orders: 3, Fees: sales.NewUSD(3)}
+	if got := r.Totals(); got != want {
+		t.Errorf("want totals %+v, got %+v", want, got)
+	}
+}
+
+func TestResolveErrorModeReportsDisagreement(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.ConflictPolicy = sales.ConflictPolicy{Mode: "error"}
+	addTwice(r)
+	err := r.Resolve()
+	if _, ok := err.(*sales.ConflictError); !ok {
+		t.Fatalf("want *ConflictError, got %v", err)
+	}
+}
+
+func TestResolveNeedsKeepRecordsToPassOverRecords(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.ConflictPolicy = sales.ConflictPolicy{Mode: "max"}
+	addTwice(r)
+	if err := r.Resolve(); err == nil {
+		t.Error("want error, got nil")
+	}
+}
+
+func TestResolvePassesOverSummariesWithoutKeepRecords(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.ConflictPolicy = sales.ConflictPolicy{Mode: "prefer-source", Prefer: []string{"detail.csv"}}
+	if err := r.ReadCSV(strings.NewReader("Lineitem name,Lineitem price,Lineitem quantity\nBook,10.00,2\n"), "detail.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if err := r.ReadCSV(strings.NewReader("Product,Units,Revenue\nBook,3,30.00\n"), "summary.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if err := r.Resolve(); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 2 {
+		t.Errorf("want 2 units, got %d", got)
+	}
+}
+
+func TestResolveKeepsSummariesThatArentPassedOver(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.ConflictPolicy = sales.ConflictPolicy{Mode: "prefer-source", Prefer: []string{"b.csv"}}
+	addTwice(r)
+	if err := r.ReadCSV(strings.NewReader("Product,Units,Revenue\nVideo,4,80.00\n"), "summary.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if err := r.Resolve(); err != nil {
+		t.Fatal(err)
+	}
+	want := sales.Totals{Units: 3 + 4, Revenue: sales.NewUSD(30 + 80), Orders: 3, Fees: sales.NewUSD(3)}
+	if got := r.Totals(); got != want {
+		t.Errorf("want totals %+v, got %+v", want, got)
+	}
+}
@@ -0,0 +1,27 @@
+package sales
+
+import (
+	"context"
+	"io"
+)
+
+// ReadCSVContext is like ReadCSV, but stops with ctx.Err() if ctx is
+// cancelled or times out. Input is read as a stream, so a service can pass
+// the body of an HTTP response directly, without saving it to disk first,
+// and stop reading if its request is cancelled.
+func (r *Report) ReadCSVContext(ctx context.Context, rd io.Reader, source string) error {
+	return r.ReadCSV(&contextReader{ctx: ctx, r: rd}, source)
+}
+
+// contextReader is a reader that fails once its context is done.
+type contextReader struct {
+	ctx context.Context
+	r   io.Reader
+}
+
+func (c *contextReader) Read(p []byte) (int, error) {
+	if err := c.ctx.Err(); err != nil {
+		return 0, err
+	}
+	return c.r.Read(p)
+}
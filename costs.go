@@ -0,0 +1,55 @@
+package sales
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Cost is the cost of goods sold for each unit of a product: either a
+// fixed amount, or a percentage of the price it sold for.
+type Cost struct {
+	Unit    USD
+	Percent float64
+}
+
+// ParseCost reads a cost written as an amount, such as "4.50", or a
+// percentage, such as "30%".
+func ParseCost(s string) (Cost, error) {
+	s = strings.TrimSpace(s)
+	if pct, ok := strings.CutSuffix(s, "%"); ok {
+		n, err := strconv.ParseFloat(strings.TrimSpace(pct), 64)
+		if err == nil && n >= 0 {
+			return Cost{Percent: n}, nil
+		}
+	} else if u, err := ParseUSD(s); err == nil && u >= 0 {
+		return Cost{Unit: u}, nil
+	}
+	return Cost{}, fmt.Errorf("want an amount, such as 4.50, or a percentage, such as 30%%, got %q", s)
+}
+
+// Costs gives the cost of each product, by the name of its report row. The
+// cost for "*", if any, applies to every product not listed.
+type Costs map[string]Cost
+
+// Cost returns the cost of goods for the units of p sold, if its cost is
+// known.
+func (cs Costs) Cost(p *Product) (USD, bool) {
+	c, ok := cs[p.Name]
+	if !ok {
+		c, ok = cs["*"]
+	}
+	if !ok {
+		return 0, false
+	}
+	return c.Unit*USD(p.Units) + p.Revenue.Mul(c.Percent/100), true
+}
+
+// percent returns part as a percentage of whole, such as "42.5%", or ""
+// if whole is zero.
+func percent(part, whole USD) string {
+	if whole == 0 {
+		return ""
+	}
+	return fmt.Sprintf("%.1f%%", 100*float64(part)/float64(whole))
+}
@@ -0,0 +1,101 @@
+package sales_test
+
+import (
+	"errors"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestReadCSVReportsTheLineAndColumnOfABadField(t *testing.T) {
+	t.Parallel()
+	input := "Lineitem name,Lineitem price,Lineitem quantity\nBook,10.00,1\nVideo,twenty,1\n"
+	err := sales.NewReport().ReadCSV(strings.NewReader(input), "orders.csv")
+	var rowErr *sales.RowError
+	if !errors.As(err, &rowErr) {
+		t.Fatalf("want RowError, got %v", err)
+	}
+	if rowErr.File != "orders.csv" || rowErr.Line != 3 {
+		t.Errorf("want error at orders.csv:3, got %s:%d", rowErr.File, rowErr.Line)
+	}
+	var fieldErr *sales.FieldError
+	if !errors.As(err, &fieldErr) {
+		t.Fatalf("want FieldError, got %v", err)
+	}
+	if fieldErr.Column != "Lineitem price" || fieldErr.Value != "twenty" {
+		t.Errorf("want bad value %q in %q, got %q in %q", "twenty", "Lineitem price", fieldErr.Value, fieldErr.Column)
+	}
+}
+
+func TestReadCSVReadsQuotedFields(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	input := "Lineitem name,Lineitem price,Lineitem quantity\n\"Go, the book\",$10.00,2\n\"Go, the book\",\"$5.00\",1\n"
+	if err := r.ReadCSV(strings.NewReader(input), "orders.csv"); err != nil {
+		t.Fatal(err)
+	}
+	products := r.Products()
+	if len(products) != 1 || products[0].Name != "Go, the book" {
+		t.Fatalf("want one product %q, got %v", "Go, the book", products)
+	}
+	if products[0].Units != 3 || products[0].Revenue != sales.NewUSD(25) {
+		t.Errorf("want 3 units and 25.00, got %d units and %v", products[0].Units, products[0].Revenue)
+	}
+}
+
+func TestReadCSVLenientSkipsBadRowsAndCountsTheRest(t *testing.T) {
+	t.Parallel()
+	input := "Lineitem name,Lineitem price,Lineitem quantity\nBook,10.00,1\nVideo,20.00,lots\nCourse,30.00,2\n"
+	r := sales.NewReport()
+	if err := r.ReadCSVLenient(strings.NewReader(input), "orders.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 3 {
+		t.Errorf("want 3 units, got %d", got)
+	}
+	if want, got := sales.NewUSD(70), r.TotalRevenue(); want != got {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+	if skipped := r.Skipped(); len(skipped) != 1 || skipped[0].Line != 3 {
+		t.Errorf("want line 3 skipped, got %v", skipped)
+	}
+}
+
+func TestReadCSVFindsFieldsByConfiguredColumnNames(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Columns = sales.Columns{Name: "Item", Price: "Amount"}
+	r.SourceColumns = []sales.SourceColumns{{Pattern: "gumroad-*.csv", Columns: sales.Columns{Name: "product_title", Quantity: "qty"}}}
+	if err := r.ReadCSV(strings.NewReader("Item,Amount,Lineitem quantity\nBook,10.00,2\n"), "shop.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if err := r.ReadCSV(strings.NewReader("product_title,Amount,qty\nVideo,20.00,3\n"), "gumroad-2024.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 5 {
+		t.Errorf("want 5 units, got %d", got)
+	}
+	if want, got := sales.NewUSD(80), r.TotalRevenue(); want != got {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+	err := r.ReadCSV(strings.NewReader("Lineitem name,Amount\nBook,1.00\n"), "shop.csv")
+	if err == nil || !strings.Contains(err.Error(), `missing column "Item"`) {
+		t.Errorf("want missing column error, got %v", err)
+	}
+}
+
+func TestReadCSVReadsSummariesByProduct(t *testing.T) {
+	t.Parallel()
+	input := "Product,Units,Revenue\nBook,3,30.00\nVideo,1,20.00\n"
+	r := sales.NewReport()
+	if err := r.ReadCSV(strings.NewReader(input), "summary.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 4 {
+		t.Errorf("want 4 units, got %d", got)
+	}
+	if want, got := sales.NewUSD(50), r.TotalRevenue(); want != got {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+}
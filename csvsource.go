@@ -0,0 +1,116 @@
+package sales
+
+import (
+	"encoding/csv"
+	"errors"
+	"fmt"
+	"io"
+)
+
+// CSVSource reads the line items of a CSV sales export as records, without
+// adding them to a report, for programs that do their own aggregation:
+//
+//	src, err := sales.NewCSVSource(f, "orders.csv", cfg)
+//	...
+//	for {
+//		rec, err := src.Next()
+//		if err == io.EOF {
+//			break
+//		}
+//		...
+//	}
+//
+// It's a Source, so its records can also be added to a report with Ingest.
+type CSVSource struct {
+	name string
+	cr   *csv.Reader
+	rr   *rowReader
+	// rec is the record emitted by the last row read, if ok is set.
+	rec Record
+	ok  bool
+}
+
+// NewCSVSource returns a CSVSource reading the export in rd, which is
+// called name in errors and in the records' Source. The export is read
+// using the columns, CSV dialect, timestamp formats, and limits in cfg.
+// Summary-only exports have no line items, and are an error.
+func NewCSVSource(rd io.Reader, name string, cfg Config) (*CSVSource, error) {
+	r := NewReport()
+	r.Limits = cfg.Limits
+	r.Columns = cfg.Columns
+	r.SourceColumns = cfg.SourceColumns
+	r.Dialect = cfg.Dialect
+	r.Timestamps = cfg.Timestamps
+	s := &CSVSource{name: name}
+	cr, header, err := r.openCSV(rd, name)
+	if err == io.EOF {
+		return s, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	cols := r.columnsFor(name)
+	col := map[string]int{}
+	for i, h := range header {
+		col[h] = i
+	}
+	if _, ok := col[cols.Name]; !ok && isAggregate(col) {
+		return nil, fmt.Errorf("%s: summary-only export has no line items", name)
+	}
+	if err := checkColumns(col, cols, name); err != nil {
+		return nil, err
+	}
+	s.cr = cr
+	s.rr = &rowReader{r: r, source: name, header: header, col: col, cols: cols}
+	s.rr.emit = func(rec Record) {
+		s.rec = rec
+		s.ok = true
+	}
+	return s, nil
+}
+
+func (s *CSVSource) Name() string {
+	return s.name
+}
+
+// Next returns the next line item, or io.EOF at the end of the export. A
+// malformed row gives a *RowError, after which the following rows can
+// still be read.
+func (s *CSVSource) Next() (Record, error) {
+	if s.cr == nil {
+		return Record{}, io.EOF
+	}
+	for {
+		row, err := s.cr.Read()
+		if err == io.EOF {
+			return Record{}, io.EOF
+		}
+		if err != nil {
+			var pe *csv.ParseError
+			if errors.As(err, &pe) {
+				return Record{}, &RowError{File: s.name, Line: pe.StartLine, Err: pe.Err}
+			}
+			return Record{}, fmt.Errorf("%s: %w", s.name, err)
+		}
+		s.ok = false
+		err = s.rr.add(row, func(i int) int {
+			line, _ := s.cr.FieldPos(i)
+			return line
+		})
+		if err != nil {
+			return Record{}, err
+		}
+		if s.ok {
+			return s.rec, nil
+		}
+	}
+}
+
+// Warnings returns the warnings about suspicious rows read so far, such as
+// zero prices.
+func (s *CSVSource) Warnings() []Warning {
+	if s.rr == nil {
+		return nil
+	}
+	return s.rr.r.Warnings()
+}
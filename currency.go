@@ -0,0 +1,165 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// CurrencyPolicy says what to do with orders in a currency other than the
+// report's Currency, such as the rows of a Gumroad export that give each
+// sale's currency, so that amounts in different currencies aren't added up
+// as if they were the same.
+type CurrencyPolicy struct {
+	// Mode is one of:
+	//
+	//   - "" or "warn" (the default): the orders are counted as they
+	//     are, with a warning.
+	//   - "reject": the orders are rejected as bad rows.
+	//   - "convert": the orders' amounts are converted to the report's
+	//     currency at the rate of the order's date in History, or else at
+	//     Rates. Orders in a currency without a rate are rejected.
+	//   - "separate": the orders are counted as they are, without a
+	//     warning, for reporting each currency's sales separately with
+	//     ByCurrency, rather than converting them.
+	Mode string
+	// Rates gives the value of one unit of each other currency, by its
+	// code, such as "EUR", in the report's currency.
+	Rates map[string]float64
+	// History gives exchange rates by date, such as those read by
+	// LoadRates. Where it has a rate for an order's currency on or before
+	// the order's date, that's used rather than the one in Rates, so
+	// that each order is converted at the rate of its day.
+	History RateHistory
+}
+
+// ParseCurrencyMode checks that mode is a valid CurrencyPolicy mode.
+func ParseCurrencyMode(mode string) (string, error) {
+	switch mode {
+	case "", "warn", "reject", "convert", "separate":
+		return mode, nil
+	}
+	return "", fmt.Errorf("unknown currency policy %q (want warn, reject, convert, or separate)", mode)
+}
+
+// apply returns rec, which is in a currency other than the report's
+// currency, as the policy says: converted, if the mode is "convert", or
+// unchanged. It returns an error if the record should be rejected.
+func (p CurrencyPolicy) apply(rec Record, currency string) (Record, error) {
+	switch p.Mode {
+	case "reject":
+		return Record{}, fmt.Errorf("order %q is in %s, not %s", rec.OrderID, rec.Currency, currency)
+	case "convert":
+		rate, ok := p.rate(rec.Currency, rec.Time)
+		if !ok {
+			return Record{}, fmt.Errorf("no exchange rate for %s to %s", rec.Currency, currency)
+		}
+		rec.Price = rec.Price.Mul(rate)
+		rec.Fee = rec.Fee.Mul(rate)
+		rec.Tax = rec.Tax.Mul(rate)
+		rec.AffiliateFee = rec.AffiliateFee.Mul(rate)
+		rec.Currency = currency
+	}
+	return rec, nil
+}
+
+// applyCurrencyPolicy returns rec, from source, as the CurrencyPolicy says
+// if it's in a currency other than the report's, with a warning in the
+// default mode. It returns an error if the record should be rejected.
+func (r *Report) applyCurrencyPolicy(source string, rec Record) (Record, error) {
+	currency := r.reportCurrency()
+	if rec.Currency == "" || rec.Currency == currency {
+		return rec, nil
+	}
+	if mode := r.CurrencyPolicy.Mode; mode == "" || mode == "warn" {
+		r.warn(source, 0, "order %q is in %s, not %s", rec.OrderID, rec.Currency, currency)
+	}
+	return r.CurrencyPolicy.apply(rec, currency)
+}
+
+// rate returns the rate for currency at time t, from History if it has one,
+// or otherwise from Rates.
+func (p CurrencyPolicy) rate(currency string, t time.Time) (float64, bool) {
+	if rate, ok := p.History.Rate(currency, t); ok {
+		return rate, true
+	}
+	rate, ok := p.Rates[strings.ToUpper(currency)]
+	return rate, ok
+}
+
+// currencySlot identifies the sales of one report row in one currency.
+type currencySlot struct {
+	currency string
+	product  string
+}
+
+// CurrencyTotal is the units and revenue of one report row from sales in
+// one currency, with amounts in that currency.
+type CurrencyTotal struct {
+	Currency string
+	Product  string
+	Tally
+}
+
+// addCurrency counts units and revenue, in currency, against the report
+// row called name.
+func (r *Report) addCurrency(currency, name string, units int, revenue USD) {
+	k := currencySlot{currency: currency, product: name}
+	t := r.currencies[k]
+	t.Units += units
+	t.Revenue += revenue
+	r.currencies[k] = t
+}
+
+// ByCurrency returns the units and revenue of each report row from sales
+// in each currency, ordered by currency and product, so that sales in
+// different currencies can be reported without converting them. Orders
+// converted by the CurrencyPolicy count as being in the report's
+// currency.
+func (r *Report) ByCurrency() []CurrencyTotal {
+	totals := make([]CurrencyTotal, 0, len(r.currencies))
+	for k, t := range r.currencies {
+		totals = append(totals, CurrencyTotal{Currency: k.currency, Product: k.product, Tally: t})
+	}
+	sort.Slice(totals, func(i, j int) bool {
+		a, b := totals[i], totals[j]
+		if a.Currency != b.Currency {
+			return a.Currency < b.Currency
+		}
+		return a.Product < b.Product
+	})
+	return totals
+}
+
+// WriteCurrencies writes the units and revenue of each report row as a
+// text table for each currency, with the currency's subtotal, and no
+// grand total, since the amounts can't be added up.
+func (r *Report) WriteCurrencies(w io.Writer) error {
+	totals := r.ByCurrency()
+	for i := 0; i < len(totals); {
+		currency := totals[i].Currency
+		t := &table{
+			header: []string{"Product", "Units", "Revenue"},
+			right:  []bool{false, true, true},
+			color:  r.Color,
+		}
+		var sub Tally
+		for ; i < len(totals) && totals[i].Currency == currency; i++ {
+			t.add(totals[i].Product, strconv.Itoa(totals[i].Units), totals[i].Revenue.String())
+			sub.Units += totals[i].Units
+			sub.Revenue += totals[i].Revenue
+		}
+		t.footer = []string{"Total " + currency, strconv.Itoa(sub.Units), sub.Revenue.String()}
+		if currency != totals[0].Currency {
+			fmt.Fprintln(w)
+		}
+		fmt.Fprintln(w, currency)
+		if err := t.write(w); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,93 @@
+package sales
+
+import (
+	"io"
+	"sort"
+	"strconv"
+)
+
+// ProductDiff is the difference in one report row between two reports,
+// such as two snapshots of the same export.
+type ProductDiff struct {
+	Product string
+	Before  Tally
+	After   Tally
+	// Added and Removed say whether the row is only in the later report
+	// or only in the earlier one.
+	Added   bool
+	Removed bool
+}
+
+// Diff compares r with an earlier report, before, returning the rows whose
+// units or revenue differ, or which are only in one of the reports, in name
+// order. Identical reports have no differences.
+func (r *Report) Diff(before *Report) []ProductDiff {
+	var diffs []ProductDiff
+	for name, p := range r.products {
+		d := ProductDiff{Product: name, After: Tally{Units: p.Units, Revenue: p.Revenue}}
+		old, ok := before.products[name]
+		if ok {
+			d.Before = Tally{Units: old.Units, Revenue: old.Revenue}
+		}
+		d.Added = !ok
+		if d.Added || d.Before != d.After {
+			diffs = append(diffs, d)
+		}
+	}
+	for name, p := range before.products {
+		if _, ok := r.products[name]; !ok {
+			diffs = append(diffs, ProductDiff{Product: name, Before: Tally{Units: p.Units, Revenue: p.Revenue}, Removed: true})
+		}
+	}
+	sort.Slice(diffs, func(i, j int) bool {
+		return diffs[i].Product < diffs[j].Product
+	})
+	return diffs
+}
+
+// WriteDiff writes diffs, from Diff, as a text table giving the units and
+// revenue before and after, and the change in each.
+func (r *Report) WriteDiff(w io.Writer, diffs []ProductDiff) error {
+	t := &table{
+		header: []string{"Product", "Change", "Units before", "Units after", "Units +/-", "Revenue before", "Revenue after", "Revenue +/-"},
+		right:  []bool{false, false, true, true, true, true, true, true},
+		color:  r.Color,
+	}
+	var before, after Tally
+	for _, d := range diffs {
+		change := "changed"
+		switch {
+		case d.Added:
+			change = "added"
+		case d.Removed:
+			change = "removed"
+		}
+		t.add(d.Product, change,
+			strconv.Itoa(d.Before.Units), strconv.Itoa(d.After.Units), signed(d.After.Units-d.Before.Units),
+			d.Before.Revenue.String(), d.After.Revenue.String(), signedUSD(d.After.Revenue-d.Before.Revenue))
+		before.Units += d.Before.Units
+		before.Revenue += d.Before.Revenue
+		after.Units += d.After.Units
+		after.Revenue += d.After.Revenue
+	}
+	t.footer = []string{"Total", "",
+		strconv.Itoa(before.Units), strconv.Itoa(after.Units), signed(after.Units - before.Units),
+		before.Revenue.String(), after.Revenue.String(), signedUSD(after.Revenue - before.Revenue)}
+	return t.write(w)
+}
+
+// signed formats n with a sign, such as "+3" or "-2".
+func signed(n int) string {
+	if n > 0 {
+		return "+" + strconv.Itoa(n)
+	}
+	return strconv.Itoa(n)
+}
+
+// signedUSD formats u with a sign, such as "+12.50" or "-3.00".
+func signedUSD(u USD) string {
+	if u > 0 {
+		return "+" + u.String()
+	}
+	return u.String()
+}
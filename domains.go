@@ -0,0 +1,129 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// webmailProviders are the email providers that individuals sign up with,
+// by the first label of their domains, so that "yahoo.co.uk" counts as
+// well as "yahoo.com". Customers with other domains are likely buying
+// through their work.
+var webmailProviders = map[string]bool{
+	"gmail": true, "googlemail": true, "yahoo": true, "ymail": true, "rocketmail": true,
+	"hotmail": true, "outlook": true, "live": true, "msn": true, "icloud": true,
+	"me": true, "mac": true, "aol": true, "proton": true, "protonmail": true,
+	"pm": true, "gmx": true, "web": true, "mail": true, "yandex": true,
+	"qq": true, "163": true, "126": true, "fastmail": true, "zoho": true,
+	"hey": true, "btinternet": true, "comcast": true, "orange": true, "free": true,
+	"t-online": true,
+}
+
+// IsWebmail reports whether domain belongs to a webmail provider, such as
+// gmail.com, rather than a company or other organisation.
+func IsWebmail(domain string) bool {
+	first, _, _ := strings.Cut(strings.ToLower(domain), ".")
+	return webmailProviders[first]
+}
+
+// The rows that ByEmailDomain combines domains with too few customers into.
+const (
+	OtherWebmail  = "(other webmail)"
+	OtherBusiness = "(other domains)"
+)
+
+// DomainTotal is the number of customers with email addresses at a domain,
+// and the revenue from them.
+type DomainTotal struct {
+	Domain string
+	// Webmail is set for webmail providers, such as gmail.com, whose
+	// customers are probably individuals, rather than companies.
+	Webmail   bool
+	Customers int
+	Revenue   USD
+}
+
+// ByEmailDomain returns the customers and revenue from each email domain,
+// largest revenue first. To avoid singling out customers, domains with
+// fewer than minCustomers customers are combined into a row of other
+// webmail domains or one of other domains, as the case may be. Customers
+// are only known for sales with an email address and a date.
+func (r *Report) ByEmailDomain(minCustomers int) []DomainTotal {
+	byDomain := map[string]*DomainTotal{}
+	for customer, months := range r.customers {
+		_, domain, ok := strings.Cut(customer, "@")
+		if !ok {
+			continue
+		}
+		domain = strings.ToLower(domain)
+		d := byDomain[domain]
+		if d == nil {
+			d = &DomainTotal{Domain: domain, Webmail: IsWebmail(domain)}
+			byDomain[domain] = d
+		}
+		d.Customers++
+		for _, rev := range months {
+			d.Revenue += rev
+		}
+	}
+	other := map[bool]*DomainTotal{
+		true:  {Domain: OtherWebmail, Webmail: true},
+		false: {Domain: OtherBusiness},
+	}
+	var totals []DomainTotal
+	for _, d := range byDomain {
+		if d.Customers >= minCustomers {
+			totals = append(totals, *d)
+			continue
+		}
+		o := other[d.Webmail]
+		o.Customers += d.Customers
+		o.Revenue += d.Revenue
+	}
+	sort.Slice(totals, func(i, j int) bool {
+		if totals[i].Revenue != totals[j].Revenue {
+			return totals[i].Revenue > totals[j].Revenue
+		}
+		return totals[i].Domain < totals[j].Domain
+	})
+	for _, webmail := range []bool{true, false} {
+		if o := other[webmail]; o.Customers > 0 {
+			totals = append(totals, *o)
+		}
+	}
+	return totals
+}
+
+// WriteEmailDomains writes the ByEmailDomain totals as a text table,
+// followed by the split of customers and revenue between webmail and other
+// domains, which suggests how much is sold to individuals rather than to
+// companies.
+func (r *Report) WriteEmailDomains(w io.Writer, minCustomers int) error {
+	t := &table{
+		header: []string{"Domain", "Type", "Customers", "Revenue"},
+		right:  []bool{false, false, true, true},
+		color:  r.Color,
+	}
+	var split [2]DomainTotal
+	for _, d := range r.ByEmailDomain(minCustomers) {
+		kind, i := "business", 1
+		if d.Webmail {
+			kind, i = "webmail", 0
+		}
+		t.add(d.Domain, kind, strconv.Itoa(d.Customers), d.Revenue.String())
+		split[i].Customers += d.Customers
+		split[i].Revenue += d.Revenue
+	}
+	total := split[0].Revenue + split[1].Revenue
+	t.footer = []string{"Total", "", strconv.Itoa(split[0].Customers + split[1].Customers), total.String()}
+	if err := t.write(w); err != nil {
+		return err
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintf(w, "Webmail:  %d customers, %s revenue (%s)\n", split[0].Customers, split[0].Revenue, percent(split[0].Revenue, total))
+	_, err := fmt.Fprintf(w, "Business: %d customers, %s revenue (%s)\n", split[1].Customers, split[1].Revenue, percent(split[1].Revenue, total))
+	return err
+}
@@ -0,0 +1,182 @@
+package sales
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/binary"
+	"encoding/csv"
+	"fmt"
+	"io"
+	"strings"
+	"unicode/utf16"
+	"unicode/utf8"
+)
+
+// Dialect describes the CSV conventions of an export.
+type Dialect struct {
+	// Delimiter separates fields. Zero means a comma.
+	Delimiter rune
+	// LazyQuotes allows quotes to appear in unquoted fields, and unescaped
+	// quotes in quoted fields.
+	LazyQuotes bool
+	// Encoding is the character encoding of input without a byte order
+	// mark: "utf-8" (the default), "utf-16le", "utf-16be",
+	// "windows-1252", or "latin1". Input starting with a byte order mark
+	// is always decoded according to the mark.
+	Encoding string
+}
+
+// ParseDelimiter interprets s as a field delimiter. It accepts a single
+// character, or "tab" or `\t` for a tab.
+func ParseDelimiter(s string) (rune, error) {
+	switch s {
+	case "tab", `\t`:
+		return '\t', nil
+	}
+	r, size := utf8.DecodeRuneInString(s)
+	if s == "" || size != len(s) || r == '"' || r == '\r' || r == '\n' || r == utf8.RuneError {
+		return 0, fmt.Errorf("invalid delimiter %q", s)
+	}
+	return r, nil
+}
+
+func (d Dialect) newReader(rd io.Reader) (*csv.Reader, error) {
+	rd, err := decode(rd, d.Encoding)
+	if err != nil {
+		return nil, err
+	}
+	cr := csv.NewReader(rd)
+	if d.Delimiter != 0 {
+		cr.Comma = d.Delimiter
+	}
+	cr.LazyQuotes = d.LazyQuotes
+	return cr, nil
+}
+
+// decode returns a reader that converts rd from the given encoding, or the
+// one indicated by its byte order mark, to UTF-8.
+func decode(rd io.Reader, encoding string) (io.Reader, error) {
+	br := bufio.NewReader(rd)
+	bom, _ := br.Peek(3)
+	switch {
+	case bytes.HasPrefix(bom, []byte{0xEF, 0xBB, 0xBF}):
+		br.Discard(3)
+		return br, nil
+	case bytes.HasPrefix(bom, []byte{0xFF, 0xFE}):
+		br.Discard(2)
+		return &utf16Reader{r: br, order: binary.LittleEndian}, nil
+	case bytes.HasPrefix(bom, []byte{0xFE, 0xFF}):
+		br.Discard(2)
+		return &utf16Reader{r: br, order: binary.BigEndian}, nil
+	}
+	switch strings.ToLower(encoding) {
+	case "", "utf-8", "utf8":
+		return br, nil
+	case "utf-16", "utf-16le":
+		return &utf16Reader{r: br, order: binary.LittleEndian}, nil
+	case "utf-16be":
+		return &utf16Reader{r: br, order: binary.BigEndian}, nil
+	case "windows-1252", "cp1252":
+		return &byteReader{r: br, decode: windows1252}, nil
+	case "latin1", "iso-8859-1":
+		return &byteReader{r: br, decode: func(b byte) rune { return rune(b) }}, nil
+	}
+	return nil, fmt.Errorf("unsupported encoding %q", encoding)
+}
+
+type utf16Reader struct {
+	r       *bufio.Reader
+	order   binary.ByteOrder
+	pending []byte
+}
+
+func (u *utf16Reader) Read(p []byte) (int, error) {
+	n := 0
+	for n < len(p) {
+		if len(u.pending) > 0 {
+			c := copy(p[n:], u.pending)
+			u.pending = u.pending[c:]
+			n += c
+			continue
+		}
+		r, err := u.next()
+		if err != nil {
+			if err == io.EOF && n > 0 {
+				return n, nil
+			}
+			return n, err
+		}
+		u.pending = utf8.AppendRune(u.pending[:0], r)
+	}
+	return n, nil
+}
+
+func (u *utf16Reader) next() (rune, error) {
+	c, err := u.unit()
+	if err != nil {
+		return 0, err
+	}
+	if !utf16.IsSurrogate(c) {
+		return c, nil
+	}
+	c2, err := u.unit()
+	if err != nil {
+		return 0, err
+	}
+	return utf16.DecodeRune(c, c2), nil
+}
+
+func (u *utf16Reader) unit() (rune, error) {
+	var b [2]byte
+	if _, err := io.ReadFull(u.r, b[:]); err != nil {
+		if err == io.ErrUnexpectedEOF {
+			return 0, fmt.Errorf("truncated UTF-16 input")
+		}
+		return 0, err
+	}
+	return rune(u.order.Uint16(b[:])), nil
+}
+
+// byteReader decodes a single-byte character encoding.
+type byteReader struct {
+	r       *bufio.Reader
+	decode  func(byte) rune
+	pending []byte
+}
+
+func (d *byteReader) Read(p []byte) (int, error) {
+	n := 0
+	for n < len(p) {
+		if len(d.pending) > 0 {
+			c := copy(p[n:], d.pending)
+			d.pending = d.pending[c:]
+			n += c
+			continue
+		}
+		b, err := d.r.ReadByte()
+		if err != nil {
+			if err == io.EOF && n > 0 {
+				return n, nil
+			}
+			return n, err
+		}
+		d.pending = utf8.AppendRune(d.pending[:0], d.decode(b))
+	}
+	return n, nil
+}
+
+// windows1252High maps bytes 0x80 to 0x9F, where Windows-1252 differs from
+// Latin-1. Unassigned bytes map to the corresponding control characters.
+var windows1252High = [32]rune{
+	0x20AC, 0x0081, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021,
+	0x02C6, 0x2030, 0x0160, 0x2039, 0x0152, 0x008D, 0x017D, 0x008F,
+	0x0090, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+	0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0x009D, 0x017E, 0x0178,
+}
+
+func windows1252(b byte) rune {
+	if b >= 0x80 && b < 0xA0 {
+		return windows1252High[b-0x80]
+	}
+	return rune(b)
+}
@@ -0,0 +1,40 @@
+package sales
+
+import (
+	"bufio"
+	"encoding/xml"
+	"fmt"
+	"io"
+)
+
+// WriteExcel writes the report as an Excel 2003 XML spreadsheet. Revenue
+// cells are stored as numbers with a number format for the report's
+// Currency in loc, so that they can be sorted and summed in Excel.
+func (r *Report) WriteExcel(w io.Writer, loc Locale) error {
+	r.logRender("excel")
+	bw := bufio.NewWriter(w)
+	fmt.Fprint(bw, `<?xml version="1.0" encoding="UTF-8"?>
+<?mso-application progid="Excel.Sheet"?>
+<Workbook xmlns="urn:schemas-microsoft-com:office:spreadsheet" xmlns:ss="urn:schemas-microsoft-com:office:spreadsheet">
+<Styles>
+<Style ss:ID="header"><Font ss:Bold="1"/></Style>
+<Style ss:ID="money"><NumberFormat ss:Format="`)
+	xml.EscapeText(bw, []byte(loc.ExcelCurrencyFormat(r.reportCurrency())))
+	fmt.Fprint(bw, `"/></Style>
+</Styles>
+<Worksheet ss:Name="Sales">
+<Table>
+<Row><Cell ss:StyleID="header"><Data ss:Type="String">Product</Data></Cell><Cell ss:StyleID="header"><Data ss:Type="String">Units</Data></Cell><Cell ss:StyleID="header"><Data ss:Type="String">Revenue</Data></Cell></Row>
+`)
+	row := func(name string, units int, revenue USD) {
+		fmt.Fprint(bw, `<Row><Cell><Data ss:Type="String">`)
+		xml.EscapeText(bw, []byte(name))
+		fmt.Fprintf(bw, `</Data></Cell><Cell><Data ss:Type="Number">%d</Data></Cell><Cell ss:StyleID="money"><Data ss:Type="Number">%s</Data></Cell></Row>`+"\n", units, revenue)
+	}
+	for _, p := range r.Products() {
+		row(p.Name, p.Units, p.Revenue)
+	}
+	row("Total", r.TotalUnits(), r.TotalRevenue())
+	fmt.Fprint(bw, "</Table>\n</Worksheet>\n</Workbook>\n")
+	return bw.Flush()
+}
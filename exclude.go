@@ -0,0 +1,62 @@
+package sales
+
+import (
+	"strconv"
+	"strings"
+)
+
+// Exclusions are rules for leaving out sales that would distort the
+// report, such as free downloads and test purchases. The zero value
+// excludes nothing.
+type Exclusions struct {
+	// ZeroPrice excludes line items with a price of zero.
+	ZeroPrice bool
+	// EmailDomains excludes sales to customers with email addresses at
+	// any of these domains, such as the seller's own.
+	EmailDomains []string
+	// Test excludes sales that the export marks as tests.
+	Test bool
+}
+
+// Reasons for excluding a record, as counted by Excluded.
+const (
+	ExcludedZeroPrice = "zero price"
+	ExcludedEmail     = "own email domain"
+	ExcludedTest      = "test"
+)
+
+// reason returns why rec should be excluded, or "" if it shouldn't.
+func (e Exclusions) reason(rec Record) string {
+	if e.Test && rec.Test {
+		return ExcludedTest
+	}
+	if e.ZeroPrice && rec.Price == 0 {
+		return ExcludedZeroPrice
+	}
+	if _, domain, ok := strings.Cut(rec.Customer, "@"); ok {
+		for _, d := range e.EmailDomains {
+			if strings.EqualFold(domain, d) {
+				return ExcludedEmail
+			}
+		}
+	}
+	return ""
+}
+
+// Excluded returns the number of records left out of the report by
+// Exclude, for each reason: ExcludedZeroPrice, ExcludedEmail, or
+// ExcludedTest.
+func (r *Report) Excluded() map[string]int {
+	return r.excluded
+}
+
+// isTestFlag reports whether s, from an export's test column, marks a test
+// sale: any value but empty, "false", "no", or "0".
+func isTestFlag(s string) bool {
+	s = strings.TrimSpace(s)
+	if s == "" || strings.EqualFold(s, "no") {
+		return false
+	}
+	b, err := strconv.ParseBool(s)
+	return err != nil || b
+}
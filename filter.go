@@ -0,0 +1,241 @@
+package sales
+
+import (
+	"cmp"
+	"fmt"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// filterField is a field of a record that filters can test: either a text
+// field or a number.
+type filterField struct {
+	text   func(Record) string
+	number func(Record) float64
+}
+
+var filterFields = map[string]filterField{
+	"name":       {text: func(rec Record) string { return rec.Product }},
+	"source":     {text: func(rec Record) string { return rec.Source }},
+	"channel":    {text: func(rec Record) string { return rec.Channel }},
+	"order":      {text: func(rec Record) string { return rec.OrderID }},
+	"customer":   {text: func(rec Record) string { return rec.Customer }},
+	"sku":        {text: func(rec Record) string { return rec.SKU }},
+	"variant":    {text: func(rec Record) string { return rec.Variant }},
+	"country":    {text: func(rec Record) string { return rec.Country }},
+	"region":     {text: func(rec Record) string { return rec.Region }},
+	"referrer":   {text: func(rec Record) string { return rec.Referrer }},
+	"affiliate":  {text: func(rec Record) string { return rec.Affiliate }},
+	"recurrence": {text: func(rec Record) string { return rec.Recurrence }},
+	"date": {text: func(rec Record) string {
+		if rec.Time.IsZero() {
+			return ""
+		}
+		return rec.Time.Format(time.DateOnly)
+	}},
+	"price":    {number: func(rec Record) float64 { return rec.Price.Dollars() }},
+	"quantity": {number: func(rec Record) float64 { return float64(rec.Quantity) }},
+	"total":    {number: func(rec Record) float64 { return rec.Total().Dollars() }},
+}
+
+// ParseFilter returns a function, for Report.Filter, that reports whether a
+// record matches the filter expression expr, such as:
+//
+//	price >= 10 && name ~ "Go" && !(channel == "Test")
+//
+// Each comparison tests one field of the record against a value. The
+// fields are name, sku, variant, source, channel, order, customer,
+// country, region, referrer, affiliate, recurrence, and date (as in
+// "2024-01-31"), which are compared with double-quoted strings, and price,
+// quantity, and total, which are compared with numbers. The operators are
+// ==, !=, <, <=, >, and >=, and, for text fields, ~ and !~, which test
+// whether the field matches a regular expression. Comparisons can be
+// combined with && (and), || (or), ! (not), and parentheses; && binds more
+// tightly than ||.
+func ParseFilter(expr string) (func(Record) bool, error) {
+	tokens, err := lexFilter(expr)
+	if err != nil {
+		return nil, fmt.Errorf("filter: %w", err)
+	}
+	p := &filterParser{tokens: tokens}
+	f, err := p.or()
+	if err == nil && p.peek() != "" {
+		err = fmt.Errorf("unexpected %q", p.peek())
+	}
+	if err != nil {
+		return nil, fmt.Errorf("filter: %w", err)
+	}
+	return f, nil
+}
+
+// filterOps are the operators of the filter language, longest first, so
+// that "!=" isn't read as "!" followed by "=".
+var filterOps = []string{"&&", "||", "==", "!=", "<=", ">=", "!~", "!", "<", ">", "~", "(", ")"}
+
+// lexFilter splits a filter expression into operators, quoted strings, and
+// words, such as field names and numbers.
+func lexFilter(s string) ([]string, error) {
+	var tokens []string
+	for s = strings.TrimSpace(s); s != ""; s = strings.TrimSpace(s) {
+		if s[0] == '"' {
+			q, err := strconv.QuotedPrefix(s)
+			if err != nil {
+				return nil, fmt.Errorf("bad string at %q", s)
+			}
+			tokens = append(tokens, q)
+			s = s[len(q):]
+			continue
+		}
+		op := ""
+		for _, o := range filterOps {
+			if strings.HasPrefix(s, o) {
+				op = o
+				break
+			}
+		}
+		if op != "" {
+			tokens = append(tokens, op)
+			s = s[len(op):]
+			continue
+		}
+		end := strings.IndexAny(s, " \t\"&|!=<>~()")
+		if end == 0 {
+			return nil, fmt.Errorf("unexpected %q", s[:1])
+		}
+		if end < 0 {
+			end = len(s)
+		}
+		tokens = append(tokens, s[:end])
+		s = s[end:]
+	}
+	return tokens, nil
+}
+
+type filterParser struct {
+	tokens []string
+	pos    int
+}
+
+// peek returns the next token, or "" at the end of the expression.
+func (p *filterParser) peek() string {
+	if p.pos < len(p.tokens) {
+		return p.tokens[p.pos]
+	}
+	return ""
+}
+
+func (p *filterParser) next() string {
+	t := p.peek()
+	p.pos++
+	return t
+}
+
+func (p *filterParser) or() (func(Record) bool, error) {
+	f, err := p.and()
+	for err == nil && p.peek() == "||" {
+		p.next()
+		var g func(Record) bool
+		g, err = p.and()
+		left := f
+		f = func(rec Record) bool { return left(rec) || g(rec) }
+	}
+	return f, err
+}
+
+func (p *filterParser) and() (func(Record) bool, error) {
+	f, err := p.unary()
+	for err == nil && p.peek() == "&&" {
+		p.next()
+		var g func(Record) bool
+		g, err = p.unary()
+		left := f
+		f = func(rec Record) bool { return left(rec) && g(rec) }
+	}
+	return f, err
+}
+
+func (p *filterParser) unary() (func(Record) bool, error) {
+	switch p.peek() {
+	case "!":
+		p.next()
+		f, err := p.unary()
+		return func(rec Record) bool { return !f(rec) }, err
+	case "(":
+		p.next()
+		f, err := p.or()
+		if err != nil {
+			return nil, err
+		}
+		if t := p.next(); t != ")" {
+			return nil, fmt.Errorf("want ), got %q", t)
+		}
+		return f, nil
+	}
+	return p.comparison()
+}
+
+func (p *filterParser) comparison() (func(Record) bool, error) {
+	name := p.next()
+	field, ok := filterFields[name]
+	if !ok {
+		names := make([]string, 0, len(filterFields))
+		for n := range filterFields {
+			names = append(names, n)
+		}
+		sort.Strings(names)
+		return nil, fmt.Errorf("unknown field %q (want one of %s)", name, strings.Join(names, ", "))
+	}
+	op := p.next()
+	switch op {
+	case "==", "!=", "<", "<=", ">", ">=", "~", "!~":
+	default:
+		return nil, fmt.Errorf("want a comparison after %s, got %q", name, op)
+	}
+	value := p.next()
+	if field.number != nil {
+		if op == "~" || op == "!~" {
+			return nil, fmt.Errorf("%s is a number, and can't be matched with %s", name, op)
+		}
+		n, err := strconv.ParseFloat(value, 64)
+		if err != nil {
+			return nil, fmt.Errorf("want a number to compare %s with, got %q", name, value)
+		}
+		get := field.number
+		return func(rec Record) bool { return compare(op, get(rec), n) }, nil
+	}
+	s, err := strconv.Unquote(value)
+	if err != nil || !strings.HasPrefix(value, `"`) {
+		return nil, fmt.Errorf("want a quoted string to compare %s with, got %q", name, value)
+	}
+	get := field.text
+	if op == "~" || op == "!~" {
+		re, err := regexp.Compile(s)
+		if err != nil {
+			return nil, err
+		}
+		want := op == "~"
+		return func(rec Record) bool { return re.MatchString(get(rec)) == want }, nil
+	}
+	return func(rec Record) bool { return compare(op, get(rec), s) }, nil
+}
+
+// compare applies the comparison operator op to a and b.
+func compare[T cmp.Ordered](op string, a, b T) bool {
+	c := cmp.Compare(a, b)
+	switch op {
+	case "==":
+		return c == 0
+	case "!=":
+		return c != 0
+	case "<":
+		return c < 0
+	case "<=":
+		return c <= 0
+	case ">":
+		return c > 0
+	}
+	return c >= 0
+}
@@ -0,0 +1,99 @@
+package sales_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func TestParseFilterMatchesRecords(t *testing.T) {
+	t.Parallel()
+	rec := sales.Record{
+		Product:  "The Power of Go: Tools",
+		SKU:      "POG-T",
+		Channel:  "web",
+		Country:  "GB",
+		Quantity: 2,
+		Price:    sales.NewUSD(25),
+		Time:     time.Date(2024, time.January, 31, 9, 0, 0, 0, time.UTC),
+	}
+	tcs := []struct {
+		expr string
+		want bool
+	}{
+		{`name == "The Power of Go: Tools"`, true},
+		{`name != "The Power of Go: Tools"`, false},
+		{`name ~ "Power"`, true},
+		{`name !~ "^Power"`, true},
+		{`price >= 25`, true},
+		{`price > 25`, false},
+		{`total == 50`, true},
+		{`quantity < 2`, false},
+		{`quantity <= 2`, true},
+		{`date == "2024-01-31"`, true},
+		{`date < "2024-02-01"`, true},
+		{`sku == "POG-T" && country == "GB"`, true},
+		{`sku == "POG-T" && country == "US"`, false},
+		{`channel == "shop" || country == "GB"`, true},
+		{`!(channel == "web")`, false},
+		{`!!(channel == "web")`, true},
+		// && binds more tightly than ||.
+		{`country == "GB" || channel == "shop" && price > 100`, true},
+		{`(country == "GB" || channel == "shop") && price > 100`, false},
+		{`customer == ""`, true},
+	}
+	for _, tc := range tcs {
+		f, err := sales.ParseFilter(tc.expr)
+		if err != nil {
+			t.Errorf("%s: %v", tc.expr, err)
+			continue
+		}
+		if got := f(rec); got != tc.want {
+			t.Errorf("%s: want %t, got %t", tc.expr, tc.want, got)
+		}
+	}
+}
+
+func TestParseFilterRejectsInvalidExpressions(t *testing.T) {
+	t.Parallel()
+	for _, expr := range []string{
+		`colour == "red"`,
+		`name`,
+		`name = "Go"`,
+		`name == Go`,
+		`name == "Go`,
+		`price == "10"`,
+		`price ~ 10`,
+		`name ~ "("`,
+		`(name == "Go"`,
+		`name == "Go")`,
+		`name == "Go" &&`,
+		`name == "Go" price > 1`,
+		`#`,
+		``,
+	} {
+		if _, err := sales.ParseFilter(expr); err == nil {
+			t.Errorf("%q: want error, got nil", expr)
+		}
+	}
+}
+
+func TestReportFilterLeavesOutRecordsNotMatching(t *testing.T) {
+	t.Parallel()
+	f, err := sales.ParseFilter(`channel != "test" && price > 0`)
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.Filter = f
+	r.Add(sales.Record{Product: "Book", Channel: "web", Quantity: 2, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Book", Channel: "test", Quantity: 1, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Video", Channel: "web", Quantity: 1, Price: 0})
+	if got := r.TotalUnits(); got != 2 {
+		t.Errorf("want 2 units, got %d", got)
+	}
+	if want, got := sales.NewUSD(20), r.TotalRevenue(); want != got {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+}
@@ -0,0 +1,90 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"time"
+)
+
+// Forecast projects the revenue for the whole of the latest month in a
+// report, from the sales so far.
+type Forecast struct {
+	// Month is the month forecast, such as "2024-03".
+	Month string
+	// Through is the day of the latest sale, and DaysElapsed its day of
+	// the month.
+	Through     time.Time
+	DaysElapsed int
+	DaysInMonth int
+	SoFar       USD
+	// Linear assumes the rest of the month sells at the month's average
+	// daily rate so far.
+	Linear USD
+	// Seasonal assumes each remaining day sells as much as the average
+	// for its day of the week in earlier months. It is the same as
+	// Linear if there are no earlier sales.
+	Seasonal USD
+}
+
+// Forecast projects the month-end revenue for the month of the latest sale.
+// It reports false if there are no dated sales.
+func (r *Report) Forecast() (Forecast, bool) {
+	days := r.dailyRevenue()
+	var latest, earliest string
+	for d := range days {
+		if latest == "" || d > latest {
+			latest = d
+		}
+		if earliest == "" || d < earliest {
+			earliest = d
+		}
+	}
+	if latest == "" {
+		return Forecast{}, false
+	}
+	through, _ := time.Parse(time.DateOnly, latest)
+	first, _ := time.Parse(time.DateOnly, earliest)
+	monthStart := time.Date(through.Year(), through.Month(), 1, 0, 0, 0, 0, time.UTC)
+	f := Forecast{
+		Month:       through.Format("2006-01"),
+		Through:     through,
+		DaysElapsed: through.Day(),
+		DaysInMonth: monthStart.AddDate(0, 1, -1).Day(),
+	}
+	for d := monthStart; !d.After(through); d = d.AddDate(0, 0, 1) {
+		f.SoFar += days[d.Format(time.DateOnly)]
+	}
+	f.Linear = f.SoFar * USD(f.DaysInMonth) / USD(f.DaysElapsed)
+	// Average each weekday over every such day before this month, counting
+	// days without sales as zero.
+	var revenue [7]USD
+	var count [7]int
+	for d := first; d.Before(monthStart); d = d.AddDate(0, 0, 1) {
+		revenue[d.Weekday()] += days[d.Format(time.DateOnly)]
+		count[d.Weekday()]++
+	}
+	f.Seasonal = f.Linear
+	if first.Before(monthStart) {
+		f.Seasonal = f.SoFar
+		for d := through.AddDate(0, 0, 1); d.Month() == through.Month(); d = d.AddDate(0, 0, 1) {
+			if n := count[d.Weekday()]; n > 0 {
+				f.Seasonal += revenue[d.Weekday()] / USD(n)
+			}
+		}
+	}
+	return f, true
+}
+
+// WriteForecast writes the month-end forecast, if there is one, as text.
+func (r *Report) WriteForecast(w io.Writer) error {
+	f, ok := r.Forecast()
+	if !ok {
+		return nil
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintf(w, "Forecast for %s (through %s, day %d of %d):\n", f.Month, f.Through.Format(time.DateOnly), f.DaysElapsed, f.DaysInMonth)
+	fmt.Fprintf(w, "  So far        %s\n", f.SoFar)
+	fmt.Fprintf(w, "  At daily rate %s\n", f.Linear)
+	_, err := fmt.Fprintf(w, "  By weekday    %s\n", f.Seasonal)
+	return err
+}
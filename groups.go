@@ -0,0 +1,424 @@
+package sales
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"os"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// Group collects every product whose name matches one of its patterns, or
+// whose SKU matches one of its SKUs, under a single report row. An SKU
+// ending in * matches any SKU starting with the rest of it.
+//
+// The name can refer to the named capture groups of the patterns, as in
+// regexp.Expand: $title or ${title}. Then each product matching a
+// pattern goes in the row named with its captures, so that one group can
+// split a product's editions or volumes into rows of their own. Any other
+// $ in the name, as in "$5 bundles", is taken literally, and $$ stands for
+// a literal $ in any name, as in "$$5 bundles: $title".
+type Group struct {
+	Name     string
+	Patterns []*regexp.Regexp
+	SKUs     []string
+}
+
+// Groups is an ordered list of groups; a product belongs to the first group
+// that matches it.
+type Groups []Group
+
+// LoadGroups reads the groups file at path.
+func LoadGroups(path string) (Groups, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	g, err := ParseGroups(f)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	return g, nil
+}
+
+// ParseGroups reads group definitions, one per line, in the form:
+//
+//	Group name = pattern | pattern ...
+//
+// Patterns are regular expressions matched against product names, except
+// for those starting with "sku:", which give an SKU, or an SKU prefix
+// ending in *, as in "sku:GO-*". Blank lines and lines starting with # are
+// ignored. The name can use the patterns' named captures, as in:
+//
+//	Power of Go: $title = The Power of Go: (?P<title>\w+) | power of go: (?P<title>\w+)
+func ParseGroups(rd io.Reader) (Groups, error) {
+	var groups Groups
+	scanner := bufio.NewScanner(rd)
+	line := 0
+	for scanner.Scan() {
+		line++
+		text := strings.TrimSpace(scanner.Text())
+		if text == "" || strings.HasPrefix(text, "#") {
+			continue
+		}
+		name, patterns, ok := strings.Cut(text, "=")
+		if !ok {
+			return nil, fmt.Errorf("line %d: want name = pattern, got %q", line, text)
+		}
+		g := Group{Name: strings.TrimSpace(name)}
+		for _, p := range strings.Split(patterns, " | ") {
+			if sku, ok := strings.CutPrefix(strings.TrimSpace(p), "sku:"); ok {
+				g.SKUs = append(g.SKUs, strings.TrimSpace(sku))
+				continue
+			}
+			re, err := regexp.Compile(strings.TrimSpace(p))
+			if err != nil {
+				return nil, fmt.Errorf("line %d: %w", line, err)
+			}
+			g.Patterns = append(g.Patterns, re)
+		}
+		// Either every pattern fills in the name, or none does, so that
+		// no row is named with a capture left unexpanded. SKUs have no
+		// captures, so can't fill it in.
+		for _, re := range g.Patterns {
+			if expands(g.Name, re) != expands(g.Name, g.Patterns[0]) {
+				return nil, fmt.Errorf("line %d: name %q uses captures that not every pattern has", line, g.Name)
+			}
+		}
+		if len(g.SKUs) > 0 && len(g.Patterns) > 0 && expands(g.Name, g.Patterns[0]) {
+			return nil, fmt.Errorf("line %d: name %q uses captures, which SKU rules don't have", line, g.Name)
+		}
+		groups = append(groups, g)
+	}
+	return groups, scanner.Err()
+}
+
+// Match returns the name of the first group matching product, if any.
+func (gs Groups) Match(product string) (string, bool) {
+	return gs.MatchItem(product, "")
+}
+
+// MatchItem returns the name of the first group matching either the name
+// or the SKU of a product, if any, with any captures in it filled in from
+// the product name. An empty SKU matches no SKU rules.
+func (gs Groups) MatchItem(product, sku string) (string, bool) {
+	_, name, ok := gs.match(product, sku)
+	return name, ok
+}
+
+// match returns the index of the first group matching a product, and the
+// name of the report row it gives the product.
+func (gs Groups) match(product, sku string) (int, string, bool) {
+	for i, g := range gs {
+		if sku != "" {
+			for _, s := range g.SKUs {
+				if prefix, ok := strings.CutSuffix(s, "*"); ok && strings.HasPrefix(sku, prefix) || s == sku {
+					return i, literalName(g.Name), true
+				}
+			}
+		}
+		for _, re := range g.Patterns {
+			m := re.FindStringSubmatchIndex(product)
+			if m == nil {
+				continue
+			}
+			if !expands(g.Name, re) {
+				return i, literalName(g.Name), true
+			}
+			return i, string(re.ExpandString(nil, g.Name, product, m)), true
+		}
+	}
+	return -1, "", false
+}
+
+// captureRef matches a reference to a capture in a group name, as
+// regexp.Expand reads them, or the escape $$.
+var captureRef = regexp.MustCompile(`\$(?:\$|\{(\w+)\}|(\w+))`)
+
+// expands reports whether the group name refers to any of the named
+// captures of re, and so is filled in from them, rather than taken
+// literally.
+func expands(name string, re *regexp.Regexp) bool {
+	if !strings.Contains(name, "$") {
+		return false
+	}
+	for _, m := range captureRef.FindAllStringSubmatch(name, -1) {
+		if ref := m[1] + m[2]; ref != "" && re.SubexpIndex(ref) >= 0 {
+			return true
+		}
+	}
+	return false
+}
+
+// literalName returns the group name as a row name, when it isn't filled
+// in from captures, with each $$ standing for a $.
+func literalName(name string) string {
+	return strings.ReplaceAll(name, "$$", "$")
+}
+
+// Classify returns the report row that product belongs to: its group name,
+// or the product name itself if no group matches.
+func (gs Groups) Classify(product string) string {
+	return gs.ClassifyItem(product, "")
+}
+
+// ClassifyItem is like Classify, but also matches groups by the product's
+// SKU, if known.
+func (gs Groups) ClassifyItem(product, sku string) string {
+	if name, ok := gs.MatchItem(product, sku); ok {
+		return name
+	}
+	return product
+}
+
+// GroupMove describes a product that is classified differently under two
+// groupings.
+type GroupMove struct {
+	Product string
+	// SKU is the product's SKU, if known. A product sold under several
+	// SKUs can move differently for each.
+	SKU  string
+	From string
+	To   string
+	Tally
+}
+
+// DiffGroups reports which of the products in an ungrouped report would move
+// between rows if the grouping before were replaced by after, largest
+// revenue first. If the report kept its records, products are matched by
+// SKU as well as by name, as they are when read; otherwise, by name alone,
+// and SKU rules match nothing. It returns an error if the kept records
+// can't be read.
+func DiffGroups(r *Report, before, after Groups) ([]GroupMove, error) {
+	type item struct{ product, sku string }
+	var items []item
+	tallies := map[item]Tally{}
+	if r.KeepRecords {
+		err := r.EachRecord(func(rec Record) error {
+			k := item{product: rec.Product, sku: rec.SKU}
+			t, ok := tallies[k]
+			if !ok {
+				items = append(items, k)
+			}
+			t.Units += rec.Quantity
+			t.Revenue += rec.Total().Round(r.Rounding)
+			tallies[k] = t
+			return nil
+		})
+		if err != nil {
+			return nil, err
+		}
+	} else {
+		for _, p := range r.Products() {
+			k := item{product: p.Name}
+			items = append(items, k)
+			tallies[k] = Tally{Units: p.Units, Revenue: p.Revenue}
+		}
+	}
+	var moves []GroupMove
+	for _, k := range items {
+		from, to := before.ClassifyItem(k.product, k.sku), after.ClassifyItem(k.product, k.sku)
+		if from == to {
+			continue
+		}
+		moves = append(moves, GroupMove{
+			Product: k.product,
+			SKU:     k.sku,
+			From:    from,
+			To:      to,
+			Tally:   tallies[k],
+		})
+	}
+	sort.SliceStable(moves, func(i, j int) bool {
+		return moves[i].Revenue > moves[j].Revenue
+	})
+	return moves, nil
+}
+
+// WriteGroupMoves writes the moves found by DiffGroups as a text table,
+// followed by the total units and revenue moved.
+func (r *Report) WriteGroupMoves(w io.Writer, moves []GroupMove) error {
+	if len(moves) == 0 {
+		_, err := fmt.Fprintln(w, "No products change group.")
+		return err
+	}
+	t := &table{
+		header: []string{"Product", "SKU", "From", "To", "Units", "Revenue"},
+		right:  []bool{false, false, false, false, true, true},
+		color:  r.Color,
+	}
+	var total Tally
+	for _, m := range moves {
+		t.add(m.Product, m.SKU, m.From, m.To, strconv.Itoa(m.Units), m.Revenue.String())
+		total.Units += m.Units
+		total.Revenue += m.Revenue
+	}
+	t.footer = []string{fmt.Sprintf("%d moved", len(moves)), "", "", "", strconv.Itoa(total.Units), total.Revenue.String()}
+	return t.write(w)
+}
+
+// itemSlot identifies the sales of one product, as named in the input, and
+// the group it matched, by its position in Groups counting from one, or
+// zero if none. Groups can share a name, so they're told apart by rule.
+type itemSlot struct {
+	rule    int
+	product string
+}
+
+// itemTally is the sales of one product, counting its line items.
+type itemTally struct {
+	Lines int
+	Tally
+}
+
+// addItem counts a line item of product, which matched the group rule, as
+// numbered in itemSlot, or no group if rule is zero.
+func (r *Report) addItem(rule int, product string, units int, revenue USD) {
+	k := itemSlot{rule: rule, product: product}
+	t := r.items[k]
+	t.Lines++
+	t.Units += units
+	t.Revenue += revenue
+	r.items[k] = t
+}
+
+// GroupStats is how much of the input a group matched: the number of line
+// items, the number of distinct products among them, and their sales.
+type GroupStats struct {
+	Name  string
+	Lines int
+	Items int
+	Tally
+}
+
+// GroupsSummary shows how the report's Groups matched the line items read.
+type GroupsSummary struct {
+	// Groups has the stats of each group, in the order they're defined.
+	Groups []GroupStats
+	// Ungrouped is the stats of the line items that matched no group.
+	Ungrouped GroupStats
+	// Unmatched lists the groups that matched nothing, which may be
+	// misspelled, or for products no longer sold.
+	Unmatched []string
+}
+
+// GroupsSummary returns the number of line items and distinct products
+// matched by each group, and their units and revenue, so that a groups
+// file can be checked against the input. Summary-only sources have no line
+// items, so aren't counted. Groups sharing a name are counted separately.
+func (r *Report) GroupsSummary() GroupsSummary {
+	stats := map[int]*GroupStats{}
+	for k, t := range r.items {
+		s := stats[k.rule]
+		if s == nil {
+			s = &GroupStats{}
+			stats[k.rule] = s
+		}
+		s.Lines += t.Lines
+		s.Items++
+		s.Units += t.Units
+		s.Revenue += t.Revenue
+	}
+	var gs GroupsSummary
+	for i, g := range r.Groups {
+		var s GroupStats
+		if stats[i+1] != nil {
+			s = *stats[i+1]
+		}
+		s.Name = g.Name
+		gs.Groups = append(gs.Groups, s)
+		if s.Lines == 0 {
+			gs.Unmatched = append(gs.Unmatched, g.Name)
+		}
+	}
+	if s := stats[0]; s != nil {
+		gs.Ungrouped = *s
+	}
+	return gs
+}
+
+// WriteGroupStats writes the GroupsSummary as a text table, followed by
+// the groups that matched nothing, if any.
+func (r *Report) WriteGroupStats(w io.Writer) error {
+	gs := r.GroupsSummary()
+	t := &table{
+		header: []string{"Group", "Lines", "Products", "Units", "Revenue"},
+		right:  []bool{false, true, true, true, true},
+		color:  r.Color,
+	}
+	var total GroupStats
+	for _, s := range append(gs.Groups, gs.Ungrouped) {
+		name := s.Name
+		if name == "" {
+			name = "(ungrouped)"
+		}
+		t.add(name, strconv.Itoa(s.Lines), strconv.Itoa(s.Items), strconv.Itoa(s.Units), s.Revenue.String())
+		total.Lines += s.Lines
+		total.Items += s.Items
+		total.Units += s.Units
+		total.Revenue += s.Revenue
+	}
+	t.footer = []string{"Total", strconv.Itoa(total.Lines), strconv.Itoa(total.Items), strconv.Itoa(total.Units), total.Revenue.String()}
+	if err := t.write(w); err != nil {
+		return err
+	}
+	if len(gs.Unmatched) > 0 {
+		fmt.Fprintln(w)
+		fmt.Fprintln(w, "Groups matching nothing:", strings.Join(gs.Unmatched, ", "))
+	}
+	return nil
+}
+
+// UngroupedItem is the sales of a product that matched no group.
+type UngroupedItem struct {
+	Product string
+	Lines   int
+	Tally
+}
+
+// Ungrouped returns the products whose line items matched none of the
+// report's Groups, largest revenue first, so that new products needing a
+// grouping rule stand out.
+func (r *Report) Ungrouped() []UngroupedItem {
+	var items []UngroupedItem
+	for k, t := range r.items {
+		if k.rule == 0 {
+			items = append(items, UngroupedItem{Product: k.product, Lines: t.Lines, Tally: t.Tally})
+		}
+	}
+	sort.Slice(items, func(i, j int) bool {
+		if items[i].Revenue != items[j].Revenue {
+			return items[i].Revenue > items[j].Revenue
+		}
+		return items[i].Product < items[j].Product
+	})
+	return items
+}
+
+// WriteUngrouped writes the products that matched no group as a text
+// table, largest revenue first.
+func (r *Report) WriteUngrouped(w io.Writer) error {
+	items := r.Ungrouped()
+	if len(items) == 0 {
+		_, err := fmt.Fprintln(w, "Every product matched a group.")
+		return err
+	}
+	t := &table{
+		header: []string{"Product", "Lines", "Units", "Revenue"},
+		right:  []bool{false, true, true, true},
+		color:  r.Color,
+	}
+	var total UngroupedItem
+	for _, it := range items {
+		t.add(it.Product, strconv.Itoa(it.Lines), strconv.Itoa(it.Units), it.Revenue.String())
+		total.Lines += it.Lines
+		total.Units += it.Units
+		total.Revenue += it.Revenue
+	}
+	t.footer = []string{"Total", strconv.Itoa(total.Lines), strconv.Itoa(total.Units), total.Revenue.String()}
+	return t.write(w)
+}
@@ -0,0 +1,218 @@
+package sales_test
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestGroupNamesExpandCapturesTheyReferTo(t *testing.T) {
+	t.Parallel()
+	g, err := sales.ParseGroups(strings.NewReader(`Power of Go: $title = The Power of Go: (?P<title>\w+) | power of go: (?P<title>\w+)`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	for product, want := range map[string]string{
+		"The Power of Go: Tools": "Power of Go: Tools",
+		"power of go: Tests":     "Power of Go: Tests",
+		"For the Love of Go":     "For the Love of Go",
+	} {
+		if got := g.Classify(product); got != want {
+			t.Errorf("%q: want %q, got %q", product, want, got)
+		}
+	}
+}
+
+func TestGroupNamesKeepLiteralDollarSigns(t *testing.T) {
+	t.Parallel()
+	g, err := sales.ParseGroups(strings.NewReader(`
+$5 bundles = ^Bundle \$5
+Under ${price} = ^Cheap (?P<size>\w+)
+$$10 bundles: $size = ^Bundle \$10 (?P<size>\w+)
+`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	for product, want := range map[string]string{
+		"Bundle $5 (ebooks)":   "$5 bundles",
+		"Cheap poster":         "Under ${price}",
+		"Bundle $10 Deluxe":    "$10 bundles: Deluxe",
+		"Something else again": "Something else again",
+	} {
+		if got := g.Classify(product); got != want {
+			t.Errorf("%q: want %q, got %q", product, want, got)
+		}
+	}
+}
+
+func TestGroupNamesEscapeDollarSignsInSKURules(t *testing.T) {
+	t.Parallel()
+	g, err := sales.ParseGroups(strings.NewReader(`$$5 bundles = sku:B5-*`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got := g.ClassifyItem("Anything", "B5-EBOOKS"); got != "$5 bundles" {
+		t.Errorf("want %q, got %q", "$5 bundles", got)
+	}
+}
+
+func TestParseGroupsRejectsCapturesMissingFromSomePatterns(t *testing.T) {
+	t.Parallel()
+	_, err := sales.ParseGroups(strings.NewReader(`Power of Go: $title = The Power of Go: (?P<title>\w+) | Power of Go`))
+	if err == nil {
+		t.Error("want error, got nil")
+	}
+}
+
+func TestParseGroupsRejectsCapturesInGroupsWithSKURules(t *testing.T) {
+	t.Parallel()
+	_, err := sales.ParseGroups(strings.NewReader(`Widgets: ${kind} = ^Widget (?P<kind>\w+) | sku:WID-*`))
+	if err == nil {
+		t.Error("want error, got nil")
+	}
+	// Without captures to fill in, the name is literal, so SKU rules are
+	// fine.
+	g, err := sales.ParseGroups(strings.NewReader(`Widgets: ${kind} = ^Widget | sku:WID-*`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got := g.ClassifyItem("Gadget", "WID-1"); got != "Widgets: ${kind}" {
+		t.Errorf("want %q, got %q", "Widgets: ${kind}", got)
+	}
+}
+
+func TestDiffGroupsMatchesSKURules(t *testing.T) {
+	t.Parallel()
+	before, err := sales.ParseGroups(strings.NewReader(`Books = ^Book`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	after, err := sales.ParseGroups(strings.NewReader(`Ebooks = sku:EB-*` + "\n" + `Books = ^Book`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Add(sales.Record{Product: "Book", SKU: "EB-1", Quantity: 2, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Book", SKU: "PB-1", Quantity: 1, Price: sales.NewUSD(30)})
+	moves, err := sales.DiffGroups(r, before, after)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(moves) != 1 {
+		t.Fatalf("want 1 move, got %+v", moves)
+	}
+	m := moves[0]
+	if m.SKU != "EB-1" || m.From != "Books" || m.To != "Ebooks" || m.Units != 2 || m.Revenue != sales.NewUSD(20) {
+		t.Errorf("want Book (EB-1) moving from Books to Ebooks with 2 units, $20, got %+v", m)
+	}
+}
+
+func TestWriteSQLClassifiesProductsBySKU(t *testing.T) {
+	t.Parallel()
+	g, err := sales.ParseGroups(strings.NewReader(`Ebooks = sku:EB-*`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Groups = g
+	r.Add(sales.Record{Product: "Book", SKU: "EB-1", Quantity: 1, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Book", SKU: "PB-1", Quantity: 1, Price: sales.NewUSD(30)})
+	var sql strings.Builder
+	if err := r.WriteSQL(&sql); err != nil {
+		t.Fatal(err)
+	}
+	for _, want := range []string{
+		"INSERT INTO products VALUES ('Book', 'EB-1', 'Ebooks');",
+		"INSERT INTO products VALUES ('Book', 'PB-1', 'Book');",
+	} {
+		if !strings.Contains(sql.String(), want) {
+			t.Errorf("want %q in:\n%s", want, sql.String())
+		}
+	}
+}
+
+func TestGroupsSummaryCountsGroupsSharingANameSeparately(t *testing.T) {
+	t.Parallel()
+	g, err := sales.ParseGroups(strings.NewReader(`
+Books = ^Go
+Books = ^Rust
+`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.Groups = g
+	r.Add(sales.Record{Product: "Go Tests", Quantity: 1, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Rust Tools", Quantity: 2, Price: sales.NewUSD(20)})
+	gs := r.GroupsSummary()
+	if len(gs.Groups) != 2 {
+		t.Fatalf("want 2 groups, got %d", len(gs.Groups))
+	}
+	for i, want := range []sales.GroupStats{
+		{Name: "Books", Lines: 1, Items: 1, Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(10)}},
+		{Name: "Books", Lines: 1, Items: 1, Tally: sales.Tally{Units: 2, Revenue: sales.NewUSD(40)}},
+	} {
+		if gs.Groups[i] != want {
+			t.Errorf("group %d: want %+v, got %+v", i, want, gs.Groups[i])
+		}
+	}
+	if len(gs.Unmatched) != 0 {
+		t.Errorf("want every group matched, got %q unmatched", gs.Unmatched)
+	}
+}
+
+func TestWriteGroupMovesListsEachMoveWithTotals(t *testing.T) {
+	t.Parallel()
+	before, err := sales.ParseGroups(strings.NewReader(`Books = ^Go`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	after, err := sales.ParseGroups(strings.NewReader(`Ebooks = sku:EB-*`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Add(sales.Record{Product: "Go Tests", SKU: "EB-1", Quantity: 2, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Product: "Go Tests", SKU: "PB-1", Quantity: 1, Price: sales.NewUSD(30)})
+	moves, err := sales.DiffGroups(r, before, after)
+	if err != nil {
+		t.Fatal(err)
+	}
+	var b strings.Builder
+	if err := r.WriteGroupMoves(&b, moves); err != nil {
+		t.Fatal(err)
+	}
+	lines := strings.Split(strings.TrimSpace(b.String()), "\n")
+	for _, want := range [][]string{
+		{"Product", "SKU", "From", "To", "Units", "Revenue"},
+		{"Go Tests", "PB-1", "Books", "Go Tests", "1", "30.00"},
+		{"Go Tests", "EB-1", "Books", "Ebooks", "2", "20.00"},
+		{"2 moved", "3", "50.00"},
+	} {
+		if !containsLine(lines, want) {
+			t.Errorf("want a line with %q, got:\n%s", want, b.String())
+		}
+	}
+	b.Reset()
+	if err := r.WriteGroupMoves(&b, nil); err != nil {
+		t.Fatal(err)
+	}
+	if got := b.String(); got != "No products change group.\n" {
+		t.Errorf("want no moves reported, got %q", got)
+	}
+}
+
+// containsLine reports whether any of lines has the fields want, in order.
+func containsLine(lines []string, want []string) bool {
+	for _, line := range lines {
+		fields := strings.Join(strings.Fields(line), " ")
+		if strings.Contains(fields, strings.Join(want, " ")) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,58 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"strings"
+	"time"
+)
+
+// heatShades are the characters used for cells of increasing heat, from
+// none at all to the most.
+var heatShades = []string{"·", "░", "▒", "▓", "█"}
+
+// ByWeekdayHour returns the orders placed in each hour of each day of the
+// week, indexed by time.Weekday and hour, in the local time recorded by
+// the export.
+func (r *Report) ByWeekdayHour() [7][24]Activity {
+	return r.timing
+}
+
+// WriteHeatmap writes a grid of the orders placed in each hour of each day
+// of the week, shaded from none to the busiest hour, so that the best
+// times for launches and emails stand out.
+func (r *Report) WriteHeatmap(w io.Writer) error {
+	grid := r.ByWeekdayHour()
+	busiest := 0
+	for _, day := range grid {
+		for _, a := range day {
+			busiest = max(busiest, a.Orders)
+		}
+	}
+	var b strings.Builder
+	b.WriteString("    ")
+	for h := 0; h < 24; h += 3 {
+		fmt.Fprintf(&b, "%02d    ", h)
+	}
+	fmt.Fprintln(w, strings.TrimRight(b.String(), " "))
+	for d, day := range grid {
+		b.Reset()
+		b.WriteString(time.Weekday(d).String()[:3] + " ")
+		for _, a := range day {
+			shade := heatShades[0]
+			if a.Orders > 0 {
+				// Any orders at all get at least the lightest shade.
+				shade = heatShades[(a.Orders*(len(heatShades)-1)+busiest-1)/busiest]
+			}
+			b.WriteString(shade + shade)
+		}
+		fmt.Fprintln(w, b.String())
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintf(w, "%s none  %s fewest  %s most (%d orders an hour)\n", heatShades[0], heatShades[1], heatShades[len(heatShades)-1], busiest)
+	if len(r.aggregates) > 0 {
+		fmt.Fprintln(w)
+		fmt.Fprintln(w, "Excludes summary-only sources:", strings.Join(r.aggregates, ", "))
+	}
+	return nil
+}
@@ -0,0 +1,72 @@
+package sales
+
+import (
+	"html/template"
+	"io"
+)
+
+var htmlReport = template.Must(template.New("report").Parse(`<!DOCTYPE html>
+<html lang="{{.Locale.Tag}}">
+<head>
+<meta charset="utf-8">
+<title>Sales report</title>
+</head>
+<body>
+<table class="sales-report">
+<thead>
+<tr><th>Product</th><th>Units</th><th>Revenue</th></tr>
+</thead>
+<tbody>
+{{- range .Products}}
+<tr><td>{{.Name}}</td><td data-value="{{.Units}}">{{.Units}}</td><td data-value="{{.Value}}" data-currency="{{$.Currency}}">{{.Display}}</td></tr>
+{{- end}}
+</tbody>
+<tfoot>
+{{- with .Total}}
+<tr><th>Total</th><td data-value="{{.Units}}">{{.Units}}</td><td data-value="{{.Value}}" data-currency="{{$.Currency}}">{{.Display}}</td></tr>
+{{- end}}
+</tfoot>
+</table>
+</body>
+</html>
+`))
+
+type htmlRow struct {
+	Name    string
+	Units   int
+	Value   string
+	Display string
+}
+
+// WriteHTML writes the report as an HTML table, with revenue formatted for
+// loc, in the report's Currency. Each number cell carries its raw value in
+// a data-value attribute, and each amount its currency code in
+// data-currency, so that scripts can sort and sum the columns without
+// parsing the display text.
+func (r *Report) WriteHTML(w io.Writer, loc Locale) error {
+	r.logRender("html")
+	currency := r.reportCurrency()
+	data := struct {
+		Locale   Locale
+		Currency string
+		Products []htmlRow
+		Total    htmlRow
+	}{
+		Locale:   loc,
+		Currency: currency,
+		Total: htmlRow{
+			Units:   r.TotalUnits(),
+			Value:   r.TotalRevenue().String(),
+			Display: loc.FormatCurrency(r.TotalRevenue(), currency),
+		},
+	}
+	for _, p := range r.Products() {
+		data.Products = append(data.Products, htmlRow{
+			Name:    p.Name,
+			Units:   p.Units,
+			Value:   p.Revenue.String(),
+			Display: loc.FormatCurrency(p.Revenue, currency),
+		})
+	}
+	return htmlReport.Execute(w, data)
+}
@@ -0,0 +1,48 @@
+package sales
+
+import (
+	"log/slog"
+	"time"
+)
+
+// InputStats describes the reading of one input into the report.
+type InputStats struct {
+	Source string
+	// Rows is the number of rows or records read from the input, and
+	// Skipped the number of those skipped as bad; see SkipBadRows.
+	Rows    int64
+	Skipped int
+	Elapsed time.Duration
+	// Err is the error that stopped the read, if any.
+	Err error
+}
+
+// Inputs returns the statistics for each input read into the report, in the
+// order they were read. Like Skipped, they describe the inputs read by this
+// process, so aren't saved by Save.
+func (r *Report) Inputs() []InputStats {
+	return r.inputs
+}
+
+// track calls read, which reads the input called source, and records its
+// statistics. The read is logged to the report's Logger, if any.
+func (r *Report) track(source string, read func() error) error {
+	r.log(slog.LevelDebug, "reading input", "source", source)
+	start, rows, skipped := time.Now(), r.rows, len(r.skipped)
+	err := read()
+	in := InputStats{
+		Source:  source,
+		Rows:    r.rows - rows,
+		Skipped: len(r.skipped) - skipped,
+		Elapsed: time.Since(start),
+		Err:     err,
+	}
+	r.inputs = append(r.inputs, in)
+	args := []any{"source", source, "rows", in.Rows, "skipped", in.Skipped, "elapsed", in.Elapsed}
+	if err != nil {
+		r.log(slog.LevelError, "reading input failed", append(args, "err", err)...)
+	} else {
+		r.log(slog.LevelInfo, "read input", args...)
+	}
+	return err
+}
@@ -0,0 +1,163 @@
+package sales
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+)
+
+// ReadJSON reads a JSON array of sale objects from rd, such as the events
+// returned by the Gumroad API. Each object is mapped to a record in the
+// same way as a CSV row, using the field names in Columns as keys. String,
+// number, and boolean values are accepted. If SkipBadRows is set,
+// malformed objects are skipped and recorded in Skipped.
+func (r *Report) ReadJSON(rd io.Reader, source string) error {
+	if r.Limits.MaxFileSize > 0 {
+		rd = &limitedReader{r: rd, limit: r.Limits.MaxFileSize}
+	}
+	data, err := io.ReadAll(rd)
+	if err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	dec := json.NewDecoder(bytes.NewReader(data))
+	dec.UseNumber()
+	tok, err := dec.Token()
+	if err == io.EOF {
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	if d, ok := tok.(json.Delim); !ok || d != '[' {
+		return fmt.Errorf("%s: want a JSON array of sales", source)
+	}
+	rr := r.jsonRowReader(source)
+	for dec.More() {
+		// The decoder's offset is just after the previous value, so
+		// skip the separator to find the line the object starts on.
+		start := dec.InputOffset()
+		for start < int64(len(data)) && bytes.IndexByte([]byte(" \t\r\n,"), data[start]) >= 0 {
+			start++
+		}
+		line := 1 + bytes.Count(data[:start], []byte("\n"))
+		var obj map[string]any
+		if err := dec.Decode(&obj); err != nil {
+			return fmt.Errorf("%s:%d: %w", source, line, err)
+		}
+		if err := rr.addObject(obj, line); err != nil {
+			return err
+		}
+	}
+	if _, err := dec.Token(); err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	return nil
+}
+
+// ReadNDJSON is like ReadJSON, but reads newline-delimited JSON: one sale
+// object per line, as sent by many webhook pipelines. Blank lines are
+// ignored.
+func (r *Report) ReadNDJSON(rd io.Reader, source string) error {
+	if r.Limits.MaxFileSize > 0 {
+		rd = &limitedReader{r: rd, limit: r.Limits.MaxFileSize}
+	}
+	rr := r.jsonRowReader(source)
+	scanner := bufio.NewScanner(rd)
+	scanner.Buffer(nil, 1<<20)
+	line := 0
+	for scanner.Scan() {
+		line++
+		text := bytes.TrimSpace(scanner.Bytes())
+		if len(text) == 0 {
+			continue
+		}
+		dec := json.NewDecoder(bytes.NewReader(text))
+		dec.UseNumber()
+		var obj map[string]any
+		if err := dec.Decode(&obj); err != nil {
+			if err := r.reject(&RowError{File: source, Line: line, Err: err}, rr.lenient); err != nil {
+				return err
+			}
+			continue
+		}
+		if err := rr.addObject(obj, line); err != nil {
+			return err
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	return nil
+}
+
+func (r *Report) jsonRowReader(source string) *rowReader {
+	return &rowReader{r: r, source: source, cols: r.columnsFor(source), lenient: r.SkipBadRows}
+}
+
+// addObject adds the sale described by obj, which starts on the given line.
+func (rr *rowReader) addObject(obj map[string]any, line int) error {
+	rr.header = rr.header[:0]
+	for key := range obj {
+		rr.header = append(rr.header, key)
+	}
+	sort.Strings(rr.header)
+	rr.col = map[string]int{}
+	record := make([]string, len(rr.header))
+	for i, key := range rr.header {
+		rr.col[key] = i
+		switch v := obj[key].(type) {
+		case nil:
+		case string:
+			record[i] = v
+		case json.Number:
+			record[i] = v.String()
+		case bool:
+			record[i] = strconv.FormatBool(v)
+		default:
+			err := fmt.Errorf("field %q is not a string, number, or boolean", key)
+			return rr.r.reject(&RowError{File: rr.source, Line: line, Err: err}, rr.lenient)
+		}
+	}
+	for _, name := range []string{rr.cols.Name, rr.cols.Price} {
+		if _, ok := rr.col[name]; !ok {
+			err := fmt.Errorf("missing field %q", name)
+			return rr.r.reject(&RowError{File: rr.source, Line: line, Err: err}, rr.lenient)
+		}
+	}
+	return rr.add(record, func(int) int { return line })
+}
+
+// MarshalJSON encodes u as an object giving both the amount in whole cents
+// and the amount as formatted by String, such as
+// {"cents":123450,"formatted":"1234.50"}.
+func (u USD) MarshalJSON() ([]byte, error) {
+	return json.Marshal(struct {
+		Cents     int64  `json:"cents"`
+		Formatted string `json:"formatted"`
+	}{u.Cents(), u.String()})
+}
+
+// MarshalJSON encodes p with its provenance, so that services embedding
+// report data can use it without reformatting the text output.
+func (p *Product) MarshalJSON() ([]byte, error) {
+	return json.Marshal(struct {
+		Name       string  `json:"name"`
+		Units      int     `json:"units"`
+		Revenue    USD     `json:"revenue"`
+		Provenance []Share `json:"provenance"`
+	}{p.Name, p.Units, p.Revenue, p.Provenance()})
+}
+
+// MarshalJSON encodes the report's products, in the order given by Sort,
+// and its totals.
+func (r *Report) MarshalJSON() ([]byte, error) {
+	return json.Marshal(struct {
+		Products     []*Product `json:"products"`
+		TotalUnits   int        `json:"total_units"`
+		TotalRevenue USD        `json:"total_revenue"`
+	}{r.Products(), r.TotalUnits(), r.TotalRevenue()})
+}
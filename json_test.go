@@ -0,0 +1,87 @@
+package sales_test
+
+import (
+	"errors"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestReadJSONAcceptsStringAndNumberValues(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	input := `[
+  {"Lineitem name": "Book", "Lineitem price": 10.5, "Lineitem quantity": "2"},
+  {"Lineitem name": "Video", "Lineitem price": "20.00", "Lineitem quantity": 1, "Lineitem sku": null}
+]`
+	if err := r.ReadJSON(strings.NewReader(input), "sales.json"); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 3 {
+		t.Errorf("want 3 units, got %d", got)
+	}
+	if want, got := sales.NewUSD(41), r.TotalRevenue(); want != got {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+}
+
+func TestReadJSONReportsTheLineOfABadObject(t *testing.T) {
+	t.Parallel()
+	input := `[
+  {"Lineitem name": "Book", "Lineitem price": 10},
+  {"Lineitem name": "Video", "Lineitem price": {"amount": 20}}
+]`
+	err := sales.NewReport().ReadJSON(strings.NewReader(input), "sales.json")
+	var rowErr *sales.RowError
+	if !errors.As(err, &rowErr) {
+		t.Fatalf("want RowError, got %v", err)
+	}
+	if rowErr.Line != 3 {
+		t.Errorf("want error on line 3, got line %d", rowErr.Line)
+	}
+}
+
+func TestReadJSONRejectsObjectsMissingRequiredFields(t *testing.T) {
+	t.Parallel()
+	input := `[{"Lineitem name": "Book"}]`
+	if err := sales.NewReport().ReadJSON(strings.NewReader(input), "sales.json"); err == nil {
+		t.Error("want error for missing price, got nil")
+	}
+}
+
+func TestReadJSONRejectsInputThatIsNotAnArray(t *testing.T) {
+	t.Parallel()
+	input := `{"Lineitem name": "Book", "Lineitem price": 10}`
+	if err := sales.NewReport().ReadJSON(strings.NewReader(input), "sales.json"); err == nil {
+		t.Error("want error for a single object, got nil")
+	}
+}
+
+func TestReadNDJSONSkipsBlankLinesAndBadRowsIfAsked(t *testing.T) {
+	t.Parallel()
+	input := `{"Lineitem name": "Book", "Lineitem price": 10, "Lineitem quantity": 2}
+
+{"Lineitem name": "Video", "Lineitem price":
+{"Lineitem name": "Course", "Lineitem price": "100.00"}
+`
+	r := sales.NewReport()
+	r.SkipBadRows = true
+	if err := r.ReadNDJSON(strings.NewReader(input), "sales.ndjson"); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 3 {
+		t.Errorf("want 3 units, got %d", got)
+	}
+	skipped := r.Skipped()
+	if len(skipped) != 1 {
+		t.Fatalf("want 1 skipped row, got %d", len(skipped))
+	}
+	if skipped[0].Line != 3 {
+		t.Errorf("want line 3 skipped, got line %d", skipped[0].Line)
+	}
+	r = sales.NewReport()
+	if err := r.ReadNDJSON(strings.NewReader(input), "sales.ndjson"); err == nil {
+		t.Error("want error for bad row without SkipBadRows, got nil")
+	}
+}
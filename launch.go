@@ -0,0 +1,136 @@
+package sales
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// LaunchWindow is the sales of a product in some period around its launch.
+type LaunchWindow struct {
+	// Name describes the window, such as "First 24h" or "Baseline".
+	Name string
+	// From and To are the bounds of the window: [From, To).
+	From, To time.Time
+	Tally
+}
+
+// PerDay returns the window's average revenue per day.
+func (lw LaunchWindow) PerDay() USD {
+	days := lw.To.Sub(lw.From).Hours() / 24
+	if days <= 0 {
+		return 0
+	}
+	return USD(float64(lw.Revenue) / days)
+}
+
+// Launch is the sales of a product in windows starting at its launch,
+// compared with a baseline window of the same length before it.
+type Launch struct {
+	Product  string
+	At       time.Time
+	Baseline LaunchWindow
+	Windows  []LaunchWindow
+}
+
+// Lift returns how many times the baseline's revenue per day lw made, or
+// false if there was no revenue in the baseline to compare it with.
+func (l Launch) Lift(lw LaunchWindow) (float64, bool) {
+	base := l.Baseline.PerDay()
+	if base <= 0 {
+		return 0, false
+	}
+	return float64(lw.PerDay()) / float64(base), true
+}
+
+// ParseWindow parses the length of a launch window, which is a number of
+// days, such as "7d", of weeks, such as "2w", or a duration understood by
+// time.ParseDuration, such as "24h".
+func ParseWindow(s string) (time.Duration, error) {
+	var d time.Duration
+	var err error
+	switch {
+	case strings.HasSuffix(s, "d"), strings.HasSuffix(s, "w"):
+		var n int
+		n, err = strconv.Atoi(s[:len(s)-1])
+		d = time.Duration(n) * 24 * time.Hour
+		if strings.HasSuffix(s, "w") {
+			d *= 7
+		}
+	default:
+		d, err = time.ParseDuration(s)
+	}
+	if err != nil || d <= 0 {
+		return 0, fmt.Errorf("invalid window %q (want a length such as 24h, 7d, or 2w)", s)
+	}
+	return d, nil
+}
+
+// windowName describes a window of length d, in days if it's a whole
+// number of them, and longer than one.
+func windowName(d time.Duration) string {
+	if d > 24*time.Hour && d%(24*time.Hour) == 0 {
+		return fmt.Sprintf("First %dd", d/(24*time.Hour))
+	}
+	return "First " + strings.TrimSuffix(strings.TrimSuffix(d.String(), "0s"), "0m")
+}
+
+// Launch returns the sales of product, the name of a report row, in each
+// of the windows starting at the launch time at, and in the baseline
+// period of the given length just before it. It needs the records kept by
+// KeepRecords, and reports an error without them.
+func (r *Report) Launch(product string, at time.Time, windows []time.Duration, baseline time.Duration) (Launch, error) {
+	if !r.KeepRecords {
+		return Launch{}, errors.New("launch analysis needs KeepRecords")
+	}
+	l := Launch{
+		Product:  product,
+		At:       at,
+		Baseline: LaunchWindow{Name: "Baseline", From: at.Add(-baseline), To: at},
+	}
+	for _, d := range windows {
+		l.Windows = append(l.Windows, LaunchWindow{Name: windowName(d), From: at, To: at.Add(d)})
+	}
+	err := r.EachRecord(func(rec Record) error {
+		if rec.Time.IsZero() || r.Groups.ClassifyItem(rec.Product, rec.SKU) != product {
+			return nil
+		}
+		total := rec.Total().Round(r.Rounding)
+		l.Baseline.add(rec, total)
+		for i := range l.Windows {
+			l.Windows[i].add(rec, total)
+		}
+		return nil
+	})
+	return l, err
+}
+
+// add counts rec, whose total is given, if it's in the window.
+func (lw *LaunchWindow) add(rec Record, total USD) {
+	if !rec.Time.Before(lw.From) && rec.Time.Before(lw.To) {
+		lw.Units += rec.Quantity
+		lw.Revenue += total
+	}
+}
+
+// WriteLaunch writes the sales in each window of l as a text table, with
+// each window's revenue per day compared with the baseline's.
+func (r *Report) WriteLaunch(w io.Writer, l Launch) error {
+	fmt.Fprintf(w, "%s, launched %s\n", l.Product, l.At.Format("2006-01-02 15:04"))
+	t := &table{
+		header: []string{"Window", "From", "To", "Units", "Revenue", "Per day", "Lift"},
+		right:  []bool{false, false, false, true, true, true, true},
+		color:  r.Color,
+	}
+	for _, lw := range append([]LaunchWindow{l.Baseline}, l.Windows...) {
+		lift := ""
+		if v, ok := l.Lift(lw); ok && lw.Name != l.Baseline.Name {
+			lift = fmt.Sprintf("%.1fx", v)
+		}
+		t.add(lw.Name, lw.From.Format("2006-01-02 15:04"), lw.To.Format("2006-01-02 15:04"), strconv.Itoa(lw.Units), lw.Revenue.String(), lw.PerDay().String(), lift)
+	}
+	return t.write(w)
+}
@@ -0,0 +1,92 @@
+package sales
+
+import (
+	"encoding/csv"
+	"errors"
+	"io"
+	"sort"
+	"strconv"
+	"time"
+)
+
+// ledgerItem identifies a stock item in the units ledger: its SKU, or,
+// for sales without one, its product name.
+type ledgerItem struct {
+	sku     string
+	product string
+}
+
+// WriteUnitsLedger writes the units of each SKU sold on each day, and the
+// cumulative units sold up to and including that day, as long-format CSV
+// with the columns date, sku, product, units, and cumulative_units, for
+// feeding inventory tools. There's a row for every day from an item's
+// first sale to the report's last, so days without sales carry the total
+// forward. Refunds and returns, with negative quantities, reduce it. Sales
+// without a SKU are ledgered by product name, with an empty sku. It needs
+// the dated records kept by KeepRecords, and reports an error without
+// them.
+func (r *Report) WriteUnitsLedger(w io.Writer) error {
+	if !r.KeepRecords {
+		return errors.New("units ledger needs KeepRecords")
+	}
+	daily := map[ledgerItem]map[string]int{}
+	first := map[ledgerItem]time.Time{}
+	var last time.Time
+	// A SKU's product name is the one on its latest sale.
+	names := map[string]string{}
+	latest := map[string]time.Time{}
+	err := r.EachRecord(func(rec Record) error {
+		if rec.Time.IsZero() {
+			return nil
+		}
+		item := ledgerItem{sku: rec.SKU}
+		if rec.SKU == "" {
+			item.product = rec.Product
+		} else if !rec.Time.Before(latest[rec.SKU]) {
+			names[rec.SKU], latest[rec.SKU] = rec.Product, rec.Time
+		}
+		if daily[item] == nil {
+			daily[item] = map[string]int{}
+		}
+		day := rec.Time.Format(time.DateOnly)
+		daily[item][day] += rec.Quantity
+		d, _ := time.Parse(time.DateOnly, day)
+		if f, ok := first[item]; !ok || d.Before(f) {
+			first[item] = d
+		}
+		if d.After(last) {
+			last = d
+		}
+		return nil
+	})
+	if err != nil {
+		return err
+	}
+	items := make([]ledgerItem, 0, len(daily))
+	for item := range daily {
+		items = append(items, item)
+	}
+	sort.Slice(items, func(i, j int) bool {
+		if items[i].sku != items[j].sku {
+			return items[i].sku < items[j].sku
+		}
+		return items[i].product < items[j].product
+	})
+	cw := csv.NewWriter(w)
+	cw.Write([]string{"date", "sku", "product", "units", "cumulative_units"})
+	for _, item := range items {
+		product := item.product
+		if item.sku != "" {
+			product = names[item.sku]
+		}
+		cumulative := 0
+		for d := first[item]; !d.After(last); d = d.AddDate(0, 0, 1) {
+			day := d.Format(time.DateOnly)
+			units := daily[item][day]
+			cumulative += units
+			cw.Write([]string{day, item.sku, product, strconv.Itoa(units), strconv.Itoa(cumulative)})
+		}
+	}
+	cw.Flush()
+	return cw.Error()
+}
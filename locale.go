@@ -0,0 +1,144 @@
+package sales
+
+import (
+	"fmt"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"unicode"
+	"unicode/utf8"
+)
+
+// Locale describes how money amounts are written for display in a particular
+// locale.
+type Locale struct {
+	Tag         string
+	Decimal     string
+	Thousands   string
+	SymbolAfter bool
+}
+
+// localesMu guards locales, which RegisterLocale may change while other
+// goroutines look locales up.
+var localesMu sync.RWMutex
+
+var locales = map[string]Locale{
+	"en-US": {Tag: "en-US", Decimal: ".", Thousands: ","},
+	"en-GB": {Tag: "en-GB", Decimal: ".", Thousands: ","},
+	"de-DE": {Tag: "de-DE", Decimal: ",", Thousands: ".", SymbolAfter: true},
+	"fr-FR": {Tag: "fr-FR", Decimal: ",", Thousands: " ", SymbolAfter: true},
+	"es-ES": {Tag: "es-ES", Decimal: ",", Thousands: ".", SymbolAfter: true},
+	"nl-NL": {Tag: "nl-NL", Decimal: ",", Thousands: "."},
+}
+
+// DefaultLocale is used when no locale is specified. It's set when the
+// package is initialised, before anything can call RegisterLocale.
+var DefaultLocale = locales["en-US"]
+
+// currencySymbols are the symbols of common currencies, by code.
+var currencySymbols = map[string]string{
+	"USD": "$",
+	"EUR": "€",
+	"GBP": "£",
+	"JPY": "¥",
+	"CNY": "¥",
+	"INR": "₹",
+	"KRW": "₩",
+	"BRL": "R$",
+	"CAD": "CA$",
+	"AUD": "A$",
+	"NZD": "NZ$",
+	"MXN": "MX$",
+	"HKD": "HK$",
+	"SGD": "S$",
+}
+
+// CurrencySymbol returns the symbol of the currency with the given code,
+// such as "€" for "EUR", or the code itself if the symbol isn't known.
+func CurrencySymbol(code string) string {
+	code = strings.ToUpper(code)
+	if symbol, ok := currencySymbols[code]; ok {
+		return symbol
+	}
+	return code
+}
+
+// RegisterLocale makes l available to LookupLocale under its tag, replacing
+// any existing locale with the same tag. It's safe to call concurrently
+// with LookupLocale.
+func RegisterLocale(l Locale) {
+	localesMu.Lock()
+	defer localesMu.Unlock()
+	locales[l.Tag] = l
+}
+
+// LookupLocale returns the locale with the given tag, such as "de-DE".
+func LookupLocale(tag string) (Locale, error) {
+	localesMu.RLock()
+	defer localesMu.RUnlock()
+	l, ok := locales[tag]
+	if !ok {
+		tags := make([]string, 0, len(locales))
+		for t := range locales {
+			tags = append(tags, t)
+		}
+		sort.Strings(tags)
+		return Locale{}, fmt.Errorf("unknown locale %q (known locales: %s)", tag, strings.Join(tags, ", "))
+	}
+	return l, nil
+}
+
+// Format writes u as a dollar amount, such as "$1,234.50" or "1.234,50 $".
+func (l Locale) Format(u USD) string {
+	return l.FormatCurrency(u, "USD")
+}
+
+// FormatCurrency writes u as an amount of the currency with the given code,
+// such as "€1,234.50" or "1.234,50 €". A currency without a known symbol
+// is written with its code, as in "SEK 1,234.50".
+func (l Locale) FormatCurrency(u USD, code string) string {
+	cents := u.Cents()
+	sign := ""
+	if cents < 0 {
+		sign = "-"
+		cents = -cents
+	}
+	whole := strconv.FormatInt(cents/100, 10)
+	var b strings.Builder
+	for i, c := range whole {
+		if i > 0 && (len(whole)-i)%3 == 0 {
+			b.WriteString(l.Thousands)
+		}
+		b.WriteRune(c)
+	}
+	amount := fmt.Sprintf("%s%s%02d", b.String(), l.Decimal, cents%100)
+	symbol := CurrencySymbol(code)
+	if l.SymbolAfter {
+		return sign + amount + " " + symbol
+	}
+	if r, _ := utf8.DecodeLastRuneInString(symbol); unicode.IsLetter(r) {
+		symbol += " "
+	}
+	return sign + symbol + amount
+}
+
+// ExcelFormat returns the Excel number format for dollar amounts in this
+// locale. Excel localises the separators itself, so only the position of
+// the currency symbol varies.
+func (l Locale) ExcelFormat() string {
+	return l.ExcelCurrencyFormat("USD")
+}
+
+// ExcelCurrencyFormat returns the Excel number format for amounts of the
+// currency with the given code in this locale.
+func (l Locale) ExcelCurrencyFormat(code string) string {
+	symbol := strings.ReplaceAll(CurrencySymbol(code), `"`, "")
+	if l.SymbolAfter {
+		return `#,##0.00\ "` + symbol + `"`
+	}
+	if r, _ := utf8.DecodeLastRuneInString(symbol); unicode.IsLetter(r) {
+		symbol += " "
+	}
+	return `"` + symbol + `"#,##0.00`
+}
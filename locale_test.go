@@ -0,0 +1,89 @@
+package sales_test
+
+import (
+	"fmt"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestFormatCurrencyUsesTheCurrencySymbol(t *testing.T) {
+	t.Parallel()
+	de, err := sales.LookupLocale("de-DE")
+	if err != nil {
+		t.Fatal(err)
+	}
+	tcs := []struct {
+		loc  sales.Locale
+		code string
+		want string
+	}{
+		{sales.DefaultLocale, "USD", "$1,234.50"},
+		{sales.DefaultLocale, "eur", "€1,234.50"},
+		{sales.DefaultLocale, "SEK", "SEK 1,234.50"},
+		{de, "EUR", "1.234,50 €"},
+		{de, "SEK", "1.234,50 SEK"},
+	}
+	for _, tc := range tcs {
+		if got := tc.loc.FormatCurrency(sales.NewUSD(1234.50), tc.code); got != tc.want {
+			t.Errorf("%s in %s: want %q, got %q", tc.code, tc.loc.Tag, tc.want, got)
+		}
+	}
+	if got := sales.DefaultLocale.Format(sales.NewUSD(-5)); got != "-$5.00" {
+		t.Errorf("want %q, got %q", "-$5.00", got)
+	}
+}
+
+func TestWriteHTMLAndExcelUseTheReportCurrency(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Currency = "EUR"
+	r.Add(sales.Record{Product: "Book", Quantity: 2, Price: sales.NewUSD(10)})
+	var html strings.Builder
+	if err := r.WriteHTML(&html, sales.DefaultLocale); err != nil {
+		t.Fatal(err)
+	}
+	for _, want := range []string{`data-currency="EUR"`, "€20.00"} {
+		if !strings.Contains(html.String(), want) {
+			t.Errorf("want %q in HTML:\n%s", want, html.String())
+		}
+	}
+	if strings.Contains(html.String(), "USD") || strings.Contains(html.String(), "$") {
+		t.Errorf("want no dollars in HTML:\n%s", html.String())
+	}
+	var excel strings.Builder
+	if err := r.WriteExcel(&excel, sales.DefaultLocale); err != nil {
+		t.Fatal(err)
+	}
+	if want := `ss:Format="&#34;€&#34;#,##0.00"`; !strings.Contains(excel.String(), want) {
+		t.Errorf("want %q in spreadsheet:\n%s", want, excel.String())
+	}
+}
+
+func TestRegisterLocaleIsSafeWhileLocalesAreLookedUp(t *testing.T) {
+	t.Parallel()
+	var wg sync.WaitGroup
+	for i := 0; i < 10; i++ {
+		tag := fmt.Sprintf("x-test-%d", i)
+		wg.Add(2)
+		go func() {
+			defer wg.Done()
+			sales.RegisterLocale(sales.Locale{Tag: tag, Decimal: ".", Thousands: ","})
+		}()
+		go func() {
+			defer wg.Done()
+			if _, err := sales.LookupLocale("en-US"); err != nil {
+				t.Error(err)
+			}
+		}()
+	}
+	wg.Wait()
+	for i := 0; i < 10; i++ {
+		tag := fmt.Sprintf("x-test-%d", i)
+		if _, err := sales.LookupLocale(tag); err != nil {
+			t.Errorf("%s: %v", tag, err)
+		}
+	}
+}
@@ -0,0 +1,19 @@
+package sales
+
+import (
+	"context"
+	"log/slog"
+)
+
+// log writes an event to the report's Logger, if it has one.
+func (r *Report) log(level slog.Level, msg string, args ...any) {
+	if r.Logger == nil {
+		return
+	}
+	r.Logger.Log(context.Background(), level, msg, args...)
+}
+
+// logRender logs the rendering of the report in format.
+func (r *Report) logRender(format string) {
+	r.log(slog.LevelDebug, "rendering report", "format", format, "rows", len(r.products))
+}
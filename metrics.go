@@ -0,0 +1,45 @@
+package sales
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"strings"
+)
+
+// WriteMetrics writes the report's totals, the revenue and units for each
+// product, and counts of the input read, in the Prometheus text exposition
+// format.
+func (r *Report) WriteMetrics(w io.Writer) error {
+	bw := bufio.NewWriter(w)
+	metric := func(name, help, kind string) {
+		fmt.Fprintf(bw, "# HELP %s %s\n# TYPE %s %s\n", name, help, name, kind)
+	}
+	metric("sales_revenue_dollars", "Total revenue in US dollars.", "gauge")
+	fmt.Fprintf(bw, "sales_revenue_dollars %s\n", r.TotalRevenue())
+	metric("sales_units", "Total units sold.", "gauge")
+	fmt.Fprintf(bw, "sales_units %d\n", r.TotalUnits())
+	products := r.Products()
+	metric("sales_product_revenue_dollars", "Revenue in US dollars by product.", "gauge")
+	for _, p := range products {
+		fmt.Fprintf(bw, "sales_product_revenue_dollars{product=\"%s\"} %s\n", metricLabel(p.Name), p.Revenue)
+	}
+	metric("sales_product_units", "Units sold by product.", "gauge")
+	for _, p := range products {
+		fmt.Fprintf(bw, "sales_product_units{product=\"%s\"} %d\n", metricLabel(p.Name), p.Units)
+	}
+	metric("sales_rows_read", "Input rows read.", "gauge")
+	fmt.Fprintf(bw, "sales_rows_read %d\n", r.rows)
+	metric("sales_rows_skipped", "Malformed input rows skipped.", "gauge")
+	fmt.Fprintf(bw, "sales_rows_skipped %d\n", len(r.skipped))
+	metric("sales_warnings", "Warnings about suspicious input.", "gauge")
+	fmt.Fprintf(bw, "sales_warnings %d\n", len(r.warnings))
+	metric("sales_duplicate_orders", "Orders skipped as duplicates.", "gauge")
+	fmt.Fprintf(bw, "sales_duplicate_orders %d\n", r.duplicates)
+	return bw.Flush()
+}
+
+// metricLabel escapes s for use as a Prometheus label value.
+func metricLabel(s string) string {
+	return strings.NewReplacer(`\`, `\\`, `"`, `\"`, "\n", `\n`).Replace(s)
+}
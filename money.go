@@ -0,0 +1,61 @@
+package sales
+
+import (
+	"errors"
+	"math"
+	"strconv"
+	"strings"
+)
+
+// ParseUSD parses a dollar amount such as "12.50", "$12.50", or "1e3".
+// Negative amounts, such as refunds and chargebacks, can be written with a
+// minus sign, as in "-12.50" or "-$12.50", or in parentheses, as
+// accountants write them: "(12.50)" or "($12.50)".
+func ParseUSD(s string) (USD, error) {
+	v := strings.TrimSpace(s)
+	neg := false
+	if strings.HasPrefix(v, "(") && strings.HasSuffix(v, ")") {
+		neg = true
+		v = strings.TrimSpace(v[1 : len(v)-1])
+	}
+	if rest, ok := strings.CutPrefix(v, "-"); ok && !neg {
+		neg = true
+		v = rest
+	}
+	v = strings.TrimPrefix(v, "$")
+	if v == "" || v[0] == '-' || v[0] == '+' {
+		return 0, errors.New("invalid amount")
+	}
+	dollars, err := strconv.ParseFloat(v, 64)
+	if err != nil {
+		return 0, errors.New("invalid amount")
+	}
+	if math.IsInf(dollars, 0) || math.IsNaN(dollars) || math.Abs(dollars) > math.MaxInt64/(100*centUnits) {
+		return 0, errors.New("amount out of range")
+	}
+	if neg {
+		dollars = -dollars
+	}
+	return NewUSD(dollars), nil
+}
+
+// Mul returns u multiplied by f, such as a tax or exchange rate, to the
+// nearest hundredth of a cent.
+func (u USD) Mul(f float64) USD {
+	return USD(math.Round(float64(u) * f))
+}
+
+// Sum returns the total of the amounts that amount gives for each of items.
+// Amounts can be added and subtracted with + and -, but Sum saves a loop
+// in ad hoc calculations, such as:
+//
+//	refunds := sales.Sum(records, func(rec sales.Record) sales.USD {
+//		return min(rec.Total(), 0)
+//	})
+func Sum[T any](items []T, amount func(T) USD) USD {
+	var total USD
+	for _, item := range items {
+		total += amount(item)
+	}
+	return total
+}
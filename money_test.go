@@ -0,0 +1,93 @@
+package sales_test
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestParseUSDAcceptsNegativeAmounts(t *testing.T) {
+	t.Parallel()
+	tcs := []struct {
+		input string
+		want  sales.USD
+	}{
+		{"12.50", sales.NewUSD(12.50)},
+		{"$12.50", sales.NewUSD(12.50)},
+		{"-12.50", sales.NewUSD(-12.50)},
+		{"-$12.50", sales.NewUSD(-12.50)},
+		{"(12.50)", sales.NewUSD(-12.50)},
+		{"($12.50)", sales.NewUSD(-12.50)},
+		{" ( 12.50 ) ", sales.NewUSD(-12.50)},
+	}
+	for _, tc := range tcs {
+		got, err := sales.ParseUSD(tc.input)
+		if err != nil {
+			t.Errorf("%q: %v", tc.input, err)
+			continue
+		}
+		if got != tc.want {
+			t.Errorf("%q: want %v, got %v", tc.input, tc.want, got)
+		}
+	}
+}
+
+func TestParseUSDRejectsDoubleNegatives(t *testing.T) {
+	t.Parallel()
+	for _, input := range []string{"", "(-12.50)", "--12.50", "-(12.50)", "$-12.50", "+12.50", "twelve"} {
+		if _, err := sales.ParseUSD(input); err == nil {
+			t.Errorf("%q: want error, got nil", input)
+		}
+	}
+}
+
+func TestRefundsReadFromCSVKeepJSONOutputValid(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	sold := "Lineitem name,Lineitem price,Lineitem quantity\nBook,$10.00,2\n"
+	refunded := "Lineitem name,Lineitem price,Lineitem quantity\nBook,($30.00),1\nVideo,-$5.00,1\n"
+	if err := r.ReadCSV(strings.NewReader(sold), "orders.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if err := r.ReadCSV(strings.NewReader(refunded), "refunds.csv"); err != nil {
+		t.Fatal(err)
+	}
+	if want, got := sales.NewUSD(-15), r.TotalRevenue(); want != got {
+		t.Errorf("want total revenue %v, got %v", want, got)
+	}
+	validateReport(t, r, []sales.OutputColumn{sales.ColumnPercent})
+}
+
+func TestUSDHoldsTotalsBeyond32Bits(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	// A million dollars is 10^10 units of USD, which needs more than 32
+	// bits.
+	r.Add(sales.Record{Product: "Licence", Quantity: 3, Price: sales.NewUSD(1_000_000)})
+	total := r.TotalRevenue()
+	if got, want := total.String(), "3000000.00"; got != want {
+		t.Errorf("want %s, got %s", want, got)
+	}
+	if got, want := total.Cents(), int64(300_000_000); got != want {
+		t.Errorf("want %d cents, got %d", want, got)
+	}
+	if got, want := sales.DefaultLocale.Format(total), "$3,000,000.00"; got != want {
+		t.Errorf("want %s, got %s", want, got)
+	}
+	data, err := json.Marshal(total)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got, want := string(data), `{"cents":300000000,"formatted":"3000000.00"}`; got != want {
+		t.Errorf("want %s, got %s", want, got)
+	}
+	got, err := sales.ParseUSD("3000000.00")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got != total {
+		t.Errorf("want %v parsed, got %v", total, got)
+	}
+}
@@ -0,0 +1,93 @@
+package sales
+
+import (
+	"encoding/csv"
+	"io"
+	"strconv"
+	"time"
+)
+
+// NormalizedColumns are the columns of the canonical CSV format written by
+// WriteNormalizedCSV. ReadCSV recognises files in this format by their
+// columns, so they can be read back without any configuration.
+var NormalizedColumns = Columns{
+	Created:  "date",
+	OrderID:  "order_id",
+	SKU:      "sku",
+	Name:     "name",
+	Quantity: "qty",
+	Price:    "price",
+	Currency: "currency",
+	Fee:      "fee",
+	Tax:      "tax",
+}
+
+// normalizedHeader is the header row of the canonical CSV format.
+var normalizedHeader = []string{"date", "order_id", "sku", "name", "qty", "price", "currency", "fee", "tax"}
+
+// isNormalized reports whether an input with the columns col is in the
+// canonical CSV format.
+func isNormalized(col map[string]int) bool {
+	for _, name := range normalizedHeader {
+		if _, ok := col[name]; !ok {
+			return false
+		}
+	}
+	return true
+}
+
+// WriteNormalizedCSV writes the line items kept by KeepRecords as CSV in a
+// canonical format, whatever platform they came from: a row for each line
+// item, with the columns date (in RFC 3339 format), order_id, sku, name,
+// qty, price, currency, fee, and tax. Amounts are decimal numbers of
+// dollars. Records without a currency are given the report's Currency,
+// or USD.
+func (r *Report) WriteNormalizedCSV(w io.Writer) error {
+	r.logRender("normalized")
+	cw := csv.NewWriter(w)
+	cw.Write(normalizedHeader)
+	err := r.EachRecord(func(rec Record) error {
+		return cw.Write(r.normalizedRow(rec))
+	})
+	if err != nil {
+		return err
+	}
+	cw.Flush()
+	return cw.Error()
+}
+
+// normalizedRow returns the fields of rec in the canonical CSV format.
+func (r *Report) normalizedRow(rec Record) []string {
+	var date string
+	if !rec.Time.IsZero() {
+		date = rec.Time.Format(time.RFC3339)
+	}
+	return []string{
+		date,
+		rec.OrderID,
+		rec.SKU,
+		rec.Product,
+		strconv.Itoa(rec.Quantity),
+		rec.Price.String(),
+		r.currencyOf(rec),
+		rec.Fee.String(),
+		rec.Tax.String(),
+	}
+}
+
+// currencyOf returns the currency of rec: its own, if the export gave one,
+// or else the report's Currency, or USD.
+func (r *Report) currencyOf(rec Record) string {
+	if rec.Currency != "" {
+		return rec.Currency
+	}
+	return r.reportCurrency()
+}
+
+// reportCurrency returns the report's Currency, or USD if it's not set.
+func (r *Report) reportCurrency() string {
+	if r.Currency != "" {
+		return r.Currency
+	}
+	return "USD"
+}
@@ -0,0 +1,96 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+)
+
+// The cumulative shares of revenue at which the A and B tiers of a Pareto
+// analysis end: the products making the first 80% of revenue are A, the
+// next 15% B, and the rest C.
+const (
+	paretoA = 0.80
+	paretoB = 0.95
+)
+
+// ParetoRow is a product's place in a Pareto analysis.
+type ParetoRow struct {
+	Product string
+	Revenue USD
+	// Share is the product's fraction of the total revenue, and
+	// Cumulative that of the products up to and including it.
+	Share      float64
+	Cumulative float64
+	// Class is the product's revenue tier: "A", "B", or "C".
+	Class string
+}
+
+// Pareto is an ABC analysis of the products in a report, largest revenue
+// first.
+type Pareto struct {
+	Rows []ParetoRow
+	// TopFifth is the number of products in the top 20%, rounded up, and
+	// TopFifthShare their fraction of the total revenue.
+	TopFifth      int
+	TopFifthShare float64
+}
+
+// Pareto ranks the report rows by revenue and classifies them into tiers:
+// A for those making up the first 80% of revenue, B for the next 15%, and
+// C for the rest. The product that takes the cumulative share past a
+// tier's limit is counted in that tier. It also gives the share of revenue
+// from the top 20% of products, which by the Pareto principle is often
+// around 80%.
+func (r *Report) Pareto() Pareto {
+	products := r.sortedProducts(SortByRevenue)
+	total := r.TotalRevenue()
+	var p Pareto
+	p.TopFifth = (len(products) + 4) / 5
+	var cumulative USD
+	for i, prod := range products {
+		row := ParetoRow{Product: prod.Name, Revenue: prod.Revenue, Class: "C"}
+		if total != 0 {
+			row.Share = float64(prod.Revenue) / float64(total)
+			switch before := float64(cumulative) / float64(total); {
+			case before < paretoA:
+				row.Class = "A"
+			case before < paretoB:
+				row.Class = "B"
+			}
+		}
+		cumulative += prod.Revenue
+		if total != 0 {
+			row.Cumulative = float64(cumulative) / float64(total)
+		}
+		if i < p.TopFifth {
+			p.TopFifthShare = row.Cumulative
+		}
+		p.Rows = append(p.Rows, row)
+	}
+	return p
+}
+
+// WritePareto writes the Pareto analysis as a text table, followed by the
+// number of products in each tier and the share of revenue from the top
+// 20%.
+func (r *Report) WritePareto(w io.Writer) error {
+	p := r.Pareto()
+	t := &table{
+		header: []string{"Product", "Revenue", "Share", "Cumulative", "Class"},
+		right:  []bool{false, true, true, true, false},
+		color:  r.Color,
+	}
+	counts := map[string]int{}
+	for _, row := range p.Rows {
+		t.add(row.Product, row.Revenue.String(), fmt.Sprintf("%.1f%%", row.Share*100), fmt.Sprintf("%.1f%%", row.Cumulative*100), row.Class)
+		counts[row.Class]++
+	}
+	t.footer = []string{"Total", r.TotalRevenue().String(), "", "", ""}
+	if err := t.write(w); err != nil {
+		return err
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintf(w, "Classes: %d A, %d B, %d C\n", counts["A"], counts["B"], counts["C"])
+	_, err := fmt.Fprintf(w, "Top 20%% of products (%d) made %.1f%% of revenue\n", p.TopFifth, p.TopFifthShare*100)
+	return err
+}
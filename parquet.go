@@ -0,0 +1,884 @@
+package sales
+
+import (
+	"bytes"
+	"compress/gzip"
+	"encoding/binary"
+	"encoding/csv"
+	"errors"
+	"fmt"
+	"io"
+	"math"
+	"math/big"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Parquet physical types.
+const (
+	parquetBoolean   = 0
+	parquetInt32     = 1
+	parquetInt64     = 2
+	parquetInt96     = 3
+	parquetFloat     = 4
+	parquetDouble    = 5
+	parquetByteArray = 6
+	parquetFixed     = 7
+)
+
+// Parquet page types.
+const (
+	parquetDataPage       = 0
+	parquetDictionaryPage = 2
+	parquetDataPageV2     = 3
+)
+
+// parquetEncodings names the Parquet encodings, for error messages.
+var parquetEncodings = map[int64]string{
+	0: "PLAIN",
+	2: "PLAIN_DICTIONARY",
+	3: "RLE",
+	4: "DELTA_BINARY_PACKED",
+	5: "DELTA_LENGTH_BYTE_ARRAY",
+	6: "DELTA_BYTE_ARRAY",
+	8: "RLE_DICTIONARY",
+	9: "BYTE_STREAM_SPLIT",
+}
+
+// parquetCodecs names the Parquet compression codecs, for error messages.
+var parquetCodecs = map[int64]string{
+	3: "LZO",
+	4: "Brotli",
+	5: "LZ4",
+	6: "Zstandard",
+	7: "LZ4",
+}
+
+// WriteParquet writes the line items kept by KeepRecords as a Parquet file,
+// for loading into tools such as DuckDB or pandas. The columns are those of
+// WriteNormalizedCSV: date, a timestamp in milliseconds that is null for
+// records without one, order_id, sku, name, qty, price, currency, fee, and
+// tax. Amounts are exact decimals, with four decimal places. ReadParquet
+// reads the file back as it would the normalized CSV.
+//
+// The file has a single row group, stored uncompressed with the plain
+// encoding, which every Parquet reader supports.
+func (r *Report) WriteParquet(w io.Writer) error {
+	r.logRender("parquet")
+	cols := []parquetColumn{
+		{name: "date", kind: parquetTime},
+		{name: "order_id", kind: parquetString},
+		{name: "sku", kind: parquetString},
+		{name: "name", kind: parquetString},
+		{name: "qty", kind: parquetInt},
+		{name: "price", kind: parquetMoney},
+		{name: "currency", kind: parquetString},
+		{name: "fee", kind: parquetMoney},
+		{name: "tax", kind: parquetMoney},
+	}
+	records, err := r.Records()
+	if err != nil {
+		return err
+	}
+	for _, rec := range records {
+		row := []any{rec.Time, rec.OrderID, rec.SKU, rec.Product, rec.Quantity, rec.Price, r.currencyOf(rec), rec.Fee, rec.Tax}
+		for i, v := range row {
+			cols[i].values = append(cols[i].values, v)
+		}
+	}
+	return writeParquet(w, len(records), cols)
+}
+
+// WriteParquetSummary writes the units and revenue of each product, in the
+// order given by Sort, as a Parquet file with the columns Product, Units,
+// and Revenue. Like a summary written by WriteCSV, ReadParquet reads it
+// back as a pre-aggregated summary.
+func (r *Report) WriteParquetSummary(w io.Writer) error {
+	r.logRender("parquet")
+	cols := []parquetColumn{
+		{name: "Product", kind: parquetString},
+		{name: "Units", kind: parquetInt},
+		{name: "Revenue", kind: parquetMoney},
+	}
+	products := r.Products()
+	for _, p := range products {
+		cols[0].values = append(cols[0].values, p.Name)
+		cols[1].values = append(cols[1].values, p.Units)
+		cols[2].values = append(cols[2].values, p.Revenue)
+	}
+	return writeParquet(w, len(products), cols)
+}
+
+// parquetKind is the kind of values in a column written by writeParquet.
+type parquetKind int
+
+const (
+	parquetString parquetKind = iota
+	parquetInt
+	parquetMoney
+	parquetTime
+)
+
+// parquetColumn is a column to be written by writeParquet. Its values are
+// strings, ints, USD amounts, or times, according to its kind. Times are
+// optional, and a zero time is written as null.
+type parquetColumn struct {
+	name   string
+	kind   parquetKind
+	values []any
+}
+
+// physical returns the column's physical type.
+func (c parquetColumn) physical() int32 {
+	if c.kind == parquetString {
+		return parquetByteArray
+	}
+	return parquetInt64
+}
+
+// schema writes the column's schema element to w.
+func (c parquetColumn) schema(w *thriftWriter) {
+	w.structElem()
+	w.i32(1, c.physical())
+	repetition := int32(0)
+	if c.kind == parquetTime {
+		repetition = 1
+	}
+	w.i32(3, repetition)
+	w.str(4, c.name)
+	switch c.kind {
+	case parquetString:
+		w.i32(6, 0) // UTF8
+	case parquetMoney:
+		w.i32(6, 5) // DECIMAL
+		w.i32(7, 4)
+		w.i32(8, 18)
+	case parquetTime:
+		w.i32(6, 9) // TIMESTAMP_MILLIS
+	}
+	w.end()
+}
+
+// page returns the column's values as a data page, in the plain encoding,
+// after the definition levels of an optional column.
+func (c parquetColumn) page() []byte {
+	var b []byte
+	if c.kind == parquetTime {
+		// The levels are a single bit-packed run, with a bit set for
+		// each value that isn't null.
+		bits := make([]byte, (len(c.values)+7)/8)
+		for i, v := range c.values {
+			if !v.(time.Time).IsZero() {
+				bits[i/8] |= 1 << (i % 8)
+			}
+		}
+		levels := binary.AppendUvarint(nil, uint64(len(bits))<<1|1)
+		levels = append(levels, bits...)
+		b = binary.LittleEndian.AppendUint32(b, uint32(len(levels)))
+		b = append(b, levels...)
+	}
+	for _, v := range c.values {
+		switch v := v.(type) {
+		case string:
+			b = binary.LittleEndian.AppendUint32(b, uint32(len(v)))
+			b = append(b, v...)
+		case int:
+			b = binary.LittleEndian.AppendUint64(b, uint64(v))
+		case USD:
+			b = binary.LittleEndian.AppendUint64(b, uint64(v))
+		case time.Time:
+			if !v.IsZero() {
+				b = binary.LittleEndian.AppendUint64(b, uint64(v.UnixMilli()))
+			}
+		}
+	}
+	return b
+}
+
+// writeParquet writes a Parquet file of rows rows, with the columns cols,
+// to w.
+func writeParquet(w io.Writer, rows int, cols []parquetColumn) error {
+	data := []byte("PAR1")
+	meta := newThriftWriter()
+	meta.structElem()
+	meta.i32(1, 1) // version
+	meta.list(2, thriftStruct, len(cols)+1)
+	meta.structElem()
+	meta.str(4, "schema")
+	meta.i32(5, int32(len(cols)))
+	meta.end()
+	for _, c := range cols {
+		c.schema(meta)
+	}
+	meta.i64(3, int64(rows))
+	meta.list(4, thriftStruct, 1)
+	meta.structElem()
+	meta.list(1, thriftStruct, len(cols))
+	start := len(data)
+	for _, c := range cols {
+		page := c.page()
+		h := newThriftWriter()
+		h.structElem()
+		h.i32(1, parquetDataPage)
+		h.i32(2, int32(len(page)))
+		h.i32(3, int32(len(page)))
+		h.structField(5)
+		h.i32(1, int32(len(c.values)))
+		h.i32(2, 0) // PLAIN
+		h.i32(3, 3) // RLE
+		h.i32(4, 3)
+		h.end()
+		h.end()
+		offset := int64(len(data))
+		data = append(data, h.buf...)
+		data = append(data, page...)
+		size := int64(len(data)) - offset
+		meta.structElem()
+		meta.i64(2, offset)
+		meta.structField(3)
+		meta.i32(1, c.physical())
+		meta.list(2, thriftI32, 2)
+		meta.i32Elem(0)
+		meta.i32Elem(3)
+		meta.list(3, thriftBinary, 1)
+		meta.stringElem(c.name)
+		meta.i32(4, 0) // uncompressed
+		meta.i64(5, int64(len(c.values)))
+		meta.i64(6, size)
+		meta.i64(7, size)
+		meta.i64(9, offset)
+		meta.end()
+		meta.end()
+	}
+	meta.i64(2, int64(len(data)-start))
+	meta.i64(3, int64(rows))
+	meta.end()
+	meta.str(6, "github.com/bitfield/sales")
+	meta.end()
+	data = append(data, meta.buf...)
+	data = binary.LittleEndian.AppendUint32(data, uint32(len(meta.buf)))
+	data = append(data, "PAR1"...)
+	_, err := w.Write(data)
+	return err
+}
+
+// ReadParquet reads a Parquet file from rd, such as one exported from
+// DuckDB or pandas, or written by WriteParquet. Its columns are found by
+// the names in Columns, just as ReadCSV finds them in a CSV header, and a
+// file with Product, Units, and Revenue columns is read as a
+// pre-aggregated summary. Values are converted to text as a CSV export
+// would have them: decimals exactly, timestamps in RFC 3339 format, and
+// dates as YYYY-MM-DD. Nulls are empty. Errors in a row give its line
+// number as if the file were CSV, counting the header as line 1.
+//
+// Only flat schemas, without nested or repeated columns, can be read. Pages
+// may be compressed with Snappy or gzip, or not at all, and encoded with
+// the plain or dictionary encodings, which are what DuckDB, pandas, and
+// Spark write by default.
+func (r *Report) ReadParquet(rd io.Reader, source string) error {
+	if r.Limits.MaxFileSize > 0 {
+		rd = &limitedReader{r: rd, limit: r.Limits.MaxFileSize}
+	}
+	data, err := io.ReadAll(rd)
+	if err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	if len(data) == 0 {
+		return nil
+	}
+	header, rows, err := parquetRows(data, r.Limits.MaxRows)
+	if err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	// The rows go through the CSV reader, so that they're mapped to
+	// records exactly as the same columns in a CSV export would be.
+	var buf bytes.Buffer
+	cw := csv.NewWriter(&buf)
+	cw.Write(header)
+	if err := cw.WriteAll(rows); err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	cr := csv.NewReader(&buf)
+	if _, err := cr.Read(); err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	return r.readTable(cr, header, source, r.SkipBadRows)
+}
+
+var errParquetShort = errors.New("data ends unexpectedly")
+
+// maxDecimalScale is the most digits after the point that a decimal column
+// may have, which is the most precision Parquet writers support.
+const maxDecimalScale = 38
+
+// pageCount returns the number of values v given in a row group or page
+// header, checking that it's neither negative nor more than max, the most
+// the data could hold.
+func pageCount(v int64, max int) (int, error) {
+	if v < 0 || v > int64(max) {
+		return 0, errThrift
+	}
+	return int(v), nil
+}
+
+// parquetField describes a column of a Parquet file being read, and how
+// to convert its values to text.
+type parquetField struct {
+	name     string
+	physical int64
+	// length is the size of each value of a fixed-length byte array.
+	length   int
+	optional bool
+	decimal  bool
+	scale    int
+	date     bool
+	// unit is the unit of a timestamp, or zero for other columns.
+	unit time.Duration
+}
+
+// parquetRows returns the column names and rows of the Parquet file data,
+// with each value as text. If maxRows is positive, a file with more rows
+// than that is rejected before any are read.
+func parquetRows(data []byte, maxRows int64) ([]string, [][]string, error) {
+	if len(data) < 12 || string(data[:4]) != "PAR1" || string(data[len(data)-4:]) != "PAR1" {
+		return nil, nil, errors.New("not a Parquet file")
+	}
+	n := int(binary.LittleEndian.Uint32(data[len(data)-8:]))
+	if n > len(data)-12 {
+		return nil, nil, errors.New("invalid file metadata length")
+	}
+	tr := &thriftReader{buf: data[len(data)-8-n : len(data)-8]}
+	meta := tr.structure()
+	if tr.err != nil {
+		return nil, nil, fmt.Errorf("file metadata: %w", tr.err)
+	}
+	schema := meta.list(2)
+	if len(schema) == 0 {
+		return nil, nil, errors.New("file metadata has no schema")
+	}
+	var fields []parquetField
+	var header []string
+	for _, v := range schema[1:] {
+		el, _ := v.(thriftFields)
+		f := newParquetField(el)
+		if el.i64(5) > 0 || el.i64(3) == 2 {
+			return nil, nil, fmt.Errorf("column %s is nested or repeated, which isn't supported", f.name)
+		}
+		if f.scale < 0 || f.scale > maxDecimalScale {
+			return nil, nil, fmt.Errorf("column %s has invalid decimal scale %d", f.name, f.scale)
+		}
+		fields = append(fields, f)
+		header = append(header, f.name)
+	}
+	var rows [][]string
+	for _, v := range meta.list(4) {
+		rg, _ := v.(thriftFields)
+		n, err := pageCount(rg.i64(3), math.MaxInt)
+		if err != nil {
+			return nil, nil, fmt.Errorf("row count: %w", err)
+		}
+		if maxRows > 0 && int64(len(rows))+int64(n) > maxRows {
+			return nil, nil, &LimitError{Limit: "max_rows", Max: maxRows}
+		}
+		chunks := rg.list(1)
+		if len(chunks) != len(fields) {
+			return nil, nil, fmt.Errorf("row group has %d columns, but the schema has %d", len(chunks), len(fields))
+		}
+		columns := make([][]string, len(fields))
+		for i, c := range chunks {
+			cc, _ := c.(thriftFields)
+			values, err := fields[i].readChunk(data, cc.fields(3), n)
+			if err != nil {
+				return nil, nil, fmt.Errorf("column %s: %w", fields[i].name, err)
+			}
+			columns[i] = values
+		}
+		for row := 0; row < n; row++ {
+			record := make([]string, len(fields))
+			for i := range fields {
+				record[i] = columns[i][row]
+			}
+			rows = append(rows, record)
+		}
+	}
+	return header, rows, nil
+}
+
+// newParquetField returns the field described by the schema element el,
+// from either its converted type or its logical type.
+func newParquetField(el thriftFields) parquetField {
+	f := parquetField{
+		name:     string(el.bytes(4)),
+		physical: el.i64(1),
+		length:   int(el.i64(2)),
+		optional: el.i64(3) == 1,
+	}
+	switch el.i64(6) {
+	case 5:
+		f.decimal, f.scale = true, int(el.i64(7))
+	case 6:
+		f.date = true
+	case 9:
+		f.unit = time.Millisecond
+	case 10:
+		f.unit = time.Microsecond
+	}
+	logical := el.fields(10)
+	switch {
+	case logical.has(5):
+		f.decimal, f.scale = true, int(logical.fields(5).i64(1))
+	case logical.has(6):
+		f.date = true
+	case logical.has(8):
+		unit := logical.fields(8).fields(2)
+		switch {
+		case unit.has(1):
+			f.unit = time.Millisecond
+		case unit.has(2):
+			f.unit = time.Microsecond
+		case unit.has(3):
+			f.unit = time.Nanosecond
+		}
+	}
+	if f.physical == parquetInt96 {
+		f.unit = time.Nanosecond
+	}
+	return f
+}
+
+// readChunk returns the n values in the column chunk described by md.
+func (f parquetField) readChunk(data []byte, md thriftFields, n int) ([]string, error) {
+	codec := md.i64(4)
+	start := md.i64(9)
+	if dict := md.i64(11); dict > 0 && dict < start {
+		start = dict
+	}
+	size := md.i64(7)
+	if start < 0 || size < 0 || size > int64(len(data))-start {
+		return nil, errParquetShort
+	}
+	end := start + size
+	var dict, values []string
+	for pos := int(start); len(values) < n; {
+		if pos >= int(end) {
+			return nil, fmt.Errorf("%d values, but the row group has %d rows", len(values), n)
+		}
+		tr := &thriftReader{buf: data[:end], pos: pos}
+		h := tr.structure()
+		if tr.err != nil {
+			return nil, fmt.Errorf("page header: %w", tr.err)
+		}
+		size := int(h.i64(3))
+		if size < 0 || size > int(end)-tr.pos {
+			return nil, errParquetShort
+		}
+		body := data[tr.pos : tr.pos+size]
+		pos = tr.pos + size
+		switch h.i64(1) {
+		case parquetDictionaryPage:
+			page, err := decompress(codec, body)
+			if err != nil {
+				return nil, err
+			}
+			// A value takes at least a bit, as booleans do.
+			count, err := pageCount(h.fields(7).i64(1), len(page)*8)
+			if err != nil {
+				return nil, err
+			}
+			if dict, err = f.plain(page, count); err != nil {
+				return nil, err
+			}
+		case parquetDataPage:
+			dh := h.fields(5)
+			page, err := decompress(codec, body)
+			if err != nil {
+				return nil, err
+			}
+			count, err := pageCount(dh.i64(1), n-len(values))
+			if err != nil {
+				return nil, err
+			}
+			var defs []int
+			if f.optional {
+				if len(page) < 4 {
+					return nil, errParquetShort
+				}
+				size := int64(binary.LittleEndian.Uint32(page))
+				if size > int64(len(page)-4) {
+					return nil, errParquetShort
+				}
+				if defs, err = decodeHybrid(page[4:4+size], 1, count); err != nil {
+					return nil, err
+				}
+				page = page[4+size:]
+			}
+			vals, err := f.decode(page, dh.i64(2), defined(defs, count), dict)
+			if err != nil {
+				return nil, err
+			}
+			values = appendWithNulls(values, vals, defs)
+		case parquetDataPageV2:
+			dh := h.fields(8)
+			count, err := pageCount(dh.i64(1), n-len(values))
+			if err != nil {
+				return nil, err
+			}
+			defSize, repSize := dh.i64(5), dh.i64(6)
+			if defSize < 0 || repSize < 0 || defSize > int64(len(body)) || repSize > int64(len(body))-defSize {
+				return nil, errParquetShort
+			}
+			var defs []int
+			if f.optional {
+				if defs, err = decodeHybrid(body[repSize:repSize+defSize], 1, count); err != nil {
+					return nil, err
+				}
+			}
+			page := body[repSize+defSize:]
+			if compressed, ok := dh.boolean(7); compressed || !ok {
+				if page, err = decompress(codec, page); err != nil {
+					return nil, err
+				}
+			}
+			vals, err := f.decode(page, dh.i64(4), defined(defs, count), dict)
+			if err != nil {
+				return nil, err
+			}
+			values = appendWithNulls(values, vals, defs)
+		}
+	}
+	return values[:n], nil
+}
+
+// defined returns the number of values that aren't null, given the
+// definition levels defs of count values, or nil if the column is
+// required.
+func defined(defs []int, count int) int {
+	if defs == nil {
+		return count
+	}
+	n := 0
+	for _, d := range defs {
+		n += d
+	}
+	return n
+}
+
+// appendWithNulls appends vals to values, with an empty string for each
+// null, as given by the definition levels defs.
+func appendWithNulls(values, vals []string, defs []int) []string {
+	if defs == nil {
+		return append(values, vals...)
+	}
+	for _, d := range defs {
+		if d == 0 || len(vals) == 0 {
+			values = append(values, "")
+			continue
+		}
+		values = append(values, vals[0])
+		vals = vals[1:]
+	}
+	return values
+}
+
+// decode returns the n values in page, which has the given encoding, using
+// dict for dictionary-encoded values.
+func (f parquetField) decode(page []byte, encoding int64, n int, dict []string) ([]string, error) {
+	switch encoding {
+	case 0:
+		return f.plain(page, n)
+	case 2, 8:
+		if n == 0 {
+			return nil, nil
+		}
+		if len(page) == 0 {
+			return nil, errParquetShort
+		}
+		indexes, err := decodeHybrid(page[1:], int(page[0]), n)
+		if err != nil {
+			return nil, err
+		}
+		values := make([]string, n)
+		for i, x := range indexes {
+			if x >= len(dict) {
+				return nil, errors.New("dictionary index out of range")
+			}
+			values[i] = dict[x]
+		}
+		return values, nil
+	}
+	name, ok := parquetEncodings[encoding]
+	if !ok {
+		name = strconv.FormatInt(encoding, 10)
+	}
+	return nil, fmt.Errorf("%s encoding isn't supported", name)
+}
+
+// plain returns the n values in b, which has the plain encoding, as text.
+func (f parquetField) plain(b []byte, n int) ([]string, error) {
+	size := map[int64]int{
+		parquetInt32:  4,
+		parquetInt64:  8,
+		parquetInt96:  12,
+		parquetFloat:  4,
+		parquetDouble: 8,
+		parquetFixed:  f.length,
+	}[f.physical]
+	if n < 0 {
+		return nil, errThrift
+	}
+	switch f.physical {
+	case parquetBoolean:
+		if len(b)*8 < n {
+			return nil, errParquetShort
+		}
+	case parquetByteArray:
+		// Each value has at least its four-byte length.
+		if len(b)/4 < n {
+			return nil, errParquetShort
+		}
+	case parquetFixed:
+		if size <= 0 {
+			return nil, errors.New("fixed-length byte array has no length")
+		}
+		fallthrough
+	default:
+		if size == 0 {
+			return nil, fmt.Errorf("unknown physical type %d", f.physical)
+		}
+		if len(b)/size < n {
+			return nil, errParquetShort
+		}
+	}
+	values := make([]string, n)
+	for i := range values {
+		switch f.physical {
+		case parquetBoolean:
+			values[i] = strconv.FormatBool(b[i/8]>>(i%8)&1 == 1)
+		case parquetInt32:
+			values[i] = f.formatInt(int64(int32(binary.LittleEndian.Uint32(b[i*4:]))))
+		case parquetInt64:
+			values[i] = f.formatInt(int64(binary.LittleEndian.Uint64(b[i*8:])))
+		case parquetInt96:
+			// A legacy timestamp: nanoseconds into the day, then the
+			// Julian day number.
+			nanos := int64(binary.LittleEndian.Uint64(b[i*12:]))
+			day := int64(binary.LittleEndian.Uint32(b[i*12+8:]))
+			values[i] = time.Unix((day-2440588)*86400, nanos).UTC().Format(time.RFC3339)
+		case parquetFloat:
+			v := math.Float32frombits(binary.LittleEndian.Uint32(b[i*4:]))
+			values[i] = strconv.FormatFloat(float64(v), 'f', -1, 32)
+		case parquetDouble:
+			v := math.Float64frombits(binary.LittleEndian.Uint64(b[i*8:]))
+			values[i] = strconv.FormatFloat(v, 'f', -1, 64)
+		case parquetByteArray:
+			if len(b) < 4 {
+				return nil, errParquetShort
+			}
+			size := binary.LittleEndian.Uint32(b)
+			if uint64(size) > uint64(len(b)-4) {
+				return nil, errParquetShort
+			}
+			values[i] = f.formatBytes(b[4 : 4+size])
+			b = b[4+size:]
+		case parquetFixed:
+			values[i] = f.formatBytes(b[i*size : (i+1)*size])
+		}
+	}
+	return values, nil
+}
+
+// formatInt returns the integer v, as stored in the column, as text.
+func (f parquetField) formatInt(v int64) string {
+	switch {
+	case f.decimal:
+		return formatDecimal(big.NewInt(v), f.scale)
+	case f.date:
+		return time.Unix(v*86400, 0).UTC().Format(time.DateOnly)
+	case f.unit == time.Millisecond:
+		return time.UnixMilli(v).UTC().Format(time.RFC3339)
+	case f.unit == time.Microsecond:
+		return time.UnixMicro(v).UTC().Format(time.RFC3339)
+	case f.unit == time.Nanosecond:
+		return time.Unix(0, v).UTC().Format(time.RFC3339)
+	}
+	return strconv.FormatInt(v, 10)
+}
+
+// formatBytes returns the byte array b, as stored in the column, as text.
+// Decimals are big-endian two's complement integers.
+func (f parquetField) formatBytes(b []byte) string {
+	if !f.decimal {
+		return string(b)
+	}
+	v := new(big.Int).SetBytes(b)
+	if len(b) > 0 && b[0]&0x80 != 0 {
+		v.Sub(v, new(big.Int).Lsh(big.NewInt(1), uint(len(b)*8)))
+	}
+	return formatDecimal(v, f.scale)
+}
+
+// formatDecimal returns the decimal with unscaled value v and scale digits
+// after the point, such as "-12.3400".
+func formatDecimal(v *big.Int, scale int) string {
+	digits := new(big.Int).Abs(v).String()
+	if scale <= 0 {
+		return v.String()
+	}
+	if len(digits) <= scale {
+		digits = strings.Repeat("0", scale-len(digits)+1) + digits
+	}
+	s := digits[:len(digits)-scale] + "." + digits[len(digits)-scale:]
+	if v.Sign() < 0 {
+		return "-" + s
+	}
+	return s
+}
+
+// decodeHybrid returns n values of the given bit width from b, which uses
+// the Parquet hybrid of run-length encoding and bit packing.
+func decodeHybrid(b []byte, width, n int) ([]int, error) {
+	if width < 0 || width > 32 {
+		return nil, fmt.Errorf("invalid bit width %d", width)
+	}
+	if n < 0 {
+		return nil, errThrift
+	}
+	values := make([]int, 0, min(n, len(b)*8))
+	for len(values) < n {
+		h, k := binary.Uvarint(b)
+		if k <= 0 {
+			return nil, errParquetShort
+		}
+		b = b[k:]
+		if h&1 == 0 {
+			// A run of a single value, stored in whole bytes.
+			size := (width + 7) / 8
+			if len(b) < size {
+				return nil, errParquetShort
+			}
+			v := 0
+			for i := 0; i < size; i++ {
+				v |= int(b[i]) << (8 * i)
+			}
+			b = b[size:]
+			for i := uint64(0); i < h>>1 && len(values) < n; i++ {
+				values = append(values, v)
+			}
+			continue
+		}
+		// Groups of eight values, packed from the least significant
+		// bit.
+		groups := h >> 1
+		if groups > uint64(len(b)) {
+			return nil, errParquetShort
+		}
+		size := int(groups) * width
+		if len(b) < size {
+			return nil, errParquetShort
+		}
+		for i := 0; i < int(groups)*8 && len(values) < n; i++ {
+			v := 0
+			for j := 0; j < width; j++ {
+				bit := i*width + j
+				v |= int(b[bit/8]>>(bit%8)&1) << j
+			}
+			values = append(values, v)
+		}
+		b = b[size:]
+	}
+	return values, nil
+}
+
+// decompress returns the page data b decompressed with codec.
+func decompress(codec int64, b []byte) ([]byte, error) {
+	switch codec {
+	case 0:
+		return b, nil
+	case 1:
+		return decodeSnappy(b)
+	case 2:
+		zr, err := gzip.NewReader(bytes.NewReader(b))
+		if err != nil {
+			return nil, err
+		}
+		return io.ReadAll(zr)
+	}
+	name, ok := parquetCodecs[codec]
+	if !ok {
+		name = strconv.FormatInt(codec, 10)
+	}
+	return nil, fmt.Errorf("%s compression isn't supported; write the file with Snappy, gzip, or no compression", name)
+}
+
+// decodeSnappy decompresses a block in the Snappy format, which Parquet
+// files are compressed with by default.
+func decodeSnappy(src []byte) ([]byte, error) {
+	n, k := binary.Uvarint(src)
+	if k <= 0 || n > uint64(len(src))*255 {
+		return nil, errors.New("invalid Snappy data")
+	}
+	src = src[k:]
+	dst := make([]byte, 0, n)
+	for len(src) > 0 {
+		tag := src[0]
+		var length, offset int
+		switch tag & 3 {
+		case 0:
+			// A literal, whose length is in the tag or the bytes after
+			// it.
+			length = int(tag >> 2)
+			src = src[1:]
+			if length >= 60 {
+				extra := length - 59
+				if len(src) < extra {
+					return nil, errParquetShort
+				}
+				length = 0
+				for i := 0; i < extra; i++ {
+					length |= int(src[i]) << (8 * i)
+				}
+				src = src[extra:]
+			}
+			length++
+			if length <= 0 || len(src) < length {
+				return nil, errParquetShort
+			}
+			dst = append(dst, src[:length]...)
+			src = src[length:]
+			continue
+		case 1:
+			if len(src) < 2 {
+				return nil, errParquetShort
+			}
+			length = 4 + int(tag>>2)&7
+			offset = int(tag&0xE0)<<3 | int(src[1])
+			src = src[2:]
+		case 2:
+			if len(src) < 3 {
+				return nil, errParquetShort
+			}
+			length = 1 + int(tag>>2)
+			offset = int(binary.LittleEndian.Uint16(src[1:]))
+			src = src[3:]
+		case 3:
+			if len(src) < 5 {
+				return nil, errParquetShort
+			}
+			length = 1 + int(tag>>2)
+			offset = int(binary.LittleEndian.Uint32(src[1:]))
+			src = src[5:]
+		}
+		if offset <= 0 || offset > len(dst) {
+			return nil, errors.New("invalid Snappy data")
+		}
+		// A copy can overlap what it's copying, so go byte by byte.
+		for i := 0; i < length; i++ {
+			dst = append(dst, dst[len(dst)-offset])
+		}
+	}
+	if uint64(len(dst)) != n {
+		return nil, errors.New("invalid Snappy data")
+	}
+	return dst, nil
+}
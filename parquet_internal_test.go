@@ -0,0 +1,96 @@
+package sales
+
+import (
+	"bytes"
+	"errors"
+	"testing"
+)
+
+func TestDecodeSnappyExpandsLiteralsAndCopies(t *testing.T) {
+	t.Parallel()
+	tcs := []struct {
+		name  string
+		input []byte
+		want  string
+	}{
+		{"empty", []byte{0}, ""},
+		{"literal", []byte{5, 4 << 2, 'h', 'e', 'l', 'l', 'o'}, "hello"},
+		{"copy", []byte{8, 3 << 2, 'a', 'b', 'c', 'd', 0<<2 | 1, 4}, "abcdabcd"},
+		{"overlapping copy", []byte{8, 0, 'a', 3<<2 | 1, 1}, "aaaaaaaa"},
+		{"two-byte offset", []byte{6, 2 << 2, 'x', 'y', 'z', 2<<2 | 2, 3, 0}, "xyzxyz"},
+		{"long literal", append([]byte{61, 60 << 2, 60}, bytes.Repeat([]byte("q"), 61)...), string(bytes.Repeat([]byte("q"), 61))},
+	}
+	for _, tc := range tcs {
+		got, err := decodeSnappy(tc.input)
+		if err != nil {
+			t.Errorf("%s: %v", tc.name, err)
+			continue
+		}
+		if string(got) != tc.want {
+			t.Errorf("%s: want %q, got %q", tc.name, tc.want, got)
+		}
+	}
+}
+
+func TestDecodeSnappyRejectsCorruptInput(t *testing.T) {
+	t.Parallel()
+	tcs := []struct {
+		name  string
+		input []byte
+	}{
+		{"no length", nil},
+		{"short literal", []byte{5, 4 << 2, 'h', 'e'}},
+		{"wrong length", []byte{6, 4 << 2, 'h', 'e', 'l', 'l', 'o'}},
+		{"copy before any data", []byte{4, 0<<2 | 1, 1}},
+		{"zero offset", []byte{8, 0, 'a', 3<<2 | 1, 0}},
+		{"offset past start", []byte{8, 0, 'a', 3<<2 | 1, 2}},
+		{"truncated copy", []byte{8, 0, 'a', 3<<2 | 2, 1}},
+		{"impossible length", []byte{0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0}},
+	}
+	for _, tc := range tcs {
+		if got, err := decodeSnappy(tc.input); err == nil {
+			t.Errorf("%s: want error, got %q", tc.name, got)
+		}
+	}
+}
+
+func TestReadChunkRejectsImpossibleValueCounts(t *testing.T) {
+	t.Parallel()
+	// The page types, and the IDs of their header fields in the page
+	// header.
+	pages := map[int32]int16{
+		parquetDictionaryPage: 7,
+		parquetDataPage:       5,
+		parquetDataPageV2:     8,
+	}
+	f := parquetField{name: "qty", physical: parquetInt64}
+	for typ, id := range pages {
+		for _, count := range []int64{-1, 1 << 40} {
+			h := newThriftWriter()
+			h.structElem()
+			h.i32(1, typ)
+			h.i32(3, 8)
+			h.structField(id)
+			h.i64(1, count)
+			h.end()
+			h.end()
+			data := append(h.buf, make([]byte, 8)...)
+			md := thriftFields{7: int64(len(data)), 9: int64(0)}
+			_, err := f.readChunk(data, md, 1)
+			if !errors.Is(err, errThrift) {
+				t.Errorf("page type %d with %d values: want errThrift, got %v", typ, count, err)
+			}
+		}
+	}
+}
+
+func TestPlainRejectsMoreValuesThanDataHolds(t *testing.T) {
+	t.Parallel()
+	f := parquetField{name: "name", physical: parquetByteArray}
+	if _, err := f.plain(make([]byte, 8), 3); !errors.Is(err, errParquetShort) {
+		t.Errorf("want errParquetShort, got %v", err)
+	}
+	if _, err := f.plain(nil, -1); !errors.Is(err, errThrift) {
+		t.Errorf("want errThrift, got %v", err)
+	}
+}
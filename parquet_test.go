@@ -0,0 +1,134 @@
+package sales_test
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/bitfield/sales"
+	"github.com/bitfield/sales/salestest"
+)
+
+// parquetReport returns a report of generated records, kept so that they
+// can be written as Parquet, with one record lacking a time, so that the
+// date column has a null.
+func parquetReport(t *testing.T) *sales.Report {
+	t.Helper()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	for _, rec := range salestest.Records(20, 1) {
+		r.Add(rec)
+	}
+	r.Add(sales.Record{OrderID: "undated", Product: "Gift card", Quantity: 1, Price: sales.NewUSD(25), Currency: "USD"})
+	return r
+}
+
+func TestWriteParquetReadsBackAsTheSameRecords(t *testing.T) {
+	t.Parallel()
+	r := parquetReport(t)
+	var file bytes.Buffer
+	if err := r.WriteParquet(&file); err != nil {
+		t.Fatal(err)
+	}
+	r2 := sales.NewReport()
+	r2.KeepRecords = true
+	if err := r2.ReadParquet(&file, "test.parquet"); err != nil {
+		t.Fatal(err)
+	}
+	var want, got strings.Builder
+	if err := r.WriteNormalizedCSV(&want); err != nil {
+		t.Fatal(err)
+	}
+	if err := r2.WriteNormalizedCSV(&got); err != nil {
+		t.Fatal(err)
+	}
+	if want.String() != got.String() {
+		t.Errorf("want records:\n%s\ngot:\n%s", want.String(), got.String())
+	}
+}
+
+func TestWriteParquetSummaryReadsBackAsTheSameProducts(t *testing.T) {
+	t.Parallel()
+	r := parquetReport(t)
+	var file bytes.Buffer
+	if err := r.WriteParquetSummary(&file); err != nil {
+		t.Fatal(err)
+	}
+	r2 := sales.NewReport()
+	if err := r2.ReadParquet(&file, "summary.parquet"); err != nil {
+		t.Fatal(err)
+	}
+	want, got := r.Products(), r2.Products()
+	if len(want) != len(got) {
+		t.Fatalf("want %d products, got %d", len(want), len(got))
+	}
+	for i := range want {
+		w, g := want[i], got[i]
+		if w.Name != g.Name || w.Units != g.Units || w.Revenue != g.Revenue {
+			t.Errorf("want %s: %d units, %v, got %s: %d units, %v", w.Name, w.Units, w.Revenue, g.Name, g.Units, g.Revenue)
+		}
+	}
+}
+
+func TestReadParquetRejectsTruncatedFiles(t *testing.T) {
+	t.Parallel()
+	var file bytes.Buffer
+	if err := parquetReport(t).WriteParquet(&file); err != nil {
+		t.Fatal(err)
+	}
+	data := file.Bytes()
+	for n := 1; n < len(data); n++ {
+		err := readParquetSafely(data[:n])
+		if err == nil {
+			t.Errorf("%d of %d bytes: want error, got nil", n, len(data))
+		}
+	}
+}
+
+func TestReadParquetSurvivesCorruptFiles(t *testing.T) {
+	t.Parallel()
+	var file bytes.Buffer
+	if err := parquetReport(t).WriteParquet(&file); err != nil {
+		t.Fatal(err)
+	}
+	data := file.Bytes()
+	// Corrupting a byte may leave a readable file, with different values,
+	// but it mustn't panic.
+	for i := range data {
+		for _, v := range []byte{0x00, 0x7F, 0xFF, data[i] ^ 0x10} {
+			corrupt := bytes.Clone(data)
+			corrupt[i] = v
+			if err := readParquetSafely(corrupt); err != nil && strings.HasPrefix(err.Error(), "panic") {
+				t.Fatalf("byte %d set to %#x: %v", i, v, err)
+			}
+		}
+	}
+}
+
+func TestReadParquetAppliesMaxRowsBeforeReading(t *testing.T) {
+	t.Parallel()
+	var file bytes.Buffer
+	if err := parquetReport(t).WriteParquet(&file); err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.Limits.MaxRows = 5
+	err := r.ReadParquet(&file, "test.parquet")
+	var limit *sales.LimitError
+	if !errors.As(err, &limit) || limit.Limit != "max_rows" {
+		t.Errorf("want max_rows limit error, got %v", err)
+	}
+}
+
+// readParquetSafely reads data as a Parquet file, returning any panic as an
+// error.
+func readParquetSafely(data []byte) (err error) {
+	defer func() {
+		if p := recover(); p != nil {
+			err = fmt.Errorf("panic: %v", p)
+		}
+	}()
+	return sales.NewReport().ReadParquet(bytes.NewReader(data), "corrupt.parquet")
+}
@@ -0,0 +1,224 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// PeriodUnit is the length of the periods that sales are grouped into over
+// time.
+type PeriodUnit int
+
+const (
+	Monthly PeriodUnit = iota
+	Weekly
+	Quarterly
+	Yearly
+)
+
+// ParsePeriodUnit returns the unit named by s: "week", "month", "quarter",
+// or "year".
+func ParsePeriodUnit(s string) (PeriodUnit, error) {
+	switch s {
+	case "week":
+		return Weekly, nil
+	case "month":
+		return Monthly, nil
+	case "quarter":
+		return Quarterly, nil
+	case "year":
+		return Yearly, nil
+	}
+	return Monthly, fmt.Errorf("unknown period %q (want week, month, quarter, or year)", s)
+}
+
+// Period says how to group sales over time, for ByPeriod, tidy output, and
+// conflict resolution. The zero value groups them by calendar month.
+type Period struct {
+	Unit PeriodUnit
+	// YearStart is the first month of the financial year, which
+	// quarters and years are counted from. Zero means January.
+	YearStart time.Month
+	// Calendar, if set, divides each financial year into weeks instead
+	// of calendar months, as retail calendars do: each quarter has 13
+	// weeks, split into three periods of the given numbers of weeks,
+	// such as 4, 4, and 5. See ParseCalendar.
+	Calendar []int
+	// YearStarts gives the first day of each financial year in a
+	// Calendar, in date order. A year runs until the next one starts,
+	// so 53-week years can be listed; the last year has 52 weeks. Dates
+	// before the first year or after the last are labelled as though
+	// there were no Calendar.
+	YearStarts []time.Time
+}
+
+// ParseCalendar parses a retail calendar pattern, such as "4-4-5", "4-5-4",
+// or "5-4-4": the number of weeks in each of the three periods of a
+// quarter.
+func ParseCalendar(s string) ([]int, error) {
+	parts := strings.Split(s, "-")
+	if len(parts) != 3 {
+		return nil, fmt.Errorf("calendar %q: want three numbers of weeks, such as 4-4-5", s)
+	}
+	var weeks []int
+	total := 0
+	for _, part := range parts {
+		n, err := strconv.Atoi(part)
+		if err != nil || n < 1 {
+			return nil, fmt.Errorf("calendar %q: want three numbers of weeks, such as 4-4-5", s)
+		}
+		weeks = append(weeks, n)
+		total += n
+	}
+	if total != 13 {
+		return nil, fmt.Errorf("calendar %q: quarters must have 13 weeks, not %d", s, total)
+	}
+	return weeks, nil
+}
+
+// Label returns the name of the period containing t, such as "2024-03"
+// for a month, "2024-W09" for an ISO week, or "2024-Q1" for a quarter.
+// Financial years not starting in January are named after the calendar year
+// they end in, such as "FY2025" or "FY2025-Q1" for April to June 2024 with
+// a year starting in April. With a Calendar, periods within the year are
+// numbered instead of named after months, such as "FY2025-P01", and weeks
+// are counted from the start of the year, such as "FY2025-W01".
+func (p Period) Label(t time.Time) string {
+	if year, week, ok := p.retail(t); ok {
+		switch p.Unit {
+		case Weekly:
+			return fmt.Sprintf("FY%d-W%02d", year, week+1)
+		case Quarterly:
+			return fmt.Sprintf("FY%d-Q%d", year, p.retailPeriod(week)/3+1)
+		case Yearly:
+			return fmt.Sprintf("FY%d", year)
+		}
+		return fmt.Sprintf("FY%d-P%02d", year, p.retailPeriod(week)+1)
+	}
+	switch p.Unit {
+	case Weekly:
+		year, week := t.ISOWeek()
+		return fmt.Sprintf("%d-W%02d", year, week)
+	case Quarterly, Yearly:
+		year, month := p.fiscal(t)
+		name := fmt.Sprint(year)
+		if p.YearStart > time.January {
+			name = "FY" + name
+		}
+		if p.Unit == Quarterly {
+			name += fmt.Sprintf("-Q%d", (month-1)/3+1)
+		}
+		return name
+	}
+	return t.Format("2006-01")
+}
+
+// retail returns the financial year containing t in the Calendar, named
+// after the calendar year it ends in, and the week of that year (from 0)
+// that t falls in. It reports false if there is no Calendar, or t is before
+// the first year or after the last.
+func (p Period) retail(t time.Time) (year, week int, ok bool) {
+	if len(p.Calendar) == 0 {
+		return 0, 0, false
+	}
+	// Compare calendar dates, ignoring the time of day.
+	day := time.Date(t.Year(), t.Month(), t.Day(), 0, 0, 0, 0, time.UTC)
+	i := sort.Search(len(p.YearStarts), func(i int) bool {
+		return p.YearStarts[i].After(day)
+	}) - 1
+	if i < 0 {
+		return 0, 0, false
+	}
+	start := p.YearStarts[i]
+	end := start.AddDate(0, 0, 364)
+	if i+1 < len(p.YearStarts) {
+		end = p.YearStarts[i+1]
+	}
+	if !day.Before(end) {
+		return 0, 0, false
+	}
+	return end.AddDate(0, 0, -1).Year(), int(day.Sub(start).Hours()) / (24 * 7), true
+}
+
+// retailPeriod returns the period of the year (from 0) that week falls in.
+// The weeks of a 53-week year past the end of the fourth quarter belong to
+// its last period.
+func (p Period) retailPeriod(week int) int {
+	quarter := min(week/13, 3)
+	w := week - quarter*13
+	period := 0
+	for period < 2 && w >= p.Calendar[period] {
+		w -= p.Calendar[period]
+		period++
+	}
+	return quarter*3 + period
+}
+
+// fiscal returns the financial year containing t, and the month of that
+// year (1 to 12) that t falls in.
+func (p Period) fiscal(t time.Time) (year, month int) {
+	start := p.YearStart
+	if start < time.January || start > time.December {
+		start = time.January
+	}
+	month = int(t.Month()-start+12)%12 + 1
+	year = t.Year()
+	if start > time.January && t.Month() >= start {
+		year++
+	}
+	return year, month
+}
+
+// PeriodTotal is the sales in one period, such as a month or a quarter.
+type PeriodTotal struct {
+	Period string
+	Tally
+}
+
+// ByPeriod returns the sales in each Period, in date order. Sales without
+// timestamps, such as those from summary-only sources, aren't included.
+func (r *Report) ByPeriod() []PeriodTotal {
+	totals := map[string]Tally{}
+	for s, t := range r.slots {
+		if s.period == "" {
+			continue
+		}
+		sum := totals[s.period]
+		sum.Units += t.Units
+		sum.Revenue += t.Revenue
+		totals[s.period] = sum
+	}
+	periods := make([]PeriodTotal, 0, len(totals))
+	for p, t := range totals {
+		periods = append(periods, PeriodTotal{Period: p, Tally: t})
+	}
+	sort.Slice(periods, func(i, j int) bool {
+		return periods[i].Period < periods[j].Period
+	})
+	return periods
+}
+
+// WritePeriods writes the sales in each period as a text table.
+func (r *Report) WritePeriods(w io.Writer) error {
+	t := &table{
+		header: []string{"Period", "Units", "Revenue"},
+		right:  []bool{false, true, true},
+		color:  r.Color,
+	}
+	for _, p := range r.ByPeriod() {
+		t.add(p.Period, strconv.Itoa(p.Units), p.Revenue.String())
+	}
+	t.footer = []string{"Total", strconv.Itoa(r.TotalUnits()), r.TotalRevenue().String()}
+	if err := t.write(w); err != nil {
+		return err
+	}
+	if len(r.aggregates) > 0 {
+		fmt.Fprintln(w)
+		fmt.Fprintln(w, "Includes undated summary-only sources:", strings.Join(r.aggregates, ", "))
+	}
+	return nil
+}
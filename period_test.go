@@ -0,0 +1,118 @@
+package sales_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func date(year int, month time.Month, day int) time.Time {
+	return time.Date(year, month, day, 12, 0, 0, 0, time.UTC)
+}
+
+func TestPeriodLabelNamesCalendarPeriods(t *testing.T) {
+	t.Parallel()
+	tcs := []struct {
+		period sales.Period
+		t      time.Time
+		want   string
+	}{
+		{sales.Period{}, date(2024, time.March, 5), "2024-03"},
+		{sales.Period{Unit: sales.Weekly}, date(2024, time.January, 1), "2024-W01"},
+		{sales.Period{Unit: sales.Weekly}, date(2021, time.January, 1), "2020-W53"},
+		{sales.Period{Unit: sales.Quarterly}, date(2024, time.May, 1), "2024-Q2"},
+		{sales.Period{Unit: sales.Yearly}, date(2024, time.May, 1), "2024"},
+		{sales.Period{Unit: sales.Quarterly, YearStart: time.April}, date(2024, time.April, 10), "FY2025-Q1"},
+		{sales.Period{Unit: sales.Quarterly, YearStart: time.April}, date(2024, time.March, 10), "FY2024-Q4"},
+		{sales.Period{Unit: sales.Yearly, YearStart: time.July}, date(2024, time.June, 30), "FY2024"},
+	}
+	for _, tc := range tcs {
+		if got := tc.period.Label(tc.t); got != tc.want {
+			t.Errorf("%+v, %s: want %q, got %q", tc.period, tc.t.Format("2006-01-02"), tc.want, got)
+		}
+	}
+}
+
+func TestPeriodLabelNamesRetailCalendarPeriods(t *testing.T) {
+	t.Parallel()
+	calendar, err := sales.ParseCalendar("4-4-5")
+	if err != nil {
+		t.Fatal(err)
+	}
+	// FY2025 has 53 weeks, and FY2026 is the last year listed, so has 52.
+	starts := []time.Time{
+		time.Date(2024, time.February, 4, 0, 0, 0, 0, time.UTC),
+		time.Date(2025, time.February, 9, 0, 0, 0, 0, time.UTC),
+	}
+	p := func(unit sales.PeriodUnit) sales.Period {
+		return sales.Period{Unit: unit, Calendar: calendar, YearStarts: starts}
+	}
+	tcs := []struct {
+		period sales.Period
+		t      time.Time
+		want   string
+	}{
+		{p(sales.Weekly), date(2024, time.February, 4), "FY2025-W01"},
+		{p(sales.Monthly), date(2024, time.February, 4), "FY2025-P01"},
+		{p(sales.Monthly), date(2024, time.March, 3), "FY2025-P02"},
+		{p(sales.Monthly), date(2024, time.April, 28), "FY2025-P03"},
+		{p(sales.Monthly), date(2024, time.May, 5), "FY2025-P04"},
+		{p(sales.Quarterly), date(2024, time.May, 5), "FY2025-Q2"},
+		{p(sales.Weekly), date(2025, time.February, 8), "FY2025-W53"},
+		{p(sales.Monthly), date(2025, time.February, 8), "FY2025-P12"},
+		{p(sales.Yearly), date(2025, time.February, 9), "FY2026"},
+		{p(sales.Weekly), date(2026, time.February, 7), "FY2026-W52"},
+		// Dates outside the listed years are labelled as though there
+		// were no calendar.
+		{p(sales.Monthly), date(2024, time.January, 15), "2024-01"},
+		{p(sales.Monthly), date(2026, time.February, 8), "2026-02"},
+		{p(sales.Weekly), date(2027, time.March, 1), "2027-W09"},
+	}
+	for _, tc := range tcs {
+		if got := tc.period.Label(tc.t); got != tc.want {
+			t.Errorf("%s: want %q, got %q", tc.t.Format("2006-01-02"), tc.want, got)
+		}
+	}
+}
+
+func TestParseCalendarRejectsPatternsNotMakingAQuarter(t *testing.T) {
+	t.Parallel()
+	for _, s := range []string{"4-4-4", "4-9", "4-4-5-0", "0-8-5", "a-b-c", ""} {
+		if _, err := sales.ParseCalendar(s); err == nil {
+			t.Errorf("%q: want error, got nil", s)
+		}
+	}
+}
+
+func TestParsePeriodUnitRejectsUnknownUnits(t *testing.T) {
+	t.Parallel()
+	if _, err := sales.ParsePeriodUnit("fortnight"); err == nil {
+		t.Error("want error, got nil")
+	}
+	if got, err := sales.ParsePeriodUnit("quarter"); err != nil || got != sales.Quarterly {
+		t.Errorf("want Quarterly, got %v (%v)", got, err)
+	}
+}
+
+func TestByPeriodTotalsSalesInEachPeriod(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Period = sales.Period{Unit: sales.Quarterly}
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Time: date(2024, time.February, 1)})
+	r.Add(sales.Record{Product: "Video", Quantity: 2, Price: sales.NewUSD(20), Time: date(2024, time.March, 1)})
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Time: date(2024, time.April, 1)})
+	want := []sales.PeriodTotal{
+		{Period: "2024-Q1", Tally: sales.Tally{Units: 3, Revenue: sales.NewUSD(50)}},
+		{Period: "2024-Q2", Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(10)}},
+	}
+	got := r.ByPeriod()
+	if len(got) != len(want) {
+		t.Fatalf("want %d periods, got %+v", len(want), got)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("want %+v, got %+v", want[i], got[i])
+		}
+	}
+}
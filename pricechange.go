@@ -0,0 +1,206 @@
+package sales
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"math"
+	"strconv"
+	"time"
+)
+
+// PriceChangePeriod is the sales of a product on either side of a price
+// change, made up of whole days counted from the change.
+type PriceChangePeriod struct {
+	From, To time.Time
+	Days     int
+	Tally
+	// daily holds the units and revenue of each day, for Significance.
+	daily []Tally
+}
+
+// UnitsPerDay returns the average units sold per day in the period.
+func (p PriceChangePeriod) UnitsPerDay() float64 {
+	if p.Days == 0 {
+		return 0
+	}
+	return float64(p.Units) / float64(p.Days)
+}
+
+// RevenuePerDay returns the average revenue per day in the period.
+func (p PriceChangePeriod) RevenuePerDay() USD {
+	if p.Days == 0 {
+		return 0
+	}
+	return p.Revenue / USD(p.Days)
+}
+
+// AveragePrice returns the average price paid per unit in the period.
+func (p PriceChangePeriod) AveragePrice() USD {
+	if p.Units == 0 {
+		return 0
+	}
+	return p.Revenue / USD(p.Units)
+}
+
+// PriceChange compares the sales of a product before and after its price
+// was changed.
+type PriceChange struct {
+	Product string
+	At      time.Time
+	Before  PriceChangePeriod
+	After   PriceChangePeriod
+}
+
+// UnitsT returns Welch's t statistic for the difference in units per day
+// after the change, or zero if either side has fewer than two days.
+func (pc PriceChange) UnitsT() float64 {
+	return welch(pc.Before.daily, pc.After.daily, func(t Tally) float64 { return float64(t.Units) })
+}
+
+// RevenueT returns Welch's t statistic for the difference in revenue per
+// day after the change, or zero if either side has fewer than two days.
+func (pc PriceChange) RevenueT() float64 {
+	return welch(pc.Before.daily, pc.After.daily, func(t Tally) float64 { return float64(t.Revenue) })
+}
+
+// welch returns Welch's t statistic for the difference between the means
+// of value over a and b.
+func welch(a, b []Tally, value func(Tally) float64) float64 {
+	if len(a) < 2 || len(b) < 2 {
+		return 0
+	}
+	stats := func(days []Tally) (mean, variance float64) {
+		for _, d := range days {
+			mean += value(d)
+		}
+		mean /= float64(len(days))
+		for _, d := range days {
+			variance += (value(d) - mean) * (value(d) - mean)
+		}
+		return mean, variance / float64(len(days)-1)
+	}
+	ma, va := stats(a)
+	mb, vb := stats(b)
+	se := math.Sqrt(va/float64(len(a)) + vb/float64(len(b)))
+	if se == 0 {
+		return 0
+	}
+	return (mb - ma) / se
+}
+
+// Significance describes how likely a difference with the t statistic t
+// is to be real, rather than day-to-day noise. It's a hint, not a proper
+// test: daily sales are seldom normally distributed.
+func Significance(t float64) string {
+	switch t = math.Abs(t); {
+	case t >= 2.6:
+		return "very likely real"
+	case t >= 2:
+		return "probably real"
+	case t >= 1:
+		return "possibly chance"
+	}
+	return "likely chance"
+}
+
+// PriceChange compares the sales of product, the name of a report row,
+// in the days before and after its price changed at the time at. With a
+// positive window, that many days on each side are compared; otherwise,
+// every day from the product's first sale to its last. It needs the
+// records kept by KeepRecords, and reports an error without them.
+func (r *Report) PriceChange(product string, at time.Time, window int) (PriceChange, error) {
+	if !r.KeepRecords {
+		return PriceChange{}, errors.New("price change analysis needs KeepRecords")
+	}
+	var recs []Record
+	var first, last time.Time
+	err := r.EachRecord(func(rec Record) error {
+		if rec.Time.IsZero() || r.Groups.ClassifyItem(rec.Product, rec.SKU) != product {
+			return nil
+		}
+		recs = append(recs, rec)
+		if first.IsZero() || rec.Time.Before(first) {
+			first = rec.Time
+		}
+		last = later(last, rec.Time)
+		return nil
+	})
+	if err != nil {
+		return PriceChange{}, err
+	}
+	if len(recs) == 0 {
+		return PriceChange{}, fmt.Errorf("no dated sales of %q", product)
+	}
+	const day = 24 * time.Hour
+	before, after := window, window
+	if window <= 0 {
+		before = int((at.Sub(first) + day - 1) / day)
+		after = int(last.Sub(at)/day) + 1
+	}
+	pc := PriceChange{
+		Product: product,
+		At:      at,
+		Before:  PriceChangePeriod{From: at.Add(-time.Duration(before) * day), To: at, Days: max(before, 0)},
+		After:   PriceChangePeriod{From: at, To: at.Add(time.Duration(after) * day), Days: max(after, 0)},
+	}
+	pc.Before.daily = make([]Tally, pc.Before.Days)
+	pc.After.daily = make([]Tally, pc.After.Days)
+	for _, rec := range recs {
+		total := rec.Total().Round(r.Rounding)
+		for _, p := range []*PriceChangePeriod{&pc.Before, &pc.After} {
+			if rec.Time.Before(p.From) || !rec.Time.Before(p.To) {
+				continue
+			}
+			i := int(rec.Time.Sub(p.From) / day)
+			p.daily[i].Units += rec.Quantity
+			p.daily[i].Revenue += total
+			p.Units += rec.Quantity
+			p.Revenue += total
+		}
+	}
+	return pc, nil
+}
+
+// later returns the later of a and b.
+func later(a, b time.Time) time.Time {
+	if b.After(a) {
+		return b
+	}
+	return a
+}
+
+// WritePriceChange writes the comparison pc as a text table, with a hint
+// of whether each difference is likely to be real.
+func (r *Report) WritePriceChange(w io.Writer, pc PriceChange) error {
+	fmt.Fprintf(w, "%s, price changed %s\n", pc.Product, pc.At.Format("2006-01-02 15:04"))
+	t := &table{
+		header: []string{"", "Before", "After", "Change", "Significance"},
+		right:  []bool{false, true, true, true, false},
+		color:  r.Color,
+	}
+	b, a := pc.Before, pc.After
+	t.add("Days", strconv.Itoa(b.Days), strconv.Itoa(a.Days), "", "")
+	t.add("Average price", b.AveragePrice().String(), a.AveragePrice().String(), change(float64(b.AveragePrice()), float64(a.AveragePrice())), "")
+	t.add("Units/day", strconv.FormatFloat(b.UnitsPerDay(), 'f', 2, 64), strconv.FormatFloat(a.UnitsPerDay(), 'f', 2, 64), change(b.UnitsPerDay(), a.UnitsPerDay()), significance(pc.UnitsT()))
+	t.add("Revenue/day", b.RevenuePerDay().String(), a.RevenuePerDay().String(), change(float64(b.RevenuePerDay()), float64(a.RevenuePerDay())), significance(pc.RevenueT()))
+	return t.write(w)
+}
+
+// change returns the percentage change from before to after, such as
+// "+12.5%", or "" if there was nothing before.
+func change(before, after float64) string {
+	if before == 0 {
+		return ""
+	}
+	return fmt.Sprintf("%+.1f%%", (after-before)/before*100)
+}
+
+// significance returns the Significance of t, or "" if there's too little
+// data to say.
+func significance(t float64) string {
+	if t == 0 {
+		return ""
+	}
+	return Significance(t)
+}
@@ -0,0 +1,118 @@
+package sales
+
+import (
+	"io"
+	"sort"
+	"strconv"
+)
+
+// priceBuckets is the most buckets that a price distribution is divided
+// into.
+const priceBuckets = 10
+
+// priceSlot identifies the units of a report row sold at one price.
+type priceSlot struct {
+	product string
+	price   USD
+}
+
+// PriceBucket is the number of units sold at prices in [From, To), or, for
+// the last bucket, [From, To].
+type PriceBucket struct {
+	From  USD
+	To    USD
+	Units int
+}
+
+// PriceDistribution describes the prices paid for a product, such as one
+// sold for whatever the customer wants to pay. Each unit counts once, so
+// the median is that of the price paid per unit.
+type PriceDistribution struct {
+	Units   int
+	Min     USD
+	Median  USD
+	Max     USD
+	Buckets []PriceBucket
+}
+
+// PriceDistribution returns the distribution of the unit prices paid for
+// the report row called product. Refunds and other lines with no units
+// aren't counted. A product with no sales has a zero distribution.
+func (r *Report) PriceDistribution(product string) PriceDistribution {
+	type count struct {
+		price USD
+		units int
+	}
+	var counts []count
+	var d PriceDistribution
+	for k, units := range r.prices {
+		if k.product == product {
+			counts = append(counts, count{price: k.price, units: units})
+			d.Units += units
+		}
+	}
+	if d.Units == 0 {
+		return PriceDistribution{}
+	}
+	sort.Slice(counts, func(i, j int) bool {
+		return counts[i].price < counts[j].price
+	})
+	d.Min, d.Max = counts[0].price, counts[len(counts)-1].price
+	// The median is the middle unit's price, or the mean of the middle
+	// two.
+	priceOf := func(n int) USD {
+		for _, c := range counts {
+			if n < c.units {
+				return c.price
+			}
+			n -= c.units
+		}
+		return d.Max
+	}
+	d.Median = (priceOf((d.Units-1)/2) + priceOf(d.Units/2)) / 2
+	buckets := min(len(counts), priceBuckets)
+	width := (d.Max - d.Min) / USD(buckets)
+	if width == 0 {
+		d.Buckets = []PriceBucket{{From: d.Min, To: d.Max, Units: d.Units}}
+		return d
+	}
+	for i := 0; i < buckets; i++ {
+		b := PriceBucket{From: d.Min + width*USD(i), To: d.Min + width*USD(i+1)}
+		if i == buckets-1 {
+			b.To = d.Max
+		}
+		d.Buckets = append(d.Buckets, b)
+	}
+	for _, c := range counts {
+		i := min(int((c.price-d.Min)/width), buckets-1)
+		d.Buckets[i].Units += c.units
+	}
+	return d
+}
+
+// WritePriceHistograms writes the distribution of prices paid for each
+// product as a text table, in the order of Products. Each product's row
+// gives its median and range of prices, and is followed by a row, with a
+// bar, for each bucket of prices.
+func (r *Report) WritePriceHistograms(w io.Writer) error {
+	t := &table{
+		header: []string{"Product", "Price", "Units", ""},
+		right:  []bool{false, false, true, false},
+		color:  r.Color,
+	}
+	for _, p := range r.Products() {
+		d := r.PriceDistribution(p.Name)
+		if d.Units == 0 {
+			continue
+		}
+		t.add(p.Name, "median "+d.Median.String()+" ("+d.Min.String()+"–"+d.Max.String()+")", strconv.Itoa(d.Units), "")
+		most := 0
+		for _, b := range d.Buckets {
+			most = max(most, b.Units)
+		}
+		for _, b := range d.Buckets {
+			t.add("", b.From.String()+"–"+b.To.String(), strconv.Itoa(b.Units), bar(int64(b.Units), int64(most)))
+		}
+	}
+	return t.write(w)
+}
@@ -0,0 +1,212 @@
+package sales
+
+import (
+	"context"
+	"encoding/csv"
+	"encoding/xml"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// ECBRatesURL is the European Central Bank's history of its daily reference
+// exchange rates, read by FetchECBRates.
+const ECBRatesURL = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-hist.xml"
+
+// DatedRate is an exchange rate published on a particular date.
+type DatedRate struct {
+	Date time.Time
+	Rate float64
+}
+
+// RateHistory gives the exchange rates of currencies, by their codes, such
+// as "EUR", over time: the value of one unit of each in the report's
+// currency, in date order.
+type RateHistory map[string][]DatedRate
+
+// Rate returns the rate for currency on the date of t: the latest one
+// published on or before that date, since rates aren't published at
+// weekends or on holidays. For a zero t, it returns the latest rate. It
+// returns false if there's no such rate.
+func (h RateHistory) Rate(currency string, t time.Time) (float64, bool) {
+	rates := h[strings.ToUpper(currency)]
+	if len(rates) == 0 {
+		return 0, false
+	}
+	if t.IsZero() {
+		return rates[len(rates)-1].Rate, true
+	}
+	day := time.Date(t.Year(), t.Month(), t.Day(), 0, 0, 0, 0, time.UTC)
+	i := sort.Search(len(rates), func(i int) bool {
+		return rates[i].Date.After(day)
+	})
+	if i == 0 {
+		return 0, false
+	}
+	return rates[i-1].Rate, true
+}
+
+// add records the rate for currency on date, keeping the rates in date
+// order once sort is called.
+func (h RateHistory) add(currency string, date time.Time, rate float64) {
+	h[currency] = append(h[currency], DatedRate{Date: date, Rate: rate})
+}
+
+func (h RateHistory) sort() {
+	for _, rates := range h {
+		sort.SliceStable(rates, func(i, j int) bool {
+			return rates[i].Date.Before(rates[j].Date)
+		})
+	}
+}
+
+// LoadRates reads the exchange rates file at path; see ReadRates.
+func LoadRates(path string) (RateHistory, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	h, err := ReadRates(f)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	return h, nil
+}
+
+// ReadRates reads exchange rates from CSV with the columns date, currency,
+// and rate, in any order, such as:
+//
+//	date,currency,rate
+//	2024-01-02,EUR,1.0956
+//
+// Each rate is the value of one unit of the currency in the report's
+// currency, so that converted revenue matches the books.
+func ReadRates(rd io.Reader) (RateHistory, error) {
+	cr := csv.NewReader(rd)
+	header, err := cr.Read()
+	if err != nil {
+		return nil, err
+	}
+	col := map[string]int{}
+	for i, name := range header {
+		col[strings.ToLower(strings.TrimSpace(name))] = i
+	}
+	for _, name := range []string{"date", "currency", "rate"} {
+		if _, ok := col[name]; !ok {
+			return nil, fmt.Errorf("missing column %q", name)
+		}
+	}
+	h := RateHistory{}
+	for {
+		record, err := cr.Read()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return nil, err
+		}
+		line, _ := cr.FieldPos(0)
+		date, err := time.Parse(time.DateOnly, strings.TrimSpace(record[col["date"]]))
+		if err != nil {
+			return nil, fmt.Errorf("line %d: want a date such as 2024-01-02, got %q", line, record[col["date"]])
+		}
+		rate, err := strconv.ParseFloat(strings.TrimSpace(record[col["rate"]]), 64)
+		if err != nil || rate <= 0 {
+			return nil, fmt.Errorf("line %d: want a positive exchange rate, got %q", line, record[col["rate"]])
+		}
+		h.add(strings.ToUpper(strings.TrimSpace(record[col["currency"]])), date, rate)
+	}
+	h.sort()
+	return h, nil
+}
+
+// FetchECBRates downloads the European Central Bank's daily reference
+// rates, from 1999 to the present, and returns them as rates into
+// currency, such as "USD". The ECB publishes rates for about 30 major
+// currencies.
+func FetchECBRates(ctx context.Context, currency string) (RateHistory, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, ECBRatesURL, nil)
+	if err != nil {
+		return nil, err
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("%s: %s", ECBRatesURL, resp.Status)
+	}
+	h, err := readECBRates(resp.Body, strings.ToUpper(currency))
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", ECBRatesURL, err)
+	}
+	return h, nil
+}
+
+// readECBRates reads the ECB's XML rates, which are the value of one euro in
+// each currency, and returns them as rates into currency.
+func readECBRates(rd io.Reader, currency string) (RateHistory, error) {
+	h := RateHistory{}
+	var date time.Time
+	var perEuro map[string]float64
+	// flush converts a day's rates to rates into currency.
+	flush := func() {
+		base, ok := perEuro[currency]
+		if !ok {
+			return
+		}
+		for code, rate := range perEuro {
+			if code != currency {
+				h.add(code, date, base/rate)
+			}
+		}
+	}
+	dec := xml.NewDecoder(rd)
+	for {
+		tok, err := dec.Token()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			return nil, err
+		}
+		el, ok := tok.(xml.StartElement)
+		if !ok || el.Name.Local != "Cube" {
+			continue
+		}
+		attrs := map[string]string{}
+		for _, a := range el.Attr {
+			attrs[a.Name.Local] = a.Value
+		}
+		if t, ok := attrs["time"]; ok {
+			flush()
+			date, err = time.Parse(time.DateOnly, t)
+			if err != nil {
+				return nil, fmt.Errorf("invalid date %q", t)
+			}
+			perEuro = map[string]float64{"EUR": 1}
+			continue
+		}
+		if code, ok := attrs["currency"]; ok && perEuro != nil {
+			rate, err := strconv.ParseFloat(attrs["rate"], 64)
+			if err != nil || rate <= 0 {
+				return nil, fmt.Errorf("invalid rate %q for %s", attrs["rate"], code)
+			}
+			perEuro[code] = rate
+		}
+	}
+	flush()
+	if len(h) == 0 {
+		return nil, errors.New("no rates for " + currency)
+	}
+	h.sort()
+	return h, nil
+}
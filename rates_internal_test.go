@@ -0,0 +1,49 @@
+package sales
+
+import (
+	"math"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestReadECBRatesConvertsEuroRatesToRatesIntoCurrency(t *testing.T) {
+	t.Parallel()
+	h, err := readECBRates(strings.NewReader(`<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+<Cube>
+<Cube time="2024-01-03"><Cube currency="USD" rate="1.10"/><Cube currency="GBP" rate="0.88"/></Cube>
+<Cube time="2024-01-02"><Cube currency="USD" rate="1.00"/><Cube currency="GBP" rate="0.80"/></Cube>
+</Cube>
+</gesmes:Envelope>`), "USD")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := h["USD"]; ok {
+		t.Error("want no rates for the target currency itself")
+	}
+	tcs := []struct {
+		currency string
+		day      int
+		want     float64
+	}{
+		{"EUR", 2, 1.00},
+		{"EUR", 3, 1.10},
+		{"GBP", 2, 1.25},
+		{"GBP", 3, 1.25},
+	}
+	for _, tc := range tcs {
+		got, ok := h.Rate(tc.currency, time.Date(2024, time.January, tc.day, 12, 0, 0, 0, time.UTC))
+		if !ok || math.Abs(got-tc.want) > 1e-9 {
+			t.Errorf("%s on January %d: want %g, got %g (%t)", tc.currency, tc.day, tc.want, got, ok)
+		}
+	}
+}
+
+func TestReadECBRatesRejectsUnknownCurrency(t *testing.T) {
+	t.Parallel()
+	_, err := readECBRates(strings.NewReader(`<Cube><Cube time="2024-01-02"><Cube currency="USD" rate="1.10"/></Cube></Cube>`), "XYZ")
+	if err == nil {
+		t.Error("want error, got nil")
+	}
+}
@@ -0,0 +1,56 @@
+package sales_test
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func TestRateUsesTheLatestRatePublishedByThatDate(t *testing.T) {
+	t.Parallel()
+	h, err := sales.ReadRates(strings.NewReader(`Rate,Date,Currency
+1.10,2024-01-05,eur
+1.08,2024-01-02,EUR
+1.27,2024-01-02,GBP
+`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	tcs := []struct {
+		currency string
+		t        time.Time
+		want     float64
+		ok       bool
+	}{
+		{"EUR", date(2024, time.January, 2), 1.08, true},
+		{"EUR", date(2024, time.January, 4), 1.08, true},
+		{"eur", date(2024, time.January, 6), 1.10, true},
+		{"EUR", time.Time{}, 1.10, true},
+		{"GBP", date(2024, time.March, 1), 1.27, true},
+		{"EUR", date(2024, time.January, 1), 0, false},
+		{"JPY", date(2024, time.January, 2), 0, false},
+	}
+	for _, tc := range tcs {
+		got, ok := h.Rate(tc.currency, tc.t)
+		if got != tc.want || ok != tc.ok {
+			t.Errorf("%s on %v: want %g, %t, got %g, %t", tc.currency, tc.t, tc.want, tc.ok, got, ok)
+		}
+	}
+}
+
+func TestReadRatesRejectsInvalidRows(t *testing.T) {
+	t.Parallel()
+	tcs := map[string]string{
+		"missing column": "date,currency\n2024-01-02,EUR\n",
+		"bad date":       "date,currency,rate\n02/01/2024,EUR,1.08\n",
+		"bad rate":       "date,currency,rate\n2024-01-02,EUR,one\n",
+		"zero rate":      "date,currency,rate\n2024-01-02,EUR,0\n",
+	}
+	for name, input := range tcs {
+		if _, err := sales.ReadRates(strings.NewReader(input)); err == nil {
+			t.Errorf("%s: want error, got nil", name)
+		}
+	}
+}
@@ -0,0 +1,78 @@
+package sales
+
+import (
+	"errors"
+	"time"
+)
+
+// AggregateOptions says how Reaggregate regroups the records of a report.
+type AggregateOptions struct {
+	// Groups replaces the report's Groups.
+	Groups Groups
+	// Filter, if set, leaves out the records for which it returns false.
+	Filter func(Record) bool
+	// Sort replaces the report's Sort.
+	Sort SortKey
+	// Period replaces the report's Period.
+	Period Period
+	// From and To restrict the new report to records timestamped in
+	// [From, To), as in Report.
+	From, To time.Time
+}
+
+// Reaggregate returns a new report made from the records kept by this one,
+// grouped, filtered, and sorted as opts says, without reading the input
+// again. This lets interactive tools try different groupings or filters
+// on the fly. The records have already been renamed, split into bundle
+// parts, converted to the report's currency, and checked against Exclude,
+// so that isn't done again; the report's other settings, such as Rounding
+// and Costs, carry over. Summary-only sources have no records, so they
+// aren't included. It needs the records kept by KeepRecords, and reports
+// an error without them.
+func (r *Report) Reaggregate(opts AggregateOptions) (*Report, error) {
+	if !r.KeepRecords {
+		return nil, errors.New("reaggregating needs KeepRecords")
+	}
+	nr := NewReport()
+	nr.Limits = r.Limits
+	nr.Renames = r.Renames
+	nr.Columns = r.Columns
+	nr.SourceColumns = r.SourceColumns
+	nr.Dialect = r.Dialect
+	nr.Color = r.Color
+	nr.Chart = r.Chart
+	nr.KeepRecords = true
+	nr.RecordBudget = r.RecordBudget
+	nr.SpillDir = r.SpillDir
+	nr.Currency = r.Currency
+	nr.CurrencyPolicy = r.CurrencyPolicy
+	nr.Timestamps = r.Timestamps
+	nr.Bundles = r.Bundles
+	nr.Costs = r.Costs
+	nr.Subscriptions = r.Subscriptions
+	nr.Rounding = r.Rounding
+	nr.Exclude = r.Exclude
+	nr.Comps = r.Comps
+	nr.Logger = r.Logger
+	nr.Groups = opts.Groups
+	nr.Filter = opts.Filter
+	nr.Sort = opts.Sort
+	nr.Period = opts.Period
+	nr.From, nr.To = opts.From, opts.To
+	// What was excluded or deduplicated on reading still was.
+	for reason, n := range r.excluded {
+		nr.excluded[reason] = n
+	}
+	nr.duplicates = r.duplicates
+	err := r.EachRecord(func(rec Record) error {
+		if nr.Filter == nil || nr.Filter(rec) {
+			nr.add(rec)
+		}
+		return nil
+	})
+	if err != nil {
+		nr.Close()
+		return nil, err
+	}
+	return nr, nil
+}
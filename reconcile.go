@@ -0,0 +1,134 @@
+package sales
+
+import (
+	"encoding/csv"
+	"errors"
+	"fmt"
+	"io"
+	"sort"
+	"time"
+)
+
+// Payout is a transfer of sales revenue from a platform to the seller's
+// bank account.
+type Payout struct {
+	Date   time.Time
+	Amount USD
+}
+
+// ReadPayouts reads payouts from CSV input with a header row, taking each
+// payout's date and amount from the columns named dateColumn and
+// amountColumn. Dates are read as by ts.
+func ReadPayouts(rd io.Reader, source, dateColumn, amountColumn string, ts Timestamps) ([]Payout, error) {
+	cr := csv.NewReader(rd)
+	header, err := cr.Read()
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", source, err)
+	}
+	col := map[string]int{}
+	for i, name := range header {
+		col[name] = i
+	}
+	for _, name := range []string{dateColumn, amountColumn} {
+		if _, ok := col[name]; !ok {
+			return nil, fmt.Errorf("%s: missing column %q", source, name)
+		}
+	}
+	var payouts []Payout
+	for {
+		record, err := cr.Read()
+		if err == io.EOF {
+			return payouts, nil
+		}
+		if err != nil {
+			var pe *csv.ParseError
+			if errors.As(err, &pe) {
+				return nil, &RowError{File: source, Line: pe.StartLine, Err: pe.Err}
+			}
+			return nil, fmt.Errorf("%s: %w", source, err)
+		}
+		invalid := func(name, kind string, err error) error {
+			line, _ := cr.FieldPos(col[name])
+			value := record[col[name]]
+			return &RowError{File: source, Line: line, Err: &FieldError{Column: name, Value: value, Kind: kind, Err: err}}
+		}
+		date, err := ts.Parse(record[col[dateColumn]])
+		if err != nil {
+			return nil, invalid(dateColumn, "date", err)
+		}
+		amount, err := ParseUSD(record[col[amountColumn]])
+		if err != nil {
+			return nil, invalid(amountColumn, "amount", err)
+		}
+		payouts = append(payouts, Payout{Date: date, Amount: amount})
+	}
+}
+
+// Reconciliation compares the revenue from one month's sales, less the
+// platform's fees, with what was paid out for them.
+type Reconciliation struct {
+	Month    string
+	Revenue  USD
+	Fees     USD
+	Expected USD
+	Paid     USD
+	// Difference is Paid less Expected.
+	Difference USD
+}
+
+// Reconcile matches the report's revenue and fees for each month with
+// payouts, returning the months in order. Since platforms pay out some
+// days after a sale, each payout is counted in the month of its date less
+// lag. Only dated sales are included.
+func (r *Report) Reconcile(payouts []Payout, lag time.Duration) []Reconciliation {
+	months := map[string]*Reconciliation{}
+	month := func(m string) *Reconciliation {
+		if months[m] == nil {
+			months[m] = &Reconciliation{Month: m}
+		}
+		return months[m]
+	}
+	for k, rev := range r.daily {
+		month(k.date[:len("2006-01")]).Revenue += rev
+	}
+	for m, fee := range r.fees {
+		if m != "" {
+			month(m).Fees += fee
+		}
+	}
+	for _, p := range payouts {
+		date := p.Date.Add(-lag)
+		if zone := r.Timestamps.Zone; zone != nil {
+			date = date.In(zone)
+		}
+		month(date.Format("2006-01")).Paid += p.Amount
+	}
+	recs := make([]Reconciliation, 0, len(months))
+	for _, rec := range months {
+		rec.Expected = rec.Revenue - rec.Fees
+		rec.Difference = rec.Paid - rec.Expected
+		recs = append(recs, *rec)
+	}
+	sort.Slice(recs, func(i, j int) bool {
+		return recs[i].Month < recs[j].Month
+	})
+	return recs
+}
+
+// WriteReconciliation writes recs as a text table, flagging the months in
+// which the payouts differ from the expected amount by more than tolerance.
+func (r *Report) WriteReconciliation(w io.Writer, recs []Reconciliation, tolerance USD) error {
+	t := &table{
+		header: []string{"Month", "Revenue", "Fees", "Expected", "Paid", "Difference", ""},
+		right:  []bool{false, true, true, true, true, true, false},
+		color:  r.Color,
+	}
+	for _, rec := range recs {
+		flag := ""
+		if rec.Difference > tolerance || rec.Difference < -tolerance {
+			flag = "unexplained"
+		}
+		t.add(rec.Month, rec.Revenue.String(), rec.Fees.String(), rec.Expected.String(), rec.Paid.String(), rec.Difference.String(), flag)
+	}
+	return t.write(w)
+}
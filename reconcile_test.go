@@ -0,0 +1,63 @@
+package sales_test
+
+import (
+	"errors"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func TestReconcileMatchesMonthlyRevenueLessFeesWithPayouts(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(100), Fee: sales.NewUSD(5), Time: date(2024, time.January, 30)})
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(50), Fee: sales.NewUSD(2.5), Time: date(2024, time.February, 10)})
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(20), Fee: sales.NewUSD(1)})
+	payouts, err := sales.ReadPayouts(strings.NewReader(`Date,Net
+2024-02-02,95.00
+2024-02-14,40.00
+`), "payouts.csv", "Date", "Net", sales.Timestamps{})
+	if err != nil {
+		t.Fatal(err)
+	}
+	got := r.Reconcile(payouts, 3*24*time.Hour)
+	want := []sales.Reconciliation{
+		{Month: "2024-01", Revenue: sales.NewUSD(100), Fees: sales.NewUSD(5), Expected: sales.NewUSD(95), Paid: sales.NewUSD(95)},
+		{Month: "2024-02", Revenue: sales.NewUSD(50), Fees: sales.NewUSD(2.5), Expected: sales.NewUSD(47.5), Paid: sales.NewUSD(40), Difference: sales.NewUSD(-7.5)},
+	}
+	if len(got) != len(want) {
+		t.Fatalf("want %+v, got %+v", want, got)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("month %d:\nwant %+v\ngot  %+v", i, want[i], got[i])
+		}
+	}
+	var b strings.Builder
+	if err := r.WriteReconciliation(&b, got, sales.NewUSD(1)); err != nil {
+		t.Fatal(err)
+	}
+	for _, line := range strings.Split(b.String(), "\n") {
+		if strings.Contains(line, "unexplained") != strings.HasPrefix(line, "2024-02") {
+			t.Errorf("want only February flagged, got line %q", line)
+		}
+	}
+}
+
+func TestReadPayoutsReportsTheLineOfABadAmount(t *testing.T) {
+	t.Parallel()
+	_, err := sales.ReadPayouts(strings.NewReader(`Date,Net
+2024-02-02,95.00
+2024-02-14,lots
+`), "payouts.csv", "Date", "Net", sales.Timestamps{})
+	var re *sales.RowError
+	if !errors.As(err, &re) || re.Line != 3 {
+		t.Errorf("want error on line 3, got %v", err)
+	}
+	_, err = sales.ReadPayouts(strings.NewReader("Date,Amount\n"), "payouts.csv", "Date", "Net", sales.Timestamps{})
+	if err == nil {
+		t.Error("want error for missing column, got nil")
+	}
+}
@@ -0,0 +1,76 @@
+package sales
+
+import (
+	"io"
+	"sort"
+	"strconv"
+)
+
+// referrerSlot identifies the sales from one referral source.
+type referrerSlot struct {
+	referrer  string
+	affiliate string
+}
+
+// ReferrerTotal is the units, revenue, and affiliate fees from sales
+// referred by one source: a referring site, an affiliate, or both. Direct
+// sales have neither.
+type ReferrerTotal struct {
+	Referrer  string
+	Affiliate string
+	Tally
+	AffiliateFees USD
+}
+
+// addReferrer counts the sale in rec against its referral source.
+func (r *Report) addReferrer(rec Record, total USD) {
+	k := referrerSlot{referrer: rec.Referrer, affiliate: rec.Affiliate}
+	t := r.referrers[k]
+	t.Units += rec.Quantity
+	t.Revenue += total
+	t.AffiliateFees += rec.AffiliateFee
+	r.referrers[k] = t
+}
+
+// ByReferrer returns the units, revenue, and affiliate fees from each
+// referral source, largest revenue first.
+func (r *Report) ByReferrer() []ReferrerTotal {
+	totals := make([]ReferrerTotal, 0, len(r.referrers))
+	for k, t := range r.referrers {
+		t.Referrer, t.Affiliate = k.referrer, k.affiliate
+		totals = append(totals, t)
+	}
+	sort.Slice(totals, func(i, j int) bool {
+		a, b := totals[i], totals[j]
+		if a.Revenue != b.Revenue {
+			return a.Revenue > b.Revenue
+		}
+		if a.Referrer != b.Referrer {
+			return a.Referrer < b.Referrer
+		}
+		return a.Affiliate < b.Affiliate
+	})
+	return totals
+}
+
+// WriteReferrers writes the totals from each referral source as a text
+// table, with the revenue net of affiliate fees.
+func (r *Report) WriteReferrers(w io.Writer) error {
+	t := &table{
+		header: []string{"Referrer", "Affiliate", "Units", "Revenue", "Affiliate fees", "Net"},
+		right:  []bool{false, false, true, true, true, true},
+		color:  r.Color,
+	}
+	var fees USD
+	for _, rt := range r.ByReferrer() {
+		referrer := rt.Referrer
+		if referrer == "" && rt.Affiliate == "" {
+			referrer = "(direct)"
+		}
+		t.add(referrer, rt.Affiliate, strconv.Itoa(rt.Units), rt.Revenue.String(), rt.AffiliateFees.String(), (rt.Revenue - rt.AffiliateFees).String())
+		fees += rt.AffiliateFees
+	}
+	total := r.TotalRevenue()
+	t.footer = []string{"Total", "", strconv.Itoa(r.TotalUnits()), total.String(), fees.String(), (total - fees).String()}
+	return t.write(w)
+}
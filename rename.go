@@ -0,0 +1,27 @@
+package sales
+
+import "regexp"
+
+// Rename is a rule that normalises product names, such as removing the
+// year from "For the Love of Go (2023)", without putting them in a group.
+// Each match of Pattern in a name is replaced by Replacement, in which $1
+// and so on stand for the text matched by subexpressions, as in
+// regexp.Regexp.Expand.
+type Rename struct {
+	Pattern     *regexp.Regexp
+	Replacement string
+}
+
+// Renames is a list of rules for renaming products. A product is renamed
+// by the first rule whose pattern matches its name, if any.
+type Renames []Rename
+
+// Apply returns the new name for the product called name.
+func (rs Renames) Apply(name string) string {
+	for _, r := range rs {
+		if r.Pattern.MatchString(name) {
+			return r.Pattern.ReplaceAllString(name, r.Replacement)
+		}
+	}
+	return name
+}
@@ -0,0 +1,75 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"strings"
+)
+
+// RenderOptions controls how the text report looks, as opposed to what's
+// in it. The Report holds the data, and each rendering can lay it out
+// differently. Render and RenderTo depend only on these options and the
+// report's data, so their output is the same wherever they run: useful for
+// comparing against a saved snapshot in tests.
+type RenderOptions struct {
+	// Width is the most columns the table may take up; product names are
+	// truncated to fit. Zero means no limit.
+	Width int
+	// Sort is the order of the product rows.
+	Sort SortKey
+	// Locale formats amounts, such as "$1,234.50". If it's the zero
+	// Locale, amounts are plain decimals, as in WriteText.
+	Locale Locale
+	// Chart adds a bar chart column.
+	Chart Chart
+	// Color highlights the table with ANSI escape codes.
+	Color bool
+	// Top, if positive, is the number of product rows to show, in Sort
+	// order. The rest are combined into a single row, whose units,
+	// revenue, and costs are theirs combined, so that the rows still add
+	// up to the totals.
+	Top int
+	// OtherLabel is the label of the row combining the products after the
+	// Top ones. A %d in it is replaced by the number of products combined.
+	// If empty, it's "Other (%d products)".
+	OtherLabel string
+	// HideTotals leaves out the row of totals.
+	HideTotals bool
+	// Columns are the columns to show after the product name, in order.
+	// If nil, they're the units and revenue, followed by any free units
+	// and costs; see Report.Comps and Report.Costs.
+	Columns []OutputColumn
+}
+
+// Display returns the RenderOptions that WriteText uses: the report's
+// Sort, Chart, and Color settings, and otherwise the defaults.
+func (r *Report) Display() RenderOptions {
+	return RenderOptions{Sort: r.Sort, Chart: r.Chart, Color: r.Color}
+}
+
+// RenderTo writes the report to w as a text table, laid out according to
+// opts.
+func (r *Report) RenderTo(w io.Writer, opts RenderOptions) error {
+	return r.writeText(w, opts)
+}
+
+// Render returns the report as a text table, laid out according to opts.
+func (r *Report) Render(opts RenderOptions) string {
+	var b strings.Builder
+	// Writing to a strings.Builder never fails.
+	r.RenderTo(&b, opts)
+	return b.String()
+}
+
+// otherLabel returns the label of the row combining n products after the
+// Top ones.
+func (opts RenderOptions) otherLabel(n int) string {
+	label := opts.OtherLabel
+	if label == "" {
+		label = "Other (%d products)"
+	}
+	if !strings.Contains(label, "%d") {
+		return label
+	}
+	return fmt.Sprintf(label, n)
+}
@@ -0,0 +1,109 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"strconv"
+	"time"
+)
+
+// Renewals is the state of a business's subscriptions in one month: how
+// many customers were subscribed, how many of them were new, and how many
+// of the previous month's subscribers didn't renew.
+type Renewals struct {
+	Month       string
+	Subscribers int
+	New         int
+	Churned     int
+	// MRR is the monthly recurring revenue from the month's
+	// subscribers.
+	MRR USD
+	// Retention is the net revenue retention: the MRR this month from
+	// the previous month's subscribers, as a fraction of their MRR last
+	// month. It's above 1 when upgrades outweigh churn. It's zero if
+	// there was no MRR last month.
+	Retention float64
+}
+
+// ByRenewal returns the subscriptions in each month from the first with
+// any subscribers to the month of the latest sale, in date order. A
+// customer is subscribed in every month paid for by one of their
+// subscription charges; see BySubscription. Charges without a customer
+// can't be followed from month to month, so aren't counted.
+func (r *Report) ByRenewal() []Renewals {
+	byMonth := map[string]map[string]USD{}
+	// Subscriptions paid in advance run past the latest sale, but
+	// whether they'll be renewed isn't known yet.
+	var last string
+	for m := range r.splits {
+		last = max(last, m)
+	}
+	var first string
+	for k, amount := range r.mrr {
+		if k.customer == "" || k.month > last {
+			continue
+		}
+		if byMonth[k.month] == nil {
+			byMonth[k.month] = map[string]USD{}
+		}
+		byMonth[k.month][k.customer] += amount
+		if first == "" || k.month < first {
+			first = k.month
+		}
+	}
+	if first == "" {
+		return nil
+	}
+	var renewals []Renewals
+	start, _ := time.Parse("2006-01", first)
+	var prev map[string]USD
+	for m := start; m.Format("2006-01") <= last; m = m.AddDate(0, 1, 0) {
+		month := m.Format("2006-01")
+		current := byMonth[month]
+		rn := Renewals{Month: month, Subscribers: len(current)}
+		for c, amount := range current {
+			rn.MRR += amount
+			if _, ok := prev[c]; !ok {
+				rn.New++
+			}
+		}
+		var before, retained USD
+		for c, amount := range prev {
+			before += amount
+			if _, ok := current[c]; !ok {
+				rn.Churned++
+			}
+			retained += current[c]
+		}
+		if before != 0 {
+			rn.Retention = float64(retained) / float64(before)
+		}
+		renewals = append(renewals, rn)
+		prev = current
+	}
+	return renewals
+}
+
+// WriteRenewals writes the subscribers, new subscribers, churned
+// subscribers, MRR, and net revenue retention for each month as a text
+// table.
+func (r *Report) WriteRenewals(w io.Writer) error {
+	t := &table{
+		header: []string{"Month", "Subscribers", "New", "Churned", "MRR", "Retention"},
+		right:  []bool{false, true, true, true, true, true},
+		color:  r.Color,
+	}
+	renewals := r.ByRenewal()
+	var added, churned int
+	for i, rn := range renewals {
+		retention := ""
+		if i > 0 && renewals[i-1].MRR != 0 {
+			retention = fmt.Sprintf("%.1f%%", rn.Retention*100)
+		}
+		t.add(rn.Month, strconv.Itoa(rn.Subscribers), strconv.Itoa(rn.New), strconv.Itoa(rn.Churned), rn.MRR.String(), retention)
+		added += rn.New
+		churned += rn.Churned
+	}
+	t.footer = []string{"Total", "", strconv.Itoa(added), strconv.Itoa(churned), "", ""}
+	return t.write(w)
+}
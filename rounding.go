@@ -0,0 +1,170 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"math/big"
+	"sort"
+)
+
+// Rounding is a way of rounding amounts to whole cents.
+type Rounding int
+
+const (
+	// RoundHalfUp rounds amounts halfway between two cents away from
+	// zero, so that 0.125 becomes 0.13.
+	RoundHalfUp Rounding = iota
+	// RoundHalfEven, or banker's rounding, rounds amounts halfway
+	// between two cents to the even cent, so that 0.125 becomes 0.12, but
+	// 0.135 becomes 0.14. Unlike RoundHalfUp, it doesn't bias totals of
+	// many rounded amounts upwards.
+	RoundHalfEven
+)
+
+// ParseRounding returns the rounding mode named by s: "half-up" or
+// "half-even".
+func ParseRounding(s string) (Rounding, error) {
+	switch s {
+	case "half-up":
+		return RoundHalfUp, nil
+	case "half-even":
+		return RoundHalfEven, nil
+	}
+	return RoundHalfUp, fmt.Errorf("unknown rounding %q (want half-up or half-even)", s)
+}
+
+// Round returns u rounded to a whole number of cents using mode.
+func (u USD) Round(mode Rounding) USD {
+	neg := u < 0
+	if neg {
+		u = -u
+	}
+	cents, rem := u/centUnits, u%centUnits
+	if 2*rem > centUnits || 2*rem == centUnits && (mode == RoundHalfUp || cents%2 == 1) {
+		cents++
+	}
+	if neg {
+		cents = -cents
+	}
+	return cents * centUnits
+}
+
+// residueSlot identifies the sales of one product in one period, for
+// auditing rounding.
+type residueSlot struct {
+	product string
+	period  string
+}
+
+// residueTally is the revenue from some sales before and after each line
+// item is rounded to whole cents.
+type residueTally struct {
+	Exact  USD
+	Booked USD
+}
+
+// addResidue records the revenue from a line item of product name, before
+// and after rounding.
+func (r *Report) addResidue(name, period string, exact, booked USD) {
+	k := residueSlot{product: name, period: period}
+	t := r.residues[k]
+	t.Exact += exact
+	t.Booked += booked
+	r.residues[k] = t
+}
+
+// RoundingResidue is the revenue from the line items of one product in one
+// period, before and after each was rounded to whole cents. Line items get
+// fractions of a cent from currency conversion, bundle splitting, and
+// computed taxes, and the rounding of each leaves a residue that can add up
+// over many sales.
+type RoundingResidue struct {
+	Product string
+	// Period is empty for sales without a date.
+	Period string
+	// Exact is the revenue before rounding.
+	Exact USD
+	// Booked is the revenue counted in the report, after rounding each
+	// line item.
+	Booked USD
+	// Residue is Exact less Booked: the revenue that rounding gained
+	// (if negative) or lost.
+	Residue USD
+	// Allocated is this row's share of the exact grand total, rounded
+	// once; see RoundingAudit.
+	Allocated USD
+}
+
+// RoundingAudit returns the rounding residue of each product in each
+// period, ordered by product and period. The exact grand total is rounded
+// to whole cents, and shared out among the rows by the largest remainder
+// method: each row gets its exact revenue rounded down to the cent, and
+// the cents left over go to the rows with the largest remainders. So the
+// Allocated amounts always add up to the rounded grand total, whereas the
+// Booked amounts may not. Summary-only sources have no line items, so
+// aren't included.
+func (r *Report) RoundingAudit() []RoundingResidue {
+	rows := make([]RoundingResidue, 0, len(r.residues))
+	var exact, allocated USD
+	for k, t := range r.residues {
+		// Rounding down, rather than towards zero, keeps the remainders of
+		// refunds positive.
+		floor := t.Exact - ((t.Exact%centUnits)+centUnits)%centUnits
+		rows = append(rows, RoundingResidue{
+			Product:   k.product,
+			Period:    k.period,
+			Exact:     t.Exact,
+			Booked:    t.Booked,
+			Residue:   t.Exact - t.Booked,
+			Allocated: floor,
+		})
+		exact += t.Exact
+		allocated += floor
+	}
+	sort.Slice(rows, func(i, j int) bool {
+		if rows[i].Product != rows[j].Product {
+			return rows[i].Product < rows[j].Product
+		}
+		return rows[i].Period < rows[j].Period
+	})
+	byRemainder := make([]int, len(rows))
+	for i := range byRemainder {
+		byRemainder[i] = i
+	}
+	sort.SliceStable(byRemainder, func(i, j int) bool {
+		a, b := rows[byRemainder[i]], rows[byRemainder[j]]
+		return a.Exact-a.Allocated > b.Exact-b.Allocated
+	})
+	extra := int((exact.Round(r.Rounding) - allocated) / centUnits)
+	for _, i := range byRemainder[:min(extra, len(rows))] {
+		rows[i].Allocated += centUnits
+	}
+	return rows
+}
+
+// WriteRoundingAudit writes the rounding residue of each product in each
+// period as a text table, with amounts before rounding shown to a
+// hundredth of a cent.
+func (r *Report) WriteRoundingAudit(w io.Writer) error {
+	t := &table{
+		header: []string{"Product", "Period", "Exact", "Booked", "Residue", "Allocated"},
+		right:  []bool{false, false, true, true, true, true},
+		color:  r.Color,
+	}
+	var total RoundingResidue
+	for _, rr := range r.RoundingAudit() {
+		t.add(rr.Product, rr.Period, exactString(rr.Exact), rr.Booked.String(), exactString(rr.Residue), rr.Allocated.String())
+		total.Exact += rr.Exact
+		total.Booked += rr.Booked
+		total.Residue += rr.Residue
+		total.Allocated += rr.Allocated
+	}
+	t.footer = []string{"Total", "", exactString(total.Exact), total.Booked.String(), exactString(total.Residue), total.Allocated.String()}
+	return t.write(w)
+}
+
+// exactString returns u as a decimal number of dollars to a hundredth of a
+// cent, such as "12.3456".
+func exactString(u USD) string {
+	return formatDecimal(big.NewInt(int64(u)), 4)
+}
@@ -0,0 +1,59 @@
+package sales_test
+
+import (
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestRoundRoundsHalfCentsByMode(t *testing.T) {
+	t.Parallel()
+	tcs := []struct {
+		amount sales.USD
+		mode   sales.Rounding
+		want   sales.USD
+	}{
+		{sales.NewUSD(0.125), sales.RoundHalfUp, sales.USDCents(13)},
+		{sales.NewUSD(0.125), sales.RoundHalfEven, sales.USDCents(12)},
+		{sales.NewUSD(0.135), sales.RoundHalfEven, sales.USDCents(14)},
+		{sales.NewUSD(0.1251), sales.RoundHalfEven, sales.USDCents(13)},
+		{sales.NewUSD(0.1249), sales.RoundHalfUp, sales.USDCents(12)},
+		{sales.NewUSD(-0.125), sales.RoundHalfUp, sales.USDCents(-13)},
+		{sales.NewUSD(-0.125), sales.RoundHalfEven, sales.USDCents(-12)},
+	}
+	for _, tc := range tcs {
+		if got := tc.amount.Round(tc.mode); got != tc.want {
+			t.Errorf("%d rounded with mode %d: want %d, got %d", tc.amount, tc.mode, tc.want, got)
+		}
+	}
+}
+
+func TestRoundingAuditAllocatesTheRoundedGrandTotal(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	for _, product := range []string{"A", "B", "C"} {
+		r.Add(sales.Record{Product: product, Quantity: 1, Price: sales.NewUSD(0.125)})
+	}
+	audit := r.RoundingAudit()
+	if len(audit) != 3 {
+		t.Fatalf("want 3 rows, got %d", len(audit))
+	}
+	var booked, allocated sales.USD
+	for _, row := range audit {
+		if row.Exact != sales.NewUSD(0.125) || row.Booked != sales.USDCents(13) {
+			t.Errorf("%s: want exact 0.125 booked as 0.13, got %d booked as %v", row.Product, row.Exact, row.Booked)
+		}
+		if row.Residue != row.Exact-row.Booked {
+			t.Errorf("%s: want residue %d, got %d", row.Product, row.Exact-row.Booked, row.Residue)
+		}
+		booked += row.Booked
+		allocated += row.Allocated
+	}
+	if booked != sales.USDCents(39) {
+		t.Errorf("want 0.39 booked, got %v", booked)
+	}
+	// The exact total, 0.375, rounds half up to 0.38.
+	if allocated != sales.USDCents(38) {
+		t.Errorf("want 0.38 allocated, got %v", allocated)
+	}
+}
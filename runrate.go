@@ -0,0 +1,88 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"time"
+)
+
+// runRateDays is the number of days up to the latest sale over which the
+// current run rate is averaged.
+const runRateDays = 7
+
+// RunRate summarises the daily revenue over the dates covered by a report.
+type RunRate struct {
+	// From and Through are the days of the earliest and latest sales, and
+	// Days the number of days from one to the other, inclusive.
+	From    time.Time
+	Through time.Time
+	Days    int
+	// PerDay is the average revenue per day over the whole range,
+	// counting days without sales.
+	PerDay USD
+	// BestDay is the day with the most revenue, and BestRevenue its
+	// revenue. The earliest such day is given in a tie.
+	BestDay     time.Time
+	BestRevenue USD
+	// Current is the average revenue per day over the last week of the
+	// range, or the whole range if it's shorter.
+	Current USD
+}
+
+// Weekly returns the current run rate over a week.
+func (rr RunRate) Weekly() USD {
+	return rr.Current * 7
+}
+
+// RunRate returns the daily revenue and current run rate of the dated
+// sales in the report. It reports false if there are none.
+func (r *Report) RunRate() (RunRate, bool) {
+	days := r.dailyRevenue()
+	var earliest, latest string
+	for d := range days {
+		if earliest == "" || d < earliest {
+			earliest = d
+		}
+		latest = max(latest, d)
+	}
+	if latest == "" {
+		return RunRate{}, false
+	}
+	rr := RunRate{}
+	rr.From, _ = time.Parse(time.DateOnly, earliest)
+	rr.Through, _ = time.Parse(time.DateOnly, latest)
+	var total, recent USD
+	recentDays := 0
+	recentFrom := rr.Through.AddDate(0, 0, 1-runRateDays)
+	for d := rr.From; !d.After(rr.Through); d = d.AddDate(0, 0, 1) {
+		rev := days[d.Format(time.DateOnly)]
+		rr.Days++
+		total += rev
+		if rr.BestDay.IsZero() || rev > rr.BestRevenue {
+			rr.BestDay, rr.BestRevenue = d, rev
+		}
+		if !d.Before(recentFrom) {
+			recent += rev
+			recentDays++
+		}
+	}
+	rr.PerDay = total / USD(rr.Days)
+	rr.Current = recent / USD(recentDays)
+	return rr, true
+}
+
+// WriteRunRate writes the daily revenue, best day, and current run rate,
+// if there are dated sales, as text.
+func (r *Report) WriteRunRate(w io.Writer) error {
+	rr, ok := r.RunRate()
+	if !ok {
+		return nil
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintf(w, "Run rate (%s to %s, %d days):\n", rr.From.Format(time.DateOnly), rr.Through.Format(time.DateOnly), rr.Days)
+	fmt.Fprintf(w, "  Per day       %s\n", rr.PerDay)
+	fmt.Fprintf(w, "  Best day      %s (%s)\n", rr.BestRevenue, rr.BestDay.Format(time.DateOnly))
+	fmt.Fprintf(w, "  Last %d days   %s/day\n", min(rr.Days, runRateDays), rr.Current)
+	_, err := fmt.Fprintf(w, "  Weekly        %s\n", rr.Weekly())
+	return err
+}
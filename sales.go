@@ -0,0 +1,1454 @@
+package sales
+
+import (
+	"compress/gzip"
+	"encoding/csv"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"log/slog"
+	"math"
+	"os"
+	"path"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// squarespaceTime is the layout of timestamps in Squarespace exports.
+const squarespaceTime = "2006-01-02 15:04:05 -0700"
+
+// USD is an amount of money in US dollars, held to a hundredth of a cent so
+// that fractional amounts, such as payment processing fees, add up exactly.
+// It's rounded to whole cents when added to a report; see Rounding. It's
+// 64 bits wide even where int isn't, such as on 386 or in WebAssembly, so
+// that totals of more than $214,748 don't overflow.
+type USD int64
+
+// centUnits is the number of units of USD in a cent.
+const centUnits = 100
+
+func NewUSD(dollars float64) USD {
+	return USD(math.Round(dollars * 100 * centUnits))
+}
+
+// USDCents returns an amount of n cents.
+func USDCents(n int) USD {
+	return USD(n) * centUnits
+}
+
+func (u USD) Dollars() float64 {
+	return float64(u) / (100 * centUnits)
+}
+
+// Cents returns u as a whole number of cents, rounding halves away from
+// zero.
+func (u USD) Cents() int64 {
+	return int64(u.Round(RoundHalfUp) / centUnits)
+}
+
+// String returns u as a plain decimal number of dollars, such as "1234.50".
+func (u USD) String() string {
+	c := u.Cents()
+	sign := ""
+	if c < 0 {
+		sign = "-"
+		c = -c
+	}
+	return fmt.Sprintf("%s%d.%02d", sign, c/100, c%100)
+}
+
+// Record is a single line item read from a sales export: Quantity units of
+// Product, at Price each.
+type Record struct {
+	Source   string
+	Channel  string
+	OrderID  string
+	Customer string
+	// BillingName is the name the order was billed to, which may be a
+	// company's, if the export gives it.
+	BillingName string
+	Product     string
+	// SKU is the product's stock-keeping unit code, if the export has
+	// one.
+	SKU string
+	// Variant is the variant of the product sold, such as a size or
+	// format, if any.
+	Variant string
+	// Country and Region give the customer's location, such as "US"
+	// and "NY", for tax reporting.
+	Country  string
+	Region   string
+	Quantity int
+	Price    USD
+	// Fee is the amount that the platform kept from the sale, if the
+	// export says.
+	Fee USD
+	// Referrer is the site that referred the customer, and Affiliate
+	// the affiliate credited with the sale, who was paid AffiliateFee.
+	Referrer     string
+	Affiliate    string
+	AffiliateFee USD
+	// Recurrence is how often the product is billed, if it's a
+	// subscription, such as "monthly"; see ParseRecurrence.
+	Recurrence string
+	// Test is set if the export marks the sale as a test purchase.
+	Test bool
+	// Currency is the currency of the order, if the export gives it.
+	Currency string
+	// Tax is the tax charged, if the export says. Exports that give it
+	// per order, such as Squarespace's, give it on the order's first line
+	// item.
+	Tax  USD
+	Time time.Time
+}
+
+// Total returns the revenue from the line item.
+func (rec Record) Total() USD {
+	return rec.Price * USD(rec.Quantity)
+}
+
+// Columns names the columns of a CSV export that hold each field of a Record.
+// Only Name and Price are required; the other columns are used if present.
+// Total, if set, names a column holding the line item's total, from which
+// the unit price is worked out for rows with a zero price.
+type Columns struct {
+	OrderID  string
+	Name     string
+	Price    string
+	Quantity string
+	Total    string
+	Channel  string
+	Created  string
+	Currency string
+	Email    string
+	// BillingName is the column of the name an order was billed to.
+	BillingName string
+	SKU         string
+	Variant     string
+	Country     string
+	Region      string
+	Fee         string
+	// Referrer, Affiliate, and AffiliateFee are the columns of
+	// Gumroad's referral and affiliate fields.
+	Referrer     string
+	Affiliate    string
+	AffiliateFee string
+	Recurrence   string
+	Test         string
+	Tax          string
+}
+
+// DefaultColumns are the column names used in Squarespace order exports.
+var DefaultColumns = Columns{
+	OrderID:      "Order ID",
+	Name:         "Lineitem name",
+	Price:        "Lineitem price",
+	Quantity:     "Lineitem quantity",
+	Channel:      "Channel Name",
+	Created:      "Created at",
+	Currency:     "Currency",
+	Email:        "Email",
+	BillingName:  "Billing Name",
+	SKU:          "Lineitem sku",
+	Variant:      "Lineitem variant",
+	Country:      "Billing Country",
+	Region:       "Billing Province",
+	Fee:          "Fee",
+	Referrer:     "Referrer",
+	Affiliate:    "Affiliate",
+	AffiliateFee: "Affiliate commission",
+	Recurrence:   "Recurrence",
+	Test:         "Test",
+	Tax:          "Taxes",
+}
+
+// SourceColumns gives the column names for sources whose file names match
+// Pattern, as used by path.Match. Its settings override those in Columns.
+type SourceColumns struct {
+	Pattern string
+	Columns Columns
+}
+
+// columnsFor returns the column names for source, with any unset names
+// taken from DefaultColumns. The first of SourceColumns matching the base
+// name of source, if any, takes precedence over Columns.
+func (r *Report) columnsFor(source string) Columns {
+	cols := r.Columns
+	for _, sc := range r.SourceColumns {
+		if ok, _ := path.Match(sc.Pattern, filepath.Base(source)); ok {
+			cols = sc.Columns.merge(cols)
+			break
+		}
+	}
+	return cols.withDefaults()
+}
+
+// merge returns c with any unset column names taken from base.
+func (c Columns) merge(base Columns) Columns {
+	set := func(name *string, def string) {
+		if *name == "" {
+			*name = def
+		}
+	}
+	set(&c.OrderID, base.OrderID)
+	set(&c.Name, base.Name)
+	set(&c.Price, base.Price)
+	set(&c.Quantity, base.Quantity)
+	set(&c.Total, base.Total)
+	set(&c.Channel, base.Channel)
+	set(&c.Created, base.Created)
+	set(&c.Currency, base.Currency)
+	set(&c.Email, base.Email)
+	set(&c.BillingName, base.BillingName)
+	set(&c.SKU, base.SKU)
+	set(&c.Variant, base.Variant)
+	set(&c.Country, base.Country)
+	set(&c.Region, base.Region)
+	set(&c.Fee, base.Fee)
+	set(&c.Referrer, base.Referrer)
+	set(&c.Affiliate, base.Affiliate)
+	set(&c.AffiliateFee, base.AffiliateFee)
+	set(&c.Recurrence, base.Recurrence)
+	set(&c.Test, base.Test)
+	set(&c.Tax, base.Tax)
+	return c
+}
+
+// withDefaults returns c with any unset column names taken from
+// DefaultColumns.
+func (c Columns) withDefaults() Columns {
+	return c.merge(DefaultColumns)
+}
+
+// Origin identifies where a record came from: the input file, and the
+// sales channel reported within it.
+type Origin struct {
+	File    string `json:"file"`
+	Channel string `json:"channel"`
+}
+
+// Tally is a count of units sold and the revenue they brought in.
+type Tally struct {
+	Units   int `json:"units"`
+	Revenue USD `json:"revenue"`
+}
+
+// SourceTotal is the sales attributed to one origin across all products.
+type SourceTotal struct {
+	Origin
+	Tally
+}
+
+// Share is the portion of a product's revenue contributed by one origin.
+type Share struct {
+	Origin
+	Revenue USD `json:"revenue"`
+	// Fraction is Revenue as a fraction of the product's revenue. Refunds
+	// can make it negative for one origin, and so more than 1 for others.
+	Fraction float64 `json:"fraction"`
+}
+
+type Product struct {
+	Name    string
+	Units   int
+	Revenue USD
+	// Comps is the number of units given away, if Report.Comps is set.
+	// They aren't included in Units.
+	Comps   int
+	origins map[Origin]Tally
+}
+
+// Provenance returns the origins that contributed to the product's revenue,
+// largest share first.
+func (p *Product) Provenance() []Share {
+	shares := make([]Share, 0, len(p.origins))
+	for o, t := range p.origins {
+		s := Share{Origin: o, Revenue: t.Revenue}
+		if p.Revenue != 0 {
+			s.Fraction = float64(t.Revenue) / float64(p.Revenue)
+		}
+		shares = append(shares, s)
+	}
+	sort.Slice(shares, func(i, j int) bool {
+		if shares[i].Revenue != shares[j].Revenue {
+			return shares[i].Revenue > shares[j].Revenue
+		}
+		if shares[i].File != shares[j].File {
+			return shares[i].File < shares[j].File
+		}
+		return shares[i].Channel < shares[j].Channel
+	})
+	return shares
+}
+
+// Limits caps how much input a report will accept, protecting long-running
+// processes from runaway inputs. A zero value means no limit.
+type Limits struct {
+	MaxFileSize int64
+	MaxRows     int64
+	MaxProducts int64
+}
+
+// LimitError reports that an input exceeded one of the configured Limits.
+type LimitError struct {
+	Limit string
+	Max   int64
+}
+
+func (e *LimitError) Error() string {
+	switch e.Limit {
+	case "max_file_size":
+		return fmt.Sprintf("input larger than %d bytes (limits.max_file_size)", e.Max)
+	case "max_rows":
+		return fmt.Sprintf("more than %d rows (limits.max_rows)", e.Max)
+	case "max_products":
+		return fmt.Sprintf("more than %d distinct products (limits.max_products)", e.Max)
+	}
+	return fmt.Sprintf("exceeded limits.%s of %d", e.Limit, e.Max)
+}
+
+type limitedReader struct {
+	r     io.Reader
+	n     int64
+	limit int64
+}
+
+func (l *limitedReader) Read(p []byte) (int, error) {
+	n, err := l.r.Read(p)
+	l.n += int64(n)
+	if l.n > l.limit {
+		return n, &LimitError{Limit: "max_file_size", Max: l.limit}
+	}
+	return n, err
+}
+
+type Report struct {
+	Limits Limits
+	// Renames normalises product names before they're grouped.
+	Renames Renames
+	Groups  Groups
+	Columns Columns
+	// SourceColumns overrides Columns for particular sources.
+	SourceColumns []SourceColumns
+	Dialect       Dialect
+	SkipBadRows   bool
+	// Color highlights the headings, totals, and negative amounts in
+	// text tables with ANSI escape codes.
+	//
+	// Color, Chart, and Sort are the only display settings left in
+	// Report. They're kept for the Write methods, such as WriteText and
+	// WriteTaxReport, which take no options. Render and RenderTo ignore
+	// them, and take theirs from RenderOptions instead.
+	Color bool
+	// Chart adds a bar chart column to the text report written by
+	// WriteText.
+	Chart Chart
+	// KeepRecords keeps every line item read, for Records, WriteSQL, and
+	// Reaggregate.
+	KeepRecords bool
+	// RecordBudget, if positive, is roughly how many bytes of memory the
+	// records kept by KeepRecords may take. Beyond it, they're spilled to
+	// a temporary file in SpillDir (or the default directory for
+	// temporary files, if it's empty), and read back as needed, so that
+	// multi-gigabyte histories can be reaggregated without running out of
+	// memory. Close removes the file.
+	RecordBudget int64
+	SpillDir     string
+	// Dedupe skips orders whose IDs have already been read, so that
+	// overlapping exports are only counted once; see Duplicates.
+	Dedupe bool
+	// Sort is the order in which Products returns the report rows, and
+	// so the order of the rows written by the Write methods.
+	Sort SortKey
+	// Currency is the currency that input amounts are expected to be
+	// in ("USD" if empty). Orders in any other currency are warned
+	// about, rejected, or converted, as CurrencyPolicy says.
+	Currency string
+	// CurrencyPolicy says what to do with orders in other currencies.
+	CurrencyPolicy CurrencyPolicy
+	// From and To restrict the report to records timestamped in
+	// [From, To). A zero value leaves that end of the range open.
+	// Records without timestamps are always included.
+	From, To time.Time
+	// Period says how sales are grouped over time.
+	Period Period
+	// Timestamps says how to read timestamps, and which time zone to
+	// report sales in.
+	Timestamps Timestamps
+	// Bundles lists the products that are bundles of others.
+	Bundles Bundles
+	// Costs gives the cost of goods for products, so that the text
+	// report can show profits.
+	Costs Costs
+	// Subscriptions recognises subscription charges among products
+	// whose exports don't give their Recurrence.
+	Subscriptions Subscriptions
+	// ConflictPolicy says how to reconcile overlapping sources; see
+	// Resolve.
+	ConflictPolicy ConflictPolicy
+	// Rounding says how the total of each line item is rounded to whole
+	// cents as it's added to the report, as payment platforms do for each
+	// charge. RoundingAudit shows the difference this makes.
+	Rounding Rounding
+	// Exclude leaves out sales such as free downloads and test
+	// purchases; see Excluded.
+	Exclude Exclusions
+	// Comps counts the units of zero-price sales, such as gifts and
+	// giveaways, as each product's Comps instead of as paid units, unless
+	// they're excluded by Exclude.
+	Comps bool
+	// Logger, if set, is given events describing the report's work:
+	// reading each input, skipping bad rows, adding rows, and
+	// rendering. Reads are logged at Info level, skipped rows at Warn,
+	// and the rest at Debug.
+	Logger *slog.Logger
+	// Filter, if set, is given each record before it's added, and
+	// records for which it returns false are left out of the report.
+	// Summary-only sources have no records, so aren't filtered. See
+	// ParseFilter.
+	Filter      func(Record) bool
+	products    map[string]*Product
+	orders      map[string]bool
+	orderIDs    map[string]string
+	timing      [7][24]Activity
+	rows        int64
+	skipped     []*RowError
+	warnings    []Warning
+	aggregates  []string
+	slots       map[slot]Tally
+	conflicts   []Conflict
+	chunk       *chunker
+	records     []Record
+	recordBytes int64
+	spill       *spillFile
+	spillErr    error
+	closed      bool
+	duplicates  int
+	daily       map[daySlot]USD
+	customers   map[string]map[string]USD
+	variants    map[variantSlot]Tally
+	taxes       map[taxSlot]Tally
+	fees        map[string]USD
+	referrers   map[referrerSlot]ReferrerTotal
+	splits      map[string]RevenueSplit
+	mrr         map[mrrSlot]USD
+	prices      map[priceSlot]int
+	excluded    map[string]int
+	residues    map[residueSlot]residueTally
+	currencies  map[currencySlot]Tally
+	items       map[itemSlot]itemTally
+	inputs      []InputStats
+	orderCount  int
+}
+
+func NewReport() *Report {
+	return &Report{
+		products:   map[string]*Product{},
+		orders:     map[string]bool{},
+		orderIDs:   map[string]string{},
+		slots:      map[slot]Tally{},
+		daily:      map[daySlot]USD{},
+		customers:  map[string]map[string]USD{},
+		variants:   map[variantSlot]Tally{},
+		taxes:      map[taxSlot]Tally{},
+		fees:       map[string]USD{},
+		referrers:  map[referrerSlot]ReferrerTotal{},
+		splits:     map[string]RevenueSplit{},
+		mrr:        map[mrrSlot]USD{},
+		prices:     map[priceSlot]int{},
+		excluded:   map[string]int{},
+		residues:   map[residueSlot]residueTally{},
+		currencies: map[currencySlot]Tally{},
+		items:      map[itemSlot]itemTally{},
+	}
+}
+
+// Add counts rec against the report row for its product, or for the group
+// that the product belongs to. Records outside the report's date range are
+// ignored. The record's time is converted to the zone set in Timestamps,
+// if any. A sale of one of the Bundles is counted as sales of its parts.
+// The product is first renamed by Renames, and records left out by Exclude
+// or rejected by Filter are ignored.
+func (r *Report) Add(rec Record) {
+	rec.Product = r.Renames.Apply(rec.Product)
+	if reason := r.Exclude.reason(rec); reason != "" {
+		r.excluded[reason]++
+		return
+	}
+	if r.Filter != nil && !r.Filter(rec) {
+		return
+	}
+	for _, part := range r.Bundles.split(rec) {
+		r.add(part)
+	}
+}
+
+func (r *Report) add(rec Record) {
+	if zone := r.Timestamps.Zone; zone != nil && !rec.Time.IsZero() {
+		rec.Time = rec.Time.In(zone)
+	}
+	if !rec.Time.IsZero() && (rec.Time.Before(r.From) || !r.To.IsZero() && !rec.Time.Before(r.To)) {
+		return
+	}
+	total := rec.Total().Round(r.Rounding)
+	var period string
+	if !rec.Time.IsZero() {
+		period = r.Period.Label(rec.Time)
+	}
+	name := rec.Product
+	var rule int
+	if i, row, ok := r.Groups.match(rec.Product, rec.SKU); ok {
+		name, rule = row, i+1
+	}
+	r.addItem(rule, rec.Product, rec.Quantity, total)
+	// An order is counted once, however many line items it has. Without
+	// an order ID, each line item is its own order. Orders of nothing
+	// but comps count too.
+	newOrder := false
+	if key := rec.Source + "\x00" + rec.OrderID; rec.OrderID == "" || !r.orders[key] {
+		r.orders[key] = true
+		r.orderCount++
+		newOrder = true
+	}
+	if newOrder && !rec.Time.IsZero() {
+		r.timing[rec.Time.Weekday()][rec.Time.Hour()].Orders++
+	}
+	if r.Comps && rec.Price == 0 {
+		r.product(name).Comps += rec.Quantity
+		return
+	}
+	r.addTally(name, Origin{File: rec.Source, Channel: rec.Channel}, period, rec.Quantity, total)
+	r.addResidue(name, period, rec.Total(), total)
+	r.addCurrency(r.currencyOf(rec), name, rec.Quantity, total)
+	v := r.variants[variantSlot{product: name, variant: rec.Variant}]
+	v.Units += rec.Quantity
+	v.Revenue += total
+	r.variants[variantSlot{product: name, variant: rec.Variant}] = v
+	r.addTax(rec, total)
+	r.addReferrer(rec, total)
+	if rec.Quantity > 0 {
+		r.prices[priceSlot{product: name, price: rec.Price.Round(r.Rounding)}] += rec.Quantity
+	}
+	if r.KeepRecords {
+		r.keep(rec)
+	}
+	if rec.Time.IsZero() {
+		// Fees without a date are kept under no month, so that they
+		// count in Totals but not in Reconcile.
+		r.fees[""] += rec.Fee
+		return
+	}
+	if rec.Customer != "" {
+		r.addCustomer(rec.Customer, rec.Time.Format("2006-01"), total)
+	}
+	r.daily[daySlot{product: name, date: rec.Time.Format(time.DateOnly)}] += total
+	r.fees[rec.Time.Format("2006-01")] += rec.Fee
+	r.addRecurring(rec, total)
+	r.timing[rec.Time.Weekday()][rec.Time.Hour()].Revenue += total
+}
+
+// addTally counts units and revenue from origin o against the report row
+// called name, in the given period ("" if unknown).
+func (r *Report) addTally(name string, o Origin, period string, units int, revenue USD) {
+	p := r.product(name)
+	p.Units += units
+	p.Revenue += revenue
+	t := p.origins[o]
+	t.Units += units
+	t.Revenue += revenue
+	p.origins[o] = t
+	s := slot{product: name, period: period, origin: o}
+	t = r.slots[s]
+	t.Units += units
+	t.Revenue += revenue
+	r.slots[s] = t
+}
+
+// product returns the report row called name, adding it if need be.
+func (r *Report) product(name string) *Product {
+	p, ok := r.products[name]
+	if !ok {
+		p = &Product{
+			Name:    name,
+			origins: map[Origin]Tally{},
+		}
+		r.products[name] = p
+		r.log(slog.LevelDebug, "adding report row", "row", name)
+	}
+	return p
+}
+
+// Activity is the number of orders placed during some period, and the revenue
+// they brought in.
+type Activity struct {
+	Orders  int
+	Revenue USD
+}
+
+// ByHour returns the orders placed in each hour of the day, in the local time
+// recorded by the export.
+func (r *Report) ByHour() [24]Activity {
+	var hours [24]Activity
+	for _, day := range r.timing {
+		for h, a := range day {
+			hours[h].Orders += a.Orders
+			hours[h].Revenue += a.Revenue
+		}
+	}
+	return hours
+}
+
+// ByWeekday returns the orders placed on each day of the week, indexed by
+// time.Weekday.
+func (r *Report) ByWeekday() [7]Activity {
+	var days [7]Activity
+	for d, day := range r.timing {
+		for _, a := range day {
+			days[d].Orders += a.Orders
+			days[d].Revenue += a.Revenue
+		}
+	}
+	return days
+}
+
+// ReadFile reads the sales export at path into the report. Files ending in
+// .json are read with ReadJSON, those ending in .ndjson or .jsonl with
+// ReadNDJSON, those ending in .parquet with ReadParquet, those ending in
+// .sql with ReadSQL, and anything else with ReadCSV. Files compressed with
+// gzip, such as "sales.csv.gz", are decompressed first.
+func (r *Report) ReadFile(path string) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	if limit := r.Limits.MaxFileSize; limit > 0 {
+		info, err := f.Stat()
+		if err != nil {
+			return err
+		}
+		if info.Size() > limit {
+			return fmt.Errorf("%s: %w", path, &LimitError{Limit: "max_file_size", Max: limit})
+		}
+	}
+	return r.readFormat(f, path, filepath.Ext(path))
+}
+
+// Read reads a sales export from rd into the report, choosing the reader
+// by the extension of the source name, as ReadFile does. It lets programs
+// without a file system, such as a browser tool built with GOOS=js, read
+// exports handed to them by name.
+func (r *Report) Read(rd io.Reader, source string) error {
+	return r.readFormat(rd, source, path.Ext(source))
+}
+
+// readFormat reads rd with the reader for files with extension ext,
+// recording its statistics for Inputs. Files ending in .gz are
+// decompressed, and read according to the extension before that, so that
+// "sales.csv.gz" is read as CSV.
+func (r *Report) readFormat(rd io.Reader, source, ext string) error {
+	return r.track(source, func() error {
+		if strings.EqualFold(ext, ".gz") {
+			zr, err := gzip.NewReader(rd)
+			if err != nil {
+				return fmt.Errorf("%s: %w", source, err)
+			}
+			defer zr.Close()
+			rd, ext = zr, path.Ext(strings.TrimSuffix(source, ext))
+		}
+		switch strings.ToLower(ext) {
+		case ".json":
+			return r.ReadJSON(rd, source)
+		case ".ndjson", ".jsonl":
+			return r.ReadNDJSON(rd, source)
+		case ".parquet":
+			return r.ReadParquet(rd, source)
+		case ".sql":
+			return r.ReadSQL(rd, source)
+		}
+		return r.ReadCSV(rd, source)
+	})
+}
+
+// RowError describes a problem with a particular row of an input file. Its
+// message gives the file and line number, so that the row can be found and
+// fixed. Line is zero for sources without lines, such as an API.
+type RowError struct {
+	File string
+	Line int
+	Err  error
+}
+
+func (e *RowError) Error() string {
+	if e.Line == 0 {
+		return fmt.Sprintf("%s: %v", e.File, e.Err)
+	}
+	return fmt.Sprintf("%s:%d: %v", e.File, e.Line, e.Err)
+}
+
+func (e *RowError) Unwrap() error {
+	return e.Err
+}
+
+// FieldError describes a value in an input row that could not be parsed.
+type FieldError struct {
+	Column string
+	Value  string
+	Kind   string
+	Err    error
+}
+
+func (e *FieldError) Error() string {
+	return fmt.Sprintf("invalid %s %q in column %q", e.Kind, e.Value, e.Column)
+}
+
+func (e *FieldError) Unwrap() error {
+	return e.Err
+}
+
+// Duplicates returns the number of orders skipped by Dedupe because their
+// IDs had already been read.
+func (r *Report) Duplicates() int {
+	return r.duplicates
+}
+
+// Skipped returns the rows that were skipped by lenient reads, in the order
+// they were encountered.
+func (r *Report) Skipped() []*RowError {
+	return r.skipped
+}
+
+// reject records e as a skipped row if lenient is set, or otherwise returns
+// it.
+func (r *Report) reject(e *RowError, lenient bool) error {
+	if lenient {
+		r.skipped = append(r.skipped, e)
+		r.log(slog.LevelWarn, "skipped bad row", "source", e.File, "line", e.Line, "err", e.Err)
+		return nil
+	}
+	return e
+}
+
+// ReadCSV reads a CSV export from rd, finding each field by the column names
+// in Columns (a Squarespace orders export by default). Input with Product,
+// Units, and Revenue columns instead is read as a pre-aggregated summary,
+// with each row added directly to the product totals. The source name is
+// recorded against each record, and used in error messages. If SkipBadRows
+// is set, malformed rows are skipped as by ReadCSVLenient.
+func (r *Report) ReadCSV(rd io.Reader, source string) error {
+	return r.readCSV(rd, source, r.SkipBadRows)
+}
+
+// ReadCSVLenient is like ReadCSV, but a malformed row doesn't abort the read.
+// Instead, the row is skipped and recorded in Skipped, and the remaining rows
+// are still counted. Errors affecting the whole input, such as a missing
+// column or an exceeded limit, are still returned.
+func (r *Report) ReadCSVLenient(rd io.Reader, source string) error {
+	return r.readCSV(rd, source, true)
+}
+
+func (r *Report) readCSV(rd io.Reader, source string, lenient bool) error {
+	cr, header, err := r.openCSV(rd, source)
+	if err == io.EOF {
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+	return r.readTable(cr, header, source, lenient)
+}
+
+// readTable reads the rows of a CSV export from cr, after its header row,
+// as ReadCSV does.
+func (r *Report) readTable(cr *csv.Reader, header []string, source string, lenient bool) error {
+	cols := r.columnsFor(source)
+	col := map[string]int{}
+	for i, name := range header {
+		col[name] = i
+	}
+	if _, ok := col[cols.Name]; !ok && isAggregate(col) {
+		return r.readAggregate(cr, header, col, source, lenient)
+	}
+	if _, ok := col[cols.Name]; !ok && isNormalized(col) {
+		cols = NormalizedColumns
+	}
+	if err := checkColumns(col, cols, source); err != nil {
+		return err
+	}
+	rr := &rowReader{r: r, source: source, header: header, col: col, cols: cols, lenient: lenient}
+	for {
+		record, err := cr.Read()
+		if err == io.EOF {
+			break
+		}
+		if err != nil {
+			var pe *csv.ParseError
+			if !errors.As(err, &pe) {
+				return fmt.Errorf("%s: %w", source, err)
+			}
+			if err := r.reject(&RowError{File: source, Line: pe.StartLine, Err: pe.Err}, lenient); err != nil {
+				return err
+			}
+			continue
+		}
+		err = rr.add(record, func(i int) int {
+			line, _ := cr.FieldPos(i)
+			return line
+		})
+		if err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// openCSV starts reading CSV input in the report's dialect, returning the
+// reader and the header row, or io.EOF if the input is empty.
+func (r *Report) openCSV(rd io.Reader, source string) (*csv.Reader, []string, error) {
+	if r.Limits.MaxFileSize > 0 {
+		rd = &limitedReader{r: rd, limit: r.Limits.MaxFileSize}
+	}
+	cr, err := r.Dialect.newReader(rd)
+	if err != nil {
+		return nil, nil, fmt.Errorf("%s: %w", source, err)
+	}
+	header, err := cr.Read()
+	if err == io.EOF {
+		return nil, nil, err
+	}
+	if err != nil {
+		return nil, nil, fmt.Errorf("%s: %w", source, err)
+	}
+	return cr, header, nil
+}
+
+// checkColumns returns an error if col lacks any of the columns that every
+// export must have.
+func checkColumns(col map[string]int, cols Columns, source string) error {
+	for _, name := range []string{cols.Name, cols.Price} {
+		if _, ok := col[name]; !ok {
+			return fmt.Errorf("%s: missing column %q", source, name)
+		}
+	}
+	return nil
+}
+
+// rowReader turns rows of named fields into records, carrying the
+// order-level fields forward from one line item to the next.
+type rowReader struct {
+	r        *Report
+	source   string
+	header   []string
+	col      map[string]int
+	cols     Columns
+	lenient  bool
+	order    string
+	channel  string
+	customer string
+	billing  string
+	country  string
+	region   string
+	currency string
+	created  time.Time
+	// duplicate is set while reading the line items of an order that
+	// has already been counted.
+	duplicate bool
+	// emit, if set, is given each record instead of adding it to the
+	// report.
+	emit func(Record)
+}
+
+// add adds the sales in record, whose fields are named by header. The line
+// function gives the line number of field i, for error messages.
+func (rr *rowReader) add(record []string, line func(i int) int) error {
+	r, col, cols := rr.r, rr.col, rr.cols
+	// invalid reports a field in the current record that couldn't be
+	// parsed.
+	invalid := func(i int, value, kind string, err error) *RowError {
+		return &RowError{
+			File: rr.source,
+			Line: line(i),
+			Err:  &FieldError{Column: rr.header[i], Value: value, Kind: kind, Err: err},
+		}
+	}
+	if err := r.countRow(rr.source); err != nil {
+		return err
+	}
+	// Additional line items in an order leave the order-level fields
+	// blank. Without an order ID, every row is its own order.
+	var id string
+	if i, ok := col[cols.OrderID]; ok {
+		id = record[i]
+	}
+	if id != rr.order || id == "" {
+		rr.order = id
+		rr.channel = ""
+		rr.customer = ""
+		rr.billing = ""
+		rr.country = ""
+		rr.region = ""
+		rr.currency = ""
+		rr.created = time.Time{}
+		rr.duplicate = false
+		if first, ok := r.orderIDs[id]; ok && id != "" {
+			if r.Dedupe {
+				rr.duplicate = true
+				r.duplicates++
+			} else {
+				r.warn(rr.source, line(col[cols.OrderID]), "duplicate order ID %q (first seen in %s)", id, first)
+			}
+		} else {
+			r.orderIDs[id] = rr.source
+		}
+	}
+	if rr.duplicate {
+		return nil
+	}
+	if i, ok := col[cols.Channel]; ok && record[i] != "" {
+		rr.channel = record[i]
+	}
+	if i, ok := col[cols.Email]; ok && record[i] != "" {
+		rr.customer = strings.ToLower(strings.TrimSpace(record[i]))
+	}
+	if i, ok := col[cols.BillingName]; ok && record[i] != "" {
+		rr.billing = strings.TrimSpace(record[i])
+	}
+	if i, ok := col[cols.Country]; ok && record[i] != "" {
+		rr.country = strings.TrimSpace(record[i])
+	}
+	if i, ok := col[cols.Region]; ok && record[i] != "" {
+		rr.region = strings.TrimSpace(record[i])
+	}
+	currency := r.reportCurrency()
+	if i, ok := col[cols.Currency]; ok && record[i] != "" {
+		rr.currency = strings.ToUpper(strings.TrimSpace(record[i]))
+		mode := r.CurrencyPolicy.Mode
+		if rr.currency != currency && (mode == "" || mode == "warn") {
+			r.warn(rr.source, line(i), "order %q is in %s, not %s", rr.order, rr.currency, currency)
+		}
+	}
+	if i, ok := col[cols.Created]; ok && record[i] != "" {
+		created, err := r.Timestamps.Parse(record[i])
+		if err != nil {
+			return r.reject(invalid(i, record[i], "timestamp", err), rr.lenient)
+		}
+		rr.created = created
+	}
+	i := col[cols.Price]
+	price, err := ParseUSD(record[i])
+	if err != nil {
+		return r.reject(invalid(i, record[i], "price", err), rr.lenient)
+	}
+	quantity := 1
+	if i, ok := col[cols.Quantity]; ok {
+		quantity, err = strconv.Atoi(record[i])
+		if err != nil {
+			return r.reject(invalid(i, record[i], "quantity", err), rr.lenient)
+		}
+		if quantity < 0 {
+			r.warn(rr.source, line(i), "negative quantity %d", quantity)
+		}
+	}
+	product := record[col[cols.Name]]
+	var fee USD
+	if i, ok := col[cols.Fee]; ok && record[i] != "" {
+		fee, err = ParseUSD(record[i])
+		if err != nil {
+			return r.reject(invalid(i, record[i], "fee", err), rr.lenient)
+		}
+	}
+	var tax USD
+	if i, ok := col[cols.Tax]; ok && record[i] != "" {
+		tax, err = ParseUSD(record[i])
+		if err != nil {
+			return r.reject(invalid(i, record[i], "tax", err), rr.lenient)
+		}
+	}
+	var affiliateFee USD
+	if i, ok := col[cols.AffiliateFee]; ok && record[i] != "" {
+		affiliateFee, err = ParseUSD(record[i])
+		if err != nil {
+			return r.reject(invalid(i, record[i], "affiliate fee", err), rr.lenient)
+		}
+	}
+	var sku, variant, referrer, affiliate, recurrence string
+	if i, ok := col[cols.SKU]; ok {
+		sku = record[i]
+	}
+	if i, ok := col[cols.Variant]; ok {
+		variant = record[i]
+	}
+	if i, ok := col[cols.Referrer]; ok {
+		referrer = record[i]
+	}
+	if i, ok := col[cols.Affiliate]; ok {
+		affiliate = record[i]
+	}
+	if i, ok := col[cols.Recurrence]; ok && record[i] != "" {
+		if _, err := ParseRecurrence(record[i]); err != nil {
+			return r.reject(invalid(i, record[i], "recurrence", err), rr.lenient)
+		}
+		recurrence = record[i]
+	}
+	// Some exports, such as Gumroad's, leave the price zero but give the
+	// line item's total, so work out the unit price from that.
+	if i, ok := col[cols.Total]; ok && price == 0 && quantity > 0 && record[i] != "" {
+		total, err := ParseUSD(record[i])
+		if err != nil {
+			return r.reject(invalid(i, record[i], "total", err), rr.lenient)
+		}
+		price = total / USD(quantity)
+	}
+	test := false
+	if i, ok := col[cols.Test]; ok {
+		test = isTestFlag(record[i])
+	}
+	if price == 0 && !r.Exclude.ZeroPrice {
+		r.warn(rr.source, line(col[cols.Price]), "zero price for %q", product)
+	}
+	if _, ok := r.products[r.Groups.ClassifyItem(r.Renames.Apply(product), sku)]; !ok {
+		if limit := r.Limits.MaxProducts; limit > 0 && int64(len(r.products)) >= limit {
+			return &RowError{File: rr.source, Line: line(col[cols.Name]), Err: &LimitError{Limit: "max_products", Max: limit}}
+		}
+	}
+	rec := Record{
+		Source:       rr.source,
+		Channel:      rr.channel,
+		OrderID:      rr.order,
+		Customer:     rr.customer,
+		BillingName:  rr.billing,
+		Product:      product,
+		SKU:          sku,
+		Variant:      variant,
+		Country:      rr.country,
+		Region:       rr.region,
+		Quantity:     quantity,
+		Price:        price,
+		Fee:          fee,
+		Referrer:     referrer,
+		Affiliate:    affiliate,
+		AffiliateFee: affiliateFee,
+		Recurrence:   recurrence,
+		Test:         test,
+		Currency:     rr.currency,
+		Tax:          tax,
+		Time:         rr.created,
+	}
+	if rec.Currency != "" && rec.Currency != currency {
+		rec, err = r.CurrencyPolicy.apply(rec, currency)
+		if err != nil {
+			return r.reject(&RowError{File: rr.source, Line: line(col[cols.Price]), Err: err}, rr.lenient)
+		}
+	}
+	if rr.emit != nil {
+		rr.emit(rec)
+		return nil
+	}
+	r.Add(rec)
+	return nil
+}
+
+// SortKey is an order for the rows of a report.
+type SortKey int
+
+const (
+	// SortByName orders rows by product name.
+	SortByName SortKey = iota
+	// SortByRevenue orders rows by revenue, largest first.
+	SortByRevenue
+	// SortByUnits orders rows by units sold, most first.
+	SortByUnits
+)
+
+// ParseSortKey parses the name of a sort order: "name", "revenue", or
+// "units".
+func ParseSortKey(s string) (SortKey, error) {
+	switch strings.ToLower(strings.TrimSpace(s)) {
+	case "name":
+		return SortByName, nil
+	case "revenue":
+		return SortByRevenue, nil
+	case "units":
+		return SortByUnits, nil
+	}
+	return 0, fmt.Errorf("unknown sort order %q (want name, revenue, or units)", s)
+}
+
+// Products returns the products in the report, in the order given by Sort.
+// Products that tie are in name order.
+func (r *Report) Products() []*Product {
+	return r.sortedProducts(r.Sort)
+}
+
+// sortedProducts returns the products in the report, in the order given by
+// key.
+func (r *Report) sortedProducts(key SortKey) []*Product {
+	products := make([]*Product, 0, len(r.products))
+	for _, p := range r.products {
+		products = append(products, p)
+	}
+	sort.Slice(products, func(i, j int) bool {
+		a, b := products[i], products[j]
+		switch {
+		case key == SortByRevenue && a.Revenue != b.Revenue:
+			return a.Revenue > b.Revenue
+		case key == SortByUnits && a.Units != b.Units:
+			return a.Units > b.Units
+		}
+		return a.Name < b.Name
+	})
+	return products
+}
+
+// IsEmpty reports whether the report has no sales, either because no
+// input has been read, or because none of it was in the date range.
+func (r *Report) IsEmpty() bool {
+	return len(r.products) == 0
+}
+
+func (r *Report) TotalUnits() int {
+	var total int
+	for _, p := range r.products {
+		total += p.Units
+	}
+	return total
+}
+
+// TotalComps returns the number of units given away across all products;
+// see Comps.
+func (r *Report) TotalComps() int {
+	var total int
+	for _, p := range r.products {
+		total += p.Comps
+	}
+	return total
+}
+
+func (r *Report) TotalRevenue() USD {
+	var total USD
+	for _, p := range r.products {
+		total += p.Revenue
+	}
+	return total
+}
+
+// BySource returns the units and revenue contributed by each origin, largest
+// revenue first.
+func (r *Report) BySource() []SourceTotal {
+	totals := map[Origin]Tally{}
+	for _, p := range r.products {
+		for o, t := range p.origins {
+			sum := totals[o]
+			sum.Units += t.Units
+			sum.Revenue += t.Revenue
+			totals[o] = sum
+		}
+	}
+	sources := make([]SourceTotal, 0, len(totals))
+	for o, t := range totals {
+		sources = append(sources, SourceTotal{Origin: o, Tally: t})
+	}
+	sort.Slice(sources, func(i, j int) bool {
+		if sources[i].Revenue != sources[j].Revenue {
+			return sources[i].Revenue > sources[j].Revenue
+		}
+		if sources[i].File != sources[j].File {
+			return sources[i].File < sources[j].File
+		}
+		return sources[i].Channel < sources[j].Channel
+	})
+	return sources
+}
+
+// WriteSources writes the per-source breakdown as a text table.
+func (r *Report) WriteSources(w io.Writer) error {
+	t := &table{
+		header: []string{"File", "Channel", "Units", "Revenue"},
+		right:  []bool{false, false, true, true},
+		color:  r.Color,
+	}
+	for _, s := range r.BySource() {
+		t.add(s.File, s.Channel, strconv.Itoa(s.Units), s.Revenue.String())
+	}
+	t.footer = []string{"Total", "", strconv.Itoa(r.TotalUnits()), r.TotalRevenue().String()}
+	return t.write(w)
+}
+
+// WriteTiming writes histograms of orders by hour of day and by day of week.
+func (r *Report) WriteTiming(w io.Writer) error {
+	fmt.Fprintln(w, "Hour  Orders Revenue")
+	for h, a := range r.ByHour() {
+		fmt.Fprintf(w, "%02d:00 %6d %.2f\n", h, a.Orders, a.Revenue.Dollars())
+	}
+	fmt.Fprintln(w)
+	fmt.Fprintln(w, "Day       Orders Revenue")
+	for d, a := range r.ByWeekday() {
+		fmt.Fprintf(w, "%-9s %6d %.2f\n", time.Weekday(d), a.Orders, a.Revenue.Dollars())
+	}
+	if len(r.aggregates) > 0 {
+		fmt.Fprintln(w)
+		fmt.Fprintln(w, "Excludes summary-only sources:", strings.Join(r.aggregates, ", "))
+	}
+	return nil
+}
+
+// WriteText writes the report as a text table, with a row for each product
+// and a row of totals, laid out by the report's Sort, Chart, and Color
+// settings; see Display. If Chart is set, each product row also has a bar
+// showing its share of the largest product's revenue or units. If Costs
+// are set, there are also columns for each product's cost of goods, gross
+// profit, and margin. An empty report is written as a message saying that
+// there were no sales.
+func (r *Report) WriteText(w io.Writer) error {
+	return r.RenderTo(w, r.Display())
+}
+
+// writeText writes the text report, laid out according to opts.
+func (r *Report) writeText(w io.Writer, opts RenderOptions) error {
+	if r.IsEmpty() {
+		msg := "No sales.\n"
+		if !r.From.IsZero() || !r.To.IsZero() {
+			msg = "No sales in the selected range.\n"
+		}
+		_, err := io.WriteString(w, msg)
+		return err
+	}
+	r.logRender("text")
+	money := USD.String
+	if opts.Locale != (Locale{}) {
+		money = func(u USD) string {
+			return opts.Locale.FormatCurrency(u, r.reportCurrency())
+		}
+	}
+	cols := opts.Columns
+	if cols == nil {
+		cols = r.defaultColumns()
+	}
+	t := &table{
+		header: []string{"Product"},
+		right:  []bool{false},
+		color:  opts.Color,
+		width:  opts.Width,
+	}
+	for _, col := range cols {
+		t.header = append(t.header, col.title())
+		t.right = append(t.right, true)
+	}
+	total := r.TotalRevenue()
+	products := r.sortedProducts(opts.Sort)
+	var limit int64
+	for _, p := range products {
+		limit = max(limit, chartValue(opts.Chart, p))
+	}
+	// The totals of cost and profit are for the products whose costs are
+	// known, whether or not they're among the Top rows shown.
+	var costs, otherCosts rowCost
+	var other Product
+	for i, p := range products {
+		var c rowCost
+		c.cost, c.known = r.Costs.Cost(p)
+		if c.known {
+			c.revenue = p.Revenue
+			costs.known = true
+			costs.cost += c.cost
+			costs.revenue += p.Revenue
+		}
+		if opts.Top > 0 && i >= opts.Top {
+			other.Units += p.Units
+			other.Revenue += p.Revenue
+			other.Comps += p.Comps
+			if c.known {
+				otherCosts.known = true
+				otherCosts.cost += c.cost
+				otherCosts.revenue += p.Revenue
+			}
+			continue
+		}
+		row := append([]string{p.Name}, cells(cols, *p, c, total, money)...)
+		if opts.Chart != NoChart {
+			row = append(row, bar(chartValue(opts.Chart, p), limit))
+		}
+		t.add(row...)
+	}
+	if n := len(products) - opts.Top; opts.Top > 0 && n > 0 {
+		row := append([]string{opts.otherLabel(n)}, cells(cols, other, otherCosts, total, money)...)
+		if opts.Chart != NoChart {
+			row = append(row, "")
+		}
+		t.add(row...)
+	}
+	if !opts.HideTotals {
+		totals := Product{Units: r.TotalUnits(), Revenue: total, Comps: r.TotalComps()}
+		t.footer = append([]string{"Total"}, cells(cols, totals, costs, total, money)...)
+	}
+	if opts.Chart != NoChart {
+		t.header = append(t.header, "")
+		t.right = append(t.right, false)
+		if t.footer != nil {
+			t.footer = append(t.footer, "")
+		}
+	}
+	return t.write(w)
+}
+
+// chartValue returns the measure of p shown by chart.
+func chartValue(chart Chart, p *Product) int64 {
+	switch chart {
+	case ChartRevenue:
+		return int64(p.Revenue)
+	case ChartUnits:
+		return int64(p.Units)
+	}
+	return 0
+}
+
+type jsonShare struct {
+	File     string  `json:"file"`
+	Channel  string  `json:"channel"`
+	Revenue  float64 `json:"revenue"`
+	Fraction float64 `json:"fraction"`
+}
+
+type jsonProduct struct {
+	Name         string      `json:"name"`
+	Units        int         `json:"units"`
+	Revenue      float64     `json:"revenue"`
+	AveragePrice *float64    `json:"average_price,omitempty"`
+	Share        *float64    `json:"share,omitempty"`
+	Free         *int        `json:"free,omitempty"`
+	Cost         *float64    `json:"cost,omitempty"`
+	Profit       *float64    `json:"profit,omitempty"`
+	Margin       *float64    `json:"margin,omitempty"`
+	Provenance   []jsonShare `json:"provenance"`
+}
+
+// setColumns sets the optional fields of jp, for the product p, that are
+// chosen by cols. Its share is of total revenue.
+func (jp *jsonProduct) setColumns(cols []OutputColumn, p *Product, c rowCost, total USD) {
+	dollars := func(u USD) *float64 {
+		d := u.Dollars()
+		return &d
+	}
+	fraction := func(part, whole USD) *float64 {
+		if whole == 0 {
+			return nil
+		}
+		f := float64(part) / float64(whole)
+		return &f
+	}
+	for _, col := range cols {
+		switch col {
+		case ColumnAverage:
+			if p.Units != 0 {
+				jp.AveragePrice = dollars(p.Revenue / USD(p.Units))
+			}
+		case ColumnPercent:
+			jp.Share = fraction(p.Revenue, total)
+		case ColumnFree:
+			free := p.Comps
+			jp.Free = &free
+		case ColumnCost:
+			if c.known {
+				jp.Cost = dollars(c.cost)
+			}
+		case ColumnProfit:
+			if c.known {
+				jp.Profit = dollars(c.revenue - c.cost)
+			}
+		case ColumnMargin:
+			if c.known {
+				jp.Margin = fraction(c.revenue-c.cost, c.revenue)
+			}
+		}
+	}
+}
+
+type jsonReport struct {
+	SchemaVersion int           `json:"schema_version"`
+	Products      []jsonProduct `json:"products"`
+	TotalUnits    int           `json:"total_units"`
+	TotalRevenue  float64       `json:"total_revenue"`
+}
+
+// WriteJSON writes the report as JSON, in the format described by
+// JSONSchemaV1.
+func (r *Report) WriteJSON(w io.Writer) error {
+	return r.WriteJSONColumns(w, nil)
+}
+
+// WriteJSONColumns is like WriteJSON, but adds the optional fields for the
+// columns cols to each product: average_price for ColumnAverage, share for
+// ColumnPercent, and free, cost, profit, and margin. The name, units,
+// revenue, and provenance are always included, as the schema requires.
+func (r *Report) WriteJSONColumns(w io.Writer, cols []OutputColumn) error {
+	r.logRender("json")
+	total := r.TotalRevenue()
+	out := jsonReport{
+		SchemaVersion: JSONSchemaVersion,
+		Products:      []jsonProduct{},
+		TotalUnits:    r.TotalUnits(),
+		TotalRevenue:  total.Dollars(),
+	}
+	for _, p := range r.Products() {
+		jp := jsonProduct{
+			Name:       p.Name,
+			Units:      p.Units,
+			Revenue:    p.Revenue.Dollars(),
+			Provenance: []jsonShare{},
+		}
+		var c rowCost
+		c.cost, c.known = r.Costs.Cost(p)
+		c.revenue = p.Revenue
+		jp.setColumns(cols, p, c, total)
+		for _, s := range p.Provenance() {
+			jp.Provenance = append(jp.Provenance, jsonShare{
+				File:     s.File,
+				Channel:  s.Channel,
+				Revenue:  s.Revenue.Dollars(),
+				Fraction: s.Fraction,
+			})
+		}
+		out.Products = append(out.Products, jp)
+	}
+	enc := json.NewEncoder(w)
+	enc.SetIndent("", "  ")
+	return enc.Encode(out)
+}
+
+func (r *Report) WriteCSV(w io.Writer) error {
+	return r.WriteCSVColumns(w, []OutputColumn{ColumnUnits, ColumnRevenue})
+}
+
+// WriteCSVColumns is like WriteCSV, but with the columns cols, instead of
+// the units and revenue, between the product name and its provenance. If
+// cols is nil, the columns are those of WriteText.
+func (r *Report) WriteCSVColumns(w io.Writer, cols []OutputColumn) error {
+	r.logRender("csv")
+	if cols == nil {
+		cols = r.defaultColumns()
+	}
+	header := []string{"Product"}
+	for _, col := range cols {
+		header = append(header, col.title())
+	}
+	cw := csv.NewWriter(w)
+	cw.Write(append(header, "Provenance"))
+	total := r.TotalRevenue()
+	for _, p := range r.Products() {
+		var prov []string
+		for _, s := range p.Provenance() {
+			prov = append(prov, fmt.Sprintf("%s [%s] %.1f%%", s.File, s.Channel, s.Fraction*100))
+		}
+		var c rowCost
+		c.cost, c.known = r.Costs.Cost(p)
+		c.revenue = p.Revenue
+		row := append([]string{p.Name}, cells(cols, *p, c, total, USD.String)...)
+		cw.Write(append(row, strings.Join(prov, "; ")))
+	}
+	cw.Flush()
+	return cw.Error()
+}
@@ -0,0 +1,165 @@
+// Package salestest generates realistic, but entirely fake, sales data,
+// for benchmarks, tests, and demonstrations, so that nobody needs to share
+// real orders to try out the sales package or to show a bug in it.
+//
+// The data is generated from a seed, so the same seed always gives the
+// same data.
+package salestest
+
+import (
+	"encoding/csv"
+	"fmt"
+	"io"
+	"math/rand"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// product is a fake product, with its list price, and how popular it is
+// relative to the others.
+type product struct {
+	name   string
+	sku    string
+	price  sales.USD
+	weight int
+}
+
+var products = []product{
+	{"The Power of Go: Tools", "POG-TOOLS", sales.NewUSD(44.95), 30},
+	{"The Power of Go: Tests", "POG-TESTS", sales.NewUSD(44.95), 25},
+	{"Know Go: Generics", "KG-GEN", sales.NewUSD(29.95), 20},
+	{"For the Love of Go", "FTLOG", sales.NewUSD(39.95), 40},
+	{"Code For Your Life", "CFYL", sales.NewUSD(39.95), 10},
+	{"Explore Go: Cryptography", "EG-CRYPTO", sales.NewUSD(24.95), 8},
+	{"Go Mentoring (1 hour)", "MENTOR-1H", sales.NewUSD(150), 3},
+	{"Go Video Course", "VIDEO", sales.NewUSD(99), 6},
+	{"Sticker Pack", "STICKERS", sales.NewUSD(5), 12},
+	{"Gift Card", "GIFT", sales.NewUSD(25), 4},
+}
+
+var countries = []struct {
+	country, region string
+}{
+	{"United States", "CA"}, {"United States", "NY"}, {"United States", "TX"},
+	{"United Kingdom", "England"}, {"Germany", ""}, {"Australia", "QLD"},
+	{"India", ""}, {"Canada", "ON"}, {"France", ""}, {"Netherlands", ""},
+}
+
+var domains = []string{"gmail.com", "outlook.com", "yahoo.com", "example.com", "example.org", "icloud.com"}
+
+// Start is the time of the first generated order.
+var Start = time.Date(2023, time.January, 1, 0, 0, 0, 0, time.UTC)
+
+// Records returns n line items of fake sales, in order of time, starting
+// at Start, generated from seed. They can be added to a report with
+// Report.Add, or written as an export with WriteSquarespaceCSV, or with
+// the report's writers, such as WriteNormalizedCSV. Orders have one to three line items, come
+// more often in the daytime and on weekdays, and are mostly, but not all,
+// at the list price; about one in fifty is a refund.
+func Records(n int, seed int64) []sales.Record {
+	rng := rand.New(rand.NewSource(seed))
+	total := 0
+	for _, p := range products {
+		total += p.weight
+	}
+	pick := func() product {
+		w := rng.Intn(total)
+		for _, p := range products {
+			if w < p.weight {
+				return p
+			}
+			w -= p.weight
+		}
+		return products[0]
+	}
+	recs := make([]sales.Record, 0, n)
+	t := Start
+	for order := 1; len(recs) < n; order++ {
+		// Orders come every half an hour or so, on average, but twice as
+		// often in the working day as at night.
+		gap := time.Duration(rng.ExpFloat64() * float64(30*time.Minute))
+		if h := t.Hour(); h < 8 || h > 20 || t.Weekday() == time.Saturday || t.Weekday() == time.Sunday {
+			gap *= 2
+		}
+		t = t.Add(gap).Truncate(time.Second)
+		where := countries[rng.Intn(len(countries))]
+		customer := fmt.Sprintf("customer%d@%s", rng.Intn(n/2+1), domains[rng.Intn(len(domains))])
+		refund := rng.Intn(50) == 0
+		for items := 1 + rng.Intn(3); items > 0 && len(recs) < n; items-- {
+			p := pick()
+			price := p.price
+			switch rng.Intn(10) {
+			case 0:
+				price = price.Mul(0.8) // a discount code
+			case 1:
+				price = price.Mul(0.5)
+			}
+			qty := 1
+			if rng.Intn(20) == 0 {
+				qty = 2 + rng.Intn(9)
+			}
+			if refund {
+				qty = -qty
+			}
+			recs = append(recs, sales.Record{
+				Source:   "generated",
+				Channel:  "Squarespace",
+				OrderID:  fmt.Sprintf("%06d", order),
+				Customer: customer,
+				Product:  p.name,
+				SKU:      p.sku,
+				Country:  where.country,
+				Region:   where.region,
+				Quantity: qty,
+				Price:    price.Round(sales.RoundHalfUp),
+				Currency: "USD",
+				Time:     t,
+			})
+		}
+	}
+	return recs
+}
+
+// Groups returns product groups matching the generated products, as a
+// groups file might give them.
+func Groups() sales.Groups {
+	g, err := sales.ParseGroups(strings.NewReader(groupsFile))
+	if err != nil {
+		panic(err)
+	}
+	return g
+}
+
+const groupsFile = `The Power of Go = ^The Power of Go
+Know Go = ^Know Go | ^Explore Go
+Beginners = For the Love of Go | Code For Your Life
+Services = (?i)mentoring | sku:VIDEO
+Merchandise = sku:STICKERS | sku:GIFT
+`
+
+// WriteSquarespaceCSV writes recs as a Squarespace order export, with the
+// columns the sales package reads by default.
+func WriteSquarespaceCSV(w io.Writer, recs []sales.Record) error {
+	cw := csv.NewWriter(w)
+	cw.Write([]string{"Order ID", "Email", "Created at", "Currency", "Lineitem quantity", "Lineitem name", "Lineitem price", "Lineitem sku", "Billing Province", "Billing Country", "Channel Name"})
+	for _, rec := range recs {
+		cw.Write([]string{
+			rec.OrderID,
+			rec.Customer,
+			rec.Time.Format("2006-01-02 15:04:05 -0700"),
+			rec.Currency,
+			strconv.Itoa(rec.Quantity),
+			rec.Product,
+			rec.Price.String(),
+			rec.SKU,
+			rec.Region,
+			rec.Country,
+			rec.Channel,
+		})
+	}
+	cw.Flush()
+	return cw.Error()
+}
@@ -0,0 +1,54 @@
+package sales
+
+// JSONSchemaVersion is the version of the JSON report format written by
+// WriteJSON, and given in its schema_version field. Within a version, fields
+// are only ever added, never removed or changed in meaning, so consumers
+// validating against that version's schema keep working.
+const JSONSchemaVersion = 1
+
+// JSONSchemaV1 is the JSON Schema for version 1 of the JSON report format.
+const JSONSchemaV1 = `{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "https://github.com/bitfield/sales/schema/report-v1.json",
+  "title": "Sales report",
+  "type": "object",
+  "required": ["schema_version", "products", "total_units", "total_revenue"],
+  "properties": {
+    "schema_version": {"const": 1},
+    "products": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["name", "units", "revenue", "provenance"],
+        "properties": {
+          "name": {"type": "string"},
+          "units": {"type": "integer"},
+          "revenue": {"type": "number", "description": "US dollars"},
+          "average_price": {"type": "number", "description": "US dollars per unit, if chosen"},
+          "share": {"type": "number", "description": "Fraction of the total revenue, if chosen"},
+          "free": {"type": "integer", "description": "Units given away, if chosen"},
+          "cost": {"type": "number", "description": "Cost of goods in US dollars, if chosen and known"},
+          "profit": {"type": "number", "description": "US dollars, if chosen and the cost is known"},
+          "margin": {"type": "number", "description": "Profit as a fraction of revenue, if chosen and the cost is known"},
+          "provenance": {
+            "type": "array",
+            "description": "Sources of the product's revenue, largest share first",
+            "items": {
+              "type": "object",
+              "required": ["file", "channel", "revenue", "fraction"],
+              "properties": {
+                "file": {"type": "string"},
+                "channel": {"type": "string"},
+                "revenue": {"type": "number", "description": "US dollars"},
+                "fraction": {"type": "number", "description": "Share of the product's revenue; negative for a source whose refunds outweigh its sales, and so more than 1 for the others"}
+              }
+            }
+          }
+        }
+      }
+    },
+    "total_units": {"type": "integer"},
+    "total_revenue": {"type": "number", "description": "US dollars"}
+  }
+}
+`
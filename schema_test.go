@@ -0,0 +1,113 @@
+package sales_test
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"math"
+	"testing"
+
+	"github.com/bitfield/sales"
+	"github.com/bitfield/sales/salestest"
+)
+
+// validate checks v against the parts of JSON Schema used by JSONSchemaV1:
+// type, const, required, properties, items, minimum, and maximum.
+func validate(t *testing.T, schema map[string]any, v any, path string) {
+	t.Helper()
+	if c, ok := schema["const"]; ok && v != c {
+		t.Errorf("%s: want %v, got %v", path, c, v)
+	}
+	switch schema["type"] {
+	case "object":
+		obj, ok := v.(map[string]any)
+		if !ok {
+			t.Errorf("%s: want object, got %T", path, v)
+			return
+		}
+		required, _ := schema["required"].([]any)
+		for _, name := range required {
+			if _, ok := obj[name.(string)]; !ok {
+				t.Errorf("%s: missing required field %q", path, name)
+			}
+		}
+		props, _ := schema["properties"].(map[string]any)
+		for name, field := range obj {
+			if ps, ok := props[name].(map[string]any); ok {
+				validate(t, ps, field, path+"."+name)
+			}
+		}
+	case "array":
+		arr, ok := v.([]any)
+		if !ok {
+			t.Errorf("%s: want array, got %T", path, v)
+			return
+		}
+		items, _ := schema["items"].(map[string]any)
+		for i, item := range arr {
+			validate(t, items, item, fmt.Sprintf("%s[%d]", path, i))
+		}
+	case "string":
+		if _, ok := v.(string); !ok {
+			t.Errorf("%s: want string, got %T", path, v)
+		}
+	case "number", "integer":
+		n, ok := v.(float64)
+		if !ok {
+			t.Errorf("%s: want number, got %T", path, v)
+			return
+		}
+		if schema["type"] == "integer" && n != math.Trunc(n) {
+			t.Errorf("%s: want integer, got %v", path, n)
+		}
+		if min, ok := schema["minimum"].(float64); ok && n < min {
+			t.Errorf("%s: want at least %v, got %v", path, min, n)
+		}
+		if max, ok := schema["maximum"].(float64); ok && n > max {
+			t.Errorf("%s: want at most %v, got %v", path, max, n)
+		}
+	}
+}
+
+// validateReport checks that r's JSON output matches JSONSchemaV1.
+func validateReport(t *testing.T, r *sales.Report, cols []sales.OutputColumn) {
+	t.Helper()
+	var schema map[string]any
+	if err := json.Unmarshal([]byte(sales.JSONSchemaV1), &schema); err != nil {
+		t.Fatalf("parsing schema: %v", err)
+	}
+	var buf bytes.Buffer
+	if err := r.WriteJSONColumns(&buf, cols); err != nil {
+		t.Fatal(err)
+	}
+	var out any
+	if err := json.Unmarshal(buf.Bytes(), &out); err != nil {
+		t.Fatalf("parsing output: %v", err)
+	}
+	validate(t, schema, out, "report")
+}
+
+func TestWriteJSONOutputMatchesSchema(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	for _, rec := range salestest.Records(200, 1) {
+		r.Add(rec)
+	}
+	validateReport(t, r, nil)
+	validateReport(t, r, []sales.OutputColumn{sales.ColumnAverage, sales.ColumnPercent, sales.ColumnFree})
+}
+
+func TestWriteJSONOutputMatchesSchemaWhenRefundsOutweighSales(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Add(sales.Record{Source: "sales.csv", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{Source: "refunds.csv", Product: "Book", Quantity: 1, Price: sales.NewUSD(-30)})
+	validateReport(t, r, nil)
+	shares := r.Products()[0].Provenance()
+	if len(shares) != 2 {
+		t.Fatalf("want 2 shares, got %d", len(shares))
+	}
+	if shares[0].Fraction != -0.5 || shares[1].Fraction != 1.5 {
+		t.Errorf("want fractions -0.5 and 1.5, got %v and %v", shares[0].Fraction, shares[1].Fraction)
+	}
+}
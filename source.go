@@ -0,0 +1,115 @@
+package sales
+
+import (
+	"errors"
+	"fmt"
+	"io"
+)
+
+// Source is a source of sales records in some format that the report
+// doesn't read itself, such as a proprietary platform's export. Implement
+// it to add records with Ingest, which applies the report's limits,
+// deduplication, and lenient reading just as for the built-in formats.
+type Source interface {
+	// Name identifies the source in error messages, and is recorded as
+	// the Source of records that don't give one.
+	Name() string
+	// Next returns the next record, or io.EOF when there are no more. An
+	// error of type *RowError means only that record is bad, and the
+	// rest of the source can still be read.
+	Next() (Record, error)
+}
+
+// Ingest reads every record from src into the report. If SkipBadRows is
+// set, records for which src returns a *RowError are skipped and recorded
+// in Skipped; otherwise, any error stops the read. The read's statistics
+// are recorded for Inputs.
+func (r *Report) Ingest(src Source) error {
+	return r.track(src.Name(), func() error {
+		return r.ingest(src)
+	})
+}
+
+func (r *Report) ingest(src Source) error {
+	name := src.Name()
+	// duplicate records, for each order ID in this source, whether it
+	// was already in the report before the source was read. An order
+	// first seen in this source isn't a duplicate, however many line
+	// items it has.
+	duplicate := map[string]bool{}
+	for {
+		rec, err := src.Next()
+		if err == io.EOF {
+			return nil
+		}
+		if err != nil {
+			var re *RowError
+			if errors.As(err, &re) {
+				if err := r.reject(re, r.SkipBadRows); err != nil {
+					return err
+				}
+				continue
+			}
+			return fmt.Errorf("%s: %w", name, err)
+		}
+		if rec.Source == "" {
+			rec.Source = name
+		}
+		if err := r.countRow(name); err != nil {
+			return err
+		}
+		// A CSVSource applies the CurrencyPolicy as it reads, when it
+		// knows each row's line.
+		if _, ok := src.(*CSVSource); !ok {
+			if rec, err = r.applyCurrencyPolicy(name, rec); err != nil {
+				if err := r.reject(&RowError{File: name, Err: err}, r.SkipBadRows); err != nil {
+					return err
+				}
+				continue
+			}
+		}
+		if id := rec.OrderID; id != "" {
+			if _, ok := duplicate[id]; !ok {
+				first, dup := r.orderIDs[id]
+				duplicate[id] = dup
+				switch {
+				case !dup:
+					r.orderIDs[id] = name
+				case r.Dedupe:
+					r.duplicates++
+				default:
+					r.warn(name, 0, "duplicate order ID %q (first seen in %s)", id, first)
+				}
+			}
+			if duplicate[id] && r.Dedupe {
+				continue
+			}
+		}
+		if _, ok := r.products[r.Groups.ClassifyItem(r.Renames.Apply(rec.Product), rec.SKU)]; !ok {
+			if limit := r.Limits.MaxProducts; limit > 0 && int64(len(r.products)) >= limit {
+				return fmt.Errorf("%s: %w", name, &LimitError{Limit: "max_products", Max: limit})
+			}
+		}
+		r.Add(rec)
+	}
+}
+
+// RecordSource is a Source that returns records from a slice, for records
+// built in memory.
+type RecordSource struct {
+	SourceName string
+	Records    []Record
+}
+
+func (s *RecordSource) Name() string {
+	return s.SourceName
+}
+
+func (s *RecordSource) Next() (Record, error) {
+	if len(s.Records) == 0 {
+		return Record{}, io.EOF
+	}
+	rec := s.Records[0]
+	s.Records = s.Records[1:]
+	return rec, nil
+}
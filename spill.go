@@ -0,0 +1,132 @@
+package sales
+
+import (
+	"bufio"
+	"encoding/gob"
+	"errors"
+	"fmt"
+	"os"
+)
+
+// recordOverhead is roughly the memory taken by a Record, apart from the
+// contents of its strings.
+const recordOverhead = 300
+
+// recordSize estimates the memory taken by rec.
+func recordSize(rec Record) int64 {
+	n := recordOverhead
+	for _, s := range []string{rec.Source, rec.Channel, rec.OrderID, rec.Customer, rec.BillingName, rec.Product, rec.SKU, rec.Variant, rec.Country, rec.Region, rec.Referrer, rec.Affiliate, rec.Recurrence, rec.Currency} {
+		n += len(s)
+	}
+	return int64(n)
+}
+
+// spillFile holds the records that a report has spilled to disk, encoded
+// with gob, oldest first.
+type spillFile struct {
+	f     *os.File
+	w     *bufio.Writer
+	enc   *gob.Encoder
+	count int
+}
+
+// keep adds rec to the records kept by the report, spilling them to disk
+// if they take more than the RecordBudget.
+func (r *Report) keep(rec Record) {
+	r.records = append(r.records, rec)
+	if r.RecordBudget <= 0 {
+		return
+	}
+	r.recordBytes += recordSize(rec)
+	if r.recordBytes > r.RecordBudget {
+		r.spillRecords()
+	}
+}
+
+// spillRecords moves the records in memory to the end of the spill file,
+// creating it if need be. If that fails, the records stay in memory, and
+// the error is reported by EachRecord.
+func (r *Report) spillRecords() {
+	if r.spillErr != nil {
+		return
+	}
+	if r.spill == nil {
+		f, err := os.CreateTemp(r.SpillDir, "sales-records-*")
+		if err != nil {
+			r.spillErr = err
+			return
+		}
+		w := bufio.NewWriter(f)
+		r.spill = &spillFile{f: f, w: w, enc: gob.NewEncoder(w)}
+	}
+	for _, rec := range r.records {
+		if err := r.spill.enc.Encode(rec); err != nil {
+			r.spillErr = fmt.Errorf("spilling records: %w", err)
+			return
+		}
+	}
+	r.spill.count += len(r.records)
+	r.records = nil
+	r.recordBytes = 0
+}
+
+// EachRecord calls fn with each line item kept by the report, in the order
+// they were added, reading back any spilled to disk, and stops at the first
+// error fn returns. Unlike Records, it never holds the spilled records in
+// memory all at once. It returns an error if the report has been closed.
+func (r *Report) EachRecord(fn func(Record) error) error {
+	if r.closed {
+		return errors.New("records read after the report was closed")
+	}
+	if r.spillErr != nil {
+		return r.spillErr
+	}
+	if r.spill != nil {
+		if err := r.spill.w.Flush(); err != nil {
+			return fmt.Errorf("spilling records: %w", err)
+		}
+		f, err := os.Open(r.spill.f.Name())
+		if err != nil {
+			return err
+		}
+		defer f.Close()
+		dec := gob.NewDecoder(bufio.NewReader(f))
+		for i := 0; i < r.spill.count; i++ {
+			var rec Record
+			if err := dec.Decode(&rec); err != nil {
+				return fmt.Errorf("reading spilled records: %w", err)
+			}
+			if err := fn(rec); err != nil {
+				return err
+			}
+		}
+	}
+	for _, rec := range r.records {
+		if err := fn(rec); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// Close removes the file of records spilled to disk, if any. The report's
+// totals are still available afterwards, but not the records: EachRecord
+// and Records return an error.
+func (r *Report) Close() error {
+	r.closed = true
+	r.records = nil
+	return r.removeSpill()
+}
+
+// removeSpill closes and removes the spill file, if any.
+func (r *Report) removeSpill() error {
+	if r.spill == nil {
+		return nil
+	}
+	err := r.spill.f.Close()
+	if rmErr := os.Remove(r.spill.f.Name()); err == nil {
+		err = rmErr
+	}
+	r.spill = nil
+	return err
+}
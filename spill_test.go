@@ -0,0 +1,80 @@
+package sales_test
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/bitfield/sales"
+)
+
+func TestRecordsOverTheBudgetAreSpilledAndReadBackInOrder(t *testing.T) {
+	t.Parallel()
+	dir := t.TempDir()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.RecordBudget = 1000
+	r.SpillDir = dir
+	var want []string
+	for _, id := range []string{"1", "2", "3", "4", "5", "6", "7"} {
+		r.Add(sales.Record{OrderID: id, Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+		want = append(want, id)
+	}
+	spilled, err := os.ReadDir(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(spilled) != 1 {
+		t.Fatalf("want one spill file, got %d", len(spilled))
+	}
+	records, err := r.Records()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(records) != len(want) {
+		t.Fatalf("want %d records, got %d", len(want), len(records))
+	}
+	for i, rec := range records {
+		if rec.OrderID != want[i] {
+			t.Errorf("record %d: want order %s, got %s", i, want[i], rec.OrderID)
+		}
+	}
+	if err := r.Close(); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := os.Stat(filepath.Join(dir, spilled[0].Name())); !os.IsNotExist(err) {
+		t.Errorf("want spill file removed by Close, got %v", err)
+	}
+	if got := r.TotalUnits(); got != 7 {
+		t.Errorf("want 7 units after Close, got %d", got)
+	}
+}
+
+func TestRecordsAreUnavailableAfterClose(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.Add(sales.Record{OrderID: "1", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+	if err := r.Close(); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := r.Records(); err == nil {
+		t.Error("Records: want error after Close, got nil")
+	}
+	err := r.EachRecord(func(sales.Record) error { return nil })
+	if err == nil {
+		t.Error("EachRecord: want error after Close, got nil")
+	}
+}
+
+func TestRecordsReportFailureToSpill(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.KeepRecords = true
+	r.RecordBudget = 1
+	r.SpillDir = filepath.Join(t.TempDir(), "missing")
+	r.Add(sales.Record{OrderID: "1", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+	if _, err := r.Records(); err == nil {
+		t.Error("want error, got nil")
+	}
+}
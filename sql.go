@@ -0,0 +1,281 @@
+package sales
+
+import (
+	"bufio"
+	"errors"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Records returns the line items read into the report, in the order they
+// were added. Records are only kept if KeepRecords was set before reading.
+// Any spilled to disk under the RecordBudget are read back into memory,
+// so for large reports, EachRecord is better. It returns an error if the
+// spilled records can't be read back, or the report has been closed.
+func (r *Report) Records() ([]Record, error) {
+	if r.spill == nil && r.spillErr == nil && !r.closed {
+		return r.records, nil
+	}
+	var all []Record
+	err := r.EachRecord(func(rec Record) error {
+		all = append(all, rec)
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	return all, nil
+}
+
+// WriteSQL writes the report as a script of SQL statements that create and
+// fill normalised tables, for ad-hoc queries. Load it with, for example:
+//
+//	sqlite3 sales.db < sales.sql
+//
+// The tables are orders, line_items, products (each product name and SKU,
+// and the group it belongs to), groups (each group pattern), and totals
+// (the report rows). Amounts are written as exact decimals to a hundredth
+// of a cent, as they're held, so that nothing is lost by rounding. The
+// orders, line_items, and products tables are only filled if KeepRecords
+// was set before reading. ReadSQL reads the line items back.
+func (r *Report) WriteSQL(w io.Writer) error {
+	r.logRender("sql")
+	bw := bufio.NewWriter(w)
+	fmt.Fprintln(bw, "BEGIN TRANSACTION;")
+	fmt.Fprintln(bw, "CREATE TABLE orders (source TEXT NOT NULL, order_id TEXT NOT NULL, channel TEXT, created_at TEXT, PRIMARY KEY (source, order_id));")
+	fmt.Fprintln(bw, "CREATE TABLE line_items (source TEXT NOT NULL, order_id TEXT NOT NULL, product TEXT NOT NULL, sku TEXT NOT NULL, quantity INTEGER NOT NULL, price NUMERIC NOT NULL, currency TEXT NOT NULL, fee NUMERIC NOT NULL, tax NUMERIC NOT NULL, created_at TEXT);")
+	fmt.Fprintln(bw, "CREATE TABLE products (name TEXT NOT NULL, sku TEXT NOT NULL, group_name TEXT NOT NULL, PRIMARY KEY (name, sku));")
+	fmt.Fprintln(bw, "CREATE TABLE groups (name TEXT NOT NULL, pattern TEXT NOT NULL);")
+	fmt.Fprintln(bw, "CREATE TABLE totals (product TEXT PRIMARY KEY, units INTEGER NOT NULL, revenue NUMERIC NOT NULL);")
+	orders := map[[2]string]bool{}
+	// The same product can be sold under several SKUs, which may put it
+	// in different groups.
+	products := map[[2]string]bool{}
+	err := r.EachRecord(func(rec Record) error {
+		products[[2]string{rec.Product, rec.SKU}] = true
+		created := "NULL"
+		if !rec.Time.IsZero() {
+			created = sqlString(rec.Time.Format(squarespaceTime))
+		}
+		// Rows without an order ID are orders of their own, but have
+		// nothing to join on, so they appear only as line items, which
+		// carry their own time.
+		if key := [2]string{rec.Source, rec.OrderID}; rec.OrderID != "" && !orders[key] {
+			orders[key] = true
+			fmt.Fprintf(bw, "INSERT INTO orders VALUES (%s, %s, %s, %s);\n",
+				sqlString(rec.Source), sqlString(rec.OrderID), sqlString(rec.Channel), created)
+		}
+		fmt.Fprintf(bw, "INSERT INTO line_items VALUES (%s, %s, %s, %s, %d, %s, %s, %s, %s, %s);\n",
+			sqlString(rec.Source), sqlString(rec.OrderID), sqlString(rec.Product), sqlString(rec.SKU), rec.Quantity, exactString(rec.Price),
+			sqlString(r.currencyOf(rec)), exactString(rec.Fee), exactString(rec.Tax), created)
+		return nil
+	})
+	if err != nil {
+		return err
+	}
+	sorted := make([][2]string, 0, len(products))
+	for p := range products {
+		sorted = append(sorted, p)
+	}
+	sort.Slice(sorted, func(i, j int) bool {
+		if sorted[i][0] != sorted[j][0] {
+			return sorted[i][0] < sorted[j][0]
+		}
+		return sorted[i][1] < sorted[j][1]
+	})
+	for _, p := range sorted {
+		name, sku := p[0], p[1]
+		fmt.Fprintf(bw, "INSERT INTO products VALUES (%s, %s, %s);\n", sqlString(name), sqlString(sku), sqlString(r.Groups.ClassifyItem(name, sku)))
+	}
+	for _, g := range r.Groups {
+		for _, re := range g.Patterns {
+			fmt.Fprintf(bw, "INSERT INTO groups VALUES (%s, %s);\n", sqlString(g.Name), sqlString(re.String()))
+		}
+		for _, sku := range g.SKUs {
+			fmt.Fprintf(bw, "INSERT INTO groups VALUES (%s, %s);\n", sqlString(g.Name), sqlString("sku:"+sku))
+		}
+	}
+	for _, p := range r.Products() {
+		fmt.Fprintf(bw, "INSERT INTO totals VALUES (%s, %d, %s);\n", sqlString(p.Name), p.Units, exactString(p.Revenue))
+	}
+	fmt.Fprintln(bw, "COMMIT;")
+	return bw.Flush()
+}
+
+// sqlString quotes s as an SQL string literal.
+func sqlString(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", "''") + "'"
+}
+
+// ReadSQL reads the line items from an SQL script written by WriteSQL, so
+// that an earlier export can be read as input again, along with new
+// exports. Each line item keeps the source it was first read from, its
+// time, and the channel of its order, if it has an order ID. Fields the script doesn't hold, such as Customer, are left
+// empty. The other tables are ignored, so sales from summary-only
+// sources, which have no line items, aren't read.
+func (r *Report) ReadSQL(rd io.Reader, source string) error {
+	if r.Limits.MaxFileSize > 0 {
+		rd = &limitedReader{r: rd, limit: r.Limits.MaxFileSize}
+	}
+	data, err := io.ReadAll(rd)
+	if err != nil {
+		return fmt.Errorf("%s: %w", source, err)
+	}
+	orders := map[[2]string]string{}
+	var recs []Record
+	for _, stmt := range sqlStatements(string(data)) {
+		rec, ok, err := sqlRow(stmt.text, orders)
+		if err != nil {
+			if err := r.reject(&RowError{File: source, Line: stmt.line, Err: err}, r.SkipBadRows); err != nil {
+				return err
+			}
+			continue
+		}
+		if ok {
+			recs = append(recs, rec)
+		}
+	}
+	return r.ingest(&RecordSource{SourceName: source, Records: recs})
+}
+
+// sqlRow reads stmt, a statement of a script written by WriteSQL. The
+// channel of an order is added to orders, by source and order ID, and a
+// line item is returned as a record, with ok set. Other statements are
+// ignored.
+func sqlRow(stmt string, orders map[[2]string]string) (rec Record, ok bool, err error) {
+	rest, found := strings.CutPrefix(stmt, "INSERT INTO ")
+	if !found {
+		return Record{}, false, nil
+	}
+	table, rest, found := strings.Cut(rest, " VALUES (")
+	if !found || !strings.HasSuffix(rest, ")") {
+		return Record{}, false, errors.New("invalid INSERT statement")
+	}
+	values, err := sqlValues(strings.TrimSuffix(rest, ")"))
+	if err != nil {
+		return Record{}, false, err
+	}
+	switch table {
+	case "orders":
+		if len(values) != 4 {
+			return Record{}, false, fmt.Errorf("want 4 values in orders, got %d", len(values))
+		}
+		orders[[2]string{values[0], values[1]}] = values[2]
+	case "line_items":
+		if len(values) != 10 {
+			return Record{}, false, fmt.Errorf("want 10 values in line_items, got %d", len(values))
+		}
+		quantity, err := strconv.Atoi(values[4])
+		if err != nil {
+			return Record{}, false, fmt.Errorf("invalid quantity %q", values[4])
+		}
+		var amounts [3]USD
+		for i, v := range []string{values[5], values[7], values[8]} {
+			if amounts[i], err = ParseUSD(v); err != nil {
+				return Record{}, false, fmt.Errorf("invalid amount %q", v)
+			}
+		}
+		var created time.Time
+		if values[9] != "" {
+			if created, err = time.Parse(squarespaceTime, values[9]); err != nil {
+				return Record{}, false, err
+			}
+		}
+		return Record{
+			Source:   values[0],
+			OrderID:  values[1],
+			Product:  values[2],
+			SKU:      values[3],
+			Quantity: quantity,
+			Price:    amounts[0],
+			Currency: values[6],
+			Fee:      amounts[1],
+			Tax:      amounts[2],
+			Channel:  orders[[2]string{values[0], values[1]}],
+			Time:     created,
+		}, true, nil
+	}
+	return Record{}, false, nil
+}
+
+// sqlStatement is a statement of an SQL script, and the line it starts on.
+type sqlStatement struct {
+	text string
+	line int
+}
+
+// sqlStatements splits an SQL script into statements, at the semicolons
+// outside string literals.
+func sqlStatements(script string) []sqlStatement {
+	var stmts []sqlStatement
+	line, start, startLine := 1, 0, 1
+	quoted := false
+	for i := 0; i < len(script); i++ {
+		switch c := script[i]; {
+		case c == '\n':
+			line++
+		case c == '\'':
+			// A quote doubled inside a literal toggles this twice.
+			quoted = !quoted
+		case c == ';' && !quoted:
+			text := script[start:i]
+			lead := len(text) - len(strings.TrimLeft(text, " \t\r\n"))
+			stmts = append(stmts, sqlStatement{
+				text: strings.TrimSpace(text),
+				line: startLine + strings.Count(text[:lead], "\n"),
+			})
+			start, startLine = i+1, line
+		}
+	}
+	return stmts
+}
+
+// sqlValues parses a list of SQL values, as written by WriteSQL: string
+// literals, integers, and NULL, which is returned as "".
+func sqlValues(s string) ([]string, error) {
+	var values []string
+	for {
+		s = strings.TrimLeft(s, " ")
+		var v string
+		if strings.HasPrefix(s, "'") {
+			var b strings.Builder
+			i := 1
+			for {
+				j := strings.IndexByte(s[i:], '\'')
+				if j < 0 {
+					return nil, errors.New("unterminated string")
+				}
+				b.WriteString(s[i : i+j])
+				i += j + 1
+				if !strings.HasPrefix(s[i:], "'") {
+					break
+				}
+				b.WriteByte('\'')
+				i++
+			}
+			v, s = b.String(), s[i:]
+		} else {
+			end := strings.IndexByte(s, ',')
+			if end < 0 {
+				end = len(s)
+			}
+			v, s = strings.TrimSpace(s[:end]), s[end:]
+			if v == "NULL" {
+				v = ""
+			}
+		}
+		values = append(values, v)
+		s = strings.TrimLeft(s, " ")
+		if s == "" {
+			return values, nil
+		}
+		if s[0] != ',' {
+			return nil, errors.New("want a comma between values")
+		}
+		s = s[1:]
+	}
+}
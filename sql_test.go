@@ -0,0 +1,81 @@
+package sales_test
+
+import (
+	"bytes"
+	"errors"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func TestReadSQLReadsBackLineItemsWrittenByWriteSQL(t *testing.T) {
+	t.Parallel()
+	when := time.Date(2024, time.March, 1, 12, 30, 0, 0, time.UTC)
+	want := []sales.Record{
+		{Source: "a.csv", Channel: "web", OrderID: "1", Product: "Bob's Book", SKU: "B-1", Quantity: 2, Price: sales.NewUSD(10), Currency: "USD", Fee: sales.NewUSD(1), Tax: sales.USDCents(150), Time: when},
+		{Source: "a.csv", Channel: "web", OrderID: "1", Product: "Video; uncut\nedition", Quantity: 1, Price: sales.USDCents(1999), Currency: "USD", Time: when},
+		{Source: "b.csv", Product: "Sticker", Quantity: 3, Price: sales.USDCents(50), Currency: "USD", Time: when.Add(time.Hour)},
+		{Source: "b.csv", Product: "Split bundle", Quantity: 1, Price: sales.NewUSD(3.3333), Currency: "USD", Fee: sales.NewUSD(0.0125)},
+	}
+	w := sales.NewReport()
+	w.KeepRecords = true
+	for _, rec := range want {
+		w.Add(rec)
+	}
+	var script bytes.Buffer
+	if err := w.WriteSQL(&script); err != nil {
+		t.Fatal(err)
+	}
+	r := sales.NewReport()
+	r.KeepRecords = true
+	if err := r.ReadSQL(&script, "sales.sql"); err != nil {
+		t.Fatal(err)
+	}
+	got, err := r.Records()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(got) != len(want) {
+		t.Fatalf("want %d records, got %d", len(want), len(got))
+	}
+	for i := range want {
+		if !got[i].Time.Equal(want[i].Time) {
+			t.Errorf("record %d: want time %v, got %v", i, want[i].Time, got[i].Time)
+		}
+		got[i].Time = want[i].Time
+		if got[i] != want[i] {
+			t.Errorf("record %d:\nwant %+v\ngot  %+v", i, want[i], got[i])
+		}
+	}
+	if r.Totals() != w.Totals() {
+		t.Errorf("want totals %+v, got %+v", w.Totals(), r.Totals())
+	}
+}
+
+func TestReadSQLReportsTheLineOfABadStatement(t *testing.T) {
+	t.Parallel()
+	script := `CREATE TABLE orders (source TEXT);
+INSERT INTO line_items VALUES ('a.csv', '', 'Book', '', 1, 10.0000, 'USD', 0.0000, 0.0000, NULL);
+
+INSERT INTO line_items VALUES ('a.csv', '', 'Book', '', lots, 10.0000, 'USD', 0.0000, 0.0000, NULL);
+`
+	r := sales.NewReport()
+	err := r.ReadSQL(strings.NewReader(script), "sales.sql")
+	var rowErr *sales.RowError
+	if !errors.As(err, &rowErr) {
+		t.Fatalf("want RowError, got %v", err)
+	}
+	if rowErr.Line != 4 {
+		t.Errorf("want error on line 4, got line %d", rowErr.Line)
+	}
+	r = sales.NewReport()
+	r.SkipBadRows = true
+	if err := r.ReadSQL(strings.NewReader(script), "sales.sql"); err != nil {
+		t.Fatal(err)
+	}
+	if got := r.TotalUnits(); got != 1 {
+		t.Errorf("want 1 unit from the good statement, got %d", got)
+	}
+}
@@ -0,0 +1,303 @@
+package sales
+
+import (
+	"encoding/gob"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+)
+
+// stateVersion is incremented whenever the format of saved state changes
+// incompatibly.
+const stateVersion = 4
+
+// state is the saved form of a Report. Settings such as Limits and Columns
+// belong to each run, and warnings and skipped rows to the input that
+// caused them, so neither is saved.
+type state struct {
+	Version    int
+	Groups     []savedGroup
+	Products   []savedProduct
+	Slots      []savedSlot
+	Orders     map[string]bool
+	OrderIDs   map[string]string
+	Timing     [7][24]Activity
+	Daily      []savedDay
+	Variants   []savedVariant
+	Taxes      []savedTax
+	Fees       map[string]USD
+	Referrers  []ReferrerTotal
+	Splits     map[string]RevenueSplit
+	MRR        []savedMRR
+	Prices     []savedPrice
+	Residues   []savedResidue
+	Currencies []CurrencyTotal
+	Items      []savedItem
+	Customers  map[string]map[string]USD
+	Aggregates []string
+	Records    []Record
+	Duplicates int
+	Excluded   map[string]int
+	OrderCount int
+}
+
+type savedGroup struct {
+	Name     string
+	Patterns []string
+	SKUs     []string
+}
+
+type savedProduct struct {
+	Name    string
+	Units   int
+	Comps   int
+	Revenue USD
+	Origins map[Origin]Tally
+}
+
+type savedVariant struct {
+	Product string
+	Variant string
+	Tally   Tally
+}
+
+type savedTax struct {
+	Quarter string
+	Country string
+	Region  string
+	Tally   Tally
+}
+
+type savedPrice struct {
+	Product string
+	Price   USD
+	Units   int
+}
+
+type savedMRR struct {
+	Month    string
+	Customer string
+	Amount   USD
+}
+
+type savedResidue struct {
+	Product string
+	Period  string
+	Tally   residueTally
+}
+
+type savedItem struct {
+	Rule    int
+	Product string
+	Tally   itemTally
+}
+
+type savedDay struct {
+	Product string
+	Date    string
+	Revenue USD
+}
+
+type savedSlot struct {
+	Product string
+	Period  string
+	Origin  Origin
+	Tally   Tally
+}
+
+// Save writes the report's accumulated data, including its groups, to the
+// file at path, so that later input can be added to it by LoadReport. The
+// file is replaced atomically, so an interrupted save leaves the previous
+// state intact.
+func (r *Report) Save(path string) error {
+	f, err := os.CreateTemp(filepath.Dir(path), filepath.Base(path)+".*")
+	if err != nil {
+		return err
+	}
+	defer os.Remove(f.Name())
+	if err := r.WriteState(f); err != nil {
+		f.Close()
+		return fmt.Errorf("%s: %w", path, err)
+	}
+	if err := f.Close(); err != nil {
+		return err
+	}
+	return os.Rename(f.Name(), path)
+}
+
+// WriteState writes the report's accumulated data to w, in the format read
+// by ReadState. Save uses it to write a state file.
+func (r *Report) WriteState(w io.Writer) error {
+	records, err := r.Records()
+	if err != nil {
+		return err
+	}
+	s := state{
+		Version:    stateVersion,
+		Orders:     r.orders,
+		OrderIDs:   r.orderIDs,
+		Timing:     r.timing,
+		Aggregates: r.aggregates,
+		Records:    records,
+		Duplicates: r.duplicates,
+		Customers:  r.customers,
+		Fees:       r.fees,
+		Splits:     r.splits,
+		Excluded:   r.excluded,
+		OrderCount: r.orderCount,
+	}
+	for _, g := range r.Groups {
+		sg := savedGroup{Name: g.Name, SKUs: g.SKUs}
+		for _, re := range g.Patterns {
+			sg.Patterns = append(sg.Patterns, re.String())
+		}
+		s.Groups = append(s.Groups, sg)
+	}
+	for _, p := range r.Products() {
+		s.Products = append(s.Products, savedProduct{
+			Name:    p.Name,
+			Units:   p.Units,
+			Comps:   p.Comps,
+			Revenue: p.Revenue,
+			Origins: p.origins,
+		})
+	}
+	for k, rev := range r.daily {
+		s.Daily = append(s.Daily, savedDay{Product: k.product, Date: k.date, Revenue: rev})
+	}
+	for k, units := range r.prices {
+		s.Prices = append(s.Prices, savedPrice{Product: k.product, Price: k.price, Units: units})
+	}
+	for k, amount := range r.mrr {
+		s.MRR = append(s.MRR, savedMRR{Month: k.month, Customer: k.customer, Amount: amount})
+	}
+	for _, t := range r.referrers {
+		s.Referrers = append(s.Referrers, t)
+	}
+	for k, t := range r.taxes {
+		s.Taxes = append(s.Taxes, savedTax{Quarter: k.quarter, Country: k.country, Region: k.region, Tally: t})
+	}
+	for k, t := range r.variants {
+		s.Variants = append(s.Variants, savedVariant{Product: k.product, Variant: k.variant, Tally: t})
+	}
+	s.Currencies = r.ByCurrency()
+	for k, t := range r.items {
+		s.Items = append(s.Items, savedItem{Rule: k.rule, Product: k.product, Tally: t})
+	}
+	for k, t := range r.residues {
+		s.Residues = append(s.Residues, savedResidue{Product: k.product, Period: k.period, Tally: t})
+	}
+	for k, t := range r.slots {
+		s.Slots = append(s.Slots, savedSlot{Product: k.product, Period: k.period, Origin: k.origin, Tally: t})
+	}
+	return gob.NewEncoder(w).Encode(s)
+}
+
+// LoadReport reads a report saved by Save, so that more input can be added
+// to it: for example, appending each month's export to a running
+// year-to-date report. The report's groups are restored too, but can be
+// replaced before reading more input.
+func LoadReport(path string) (*Report, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+	r, err := ReadState(f)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", path, err)
+	}
+	return r, nil
+}
+
+// ReadState reads a report written by WriteState.
+func ReadState(rd io.Reader) (*Report, error) {
+	var s state
+	if err := gob.NewDecoder(rd).Decode(&s); err != nil {
+		return nil, err
+	}
+	if s.Version != stateVersion {
+		return nil, fmt.Errorf("unsupported state version %d (want %d)", s.Version, stateVersion)
+	}
+	r := NewReport()
+	for _, sg := range s.Groups {
+		g := Group{Name: sg.Name, SKUs: sg.SKUs}
+		for _, p := range sg.Patterns {
+			re, err := regexp.Compile(p)
+			if err != nil {
+				return nil, fmt.Errorf("group %q: %w", sg.Name, err)
+			}
+			g.Patterns = append(g.Patterns, re)
+		}
+		r.Groups = append(r.Groups, g)
+	}
+	for _, sp := range s.Products {
+		p := &Product{
+			Name:    sp.Name,
+			Units:   sp.Units,
+			Comps:   sp.Comps,
+			Revenue: sp.Revenue,
+			origins: sp.Origins,
+		}
+		if p.origins == nil {
+			p.origins = map[Origin]Tally{}
+		}
+		r.products[p.Name] = p
+	}
+	for _, ss := range s.Slots {
+		r.slots[slot{product: ss.Product, period: ss.Period, origin: ss.Origin}] = ss.Tally
+	}
+	if s.Orders != nil {
+		r.orders = s.Orders
+	}
+	if s.OrderIDs != nil {
+		r.orderIDs = s.OrderIDs
+	}
+	r.timing = s.Timing
+	if s.Customers != nil {
+		r.customers = s.Customers
+	}
+	if s.Fees != nil {
+		r.fees = s.Fees
+	}
+	if s.Excluded != nil {
+		r.excluded = s.Excluded
+	}
+	if s.Splits != nil {
+		r.splits = s.Splits
+	}
+	for _, sp := range s.Prices {
+		r.prices[priceSlot{product: sp.Product, price: sp.Price}] = sp.Units
+	}
+	for _, sm := range s.MRR {
+		r.mrr[mrrSlot{month: sm.Month, customer: sm.Customer}] = sm.Amount
+	}
+	for _, rt := range s.Referrers {
+		r.referrers[referrerSlot{referrer: rt.Referrer, affiliate: rt.Affiliate}] = rt
+	}
+	for _, st := range s.Taxes {
+		r.taxes[taxSlot{quarter: st.Quarter, country: st.Country, region: st.Region}] = st.Tally
+	}
+	for _, sv := range s.Variants {
+		r.variants[variantSlot{product: sv.Product, variant: sv.Variant}] = sv.Tally
+	}
+	for _, ct := range s.Currencies {
+		r.currencies[currencySlot{currency: ct.Currency, product: ct.Product}] = ct.Tally
+	}
+	for _, si := range s.Items {
+		r.items[itemSlot{rule: si.Rule, product: si.Product}] = si.Tally
+	}
+	for _, sr := range s.Residues {
+		r.residues[residueSlot{product: sr.Product, period: sr.Period}] = sr.Tally
+	}
+	for _, sd := range s.Daily {
+		r.daily[daySlot{product: sd.Product, date: sd.Date}] = sd.Revenue
+	}
+	r.aggregates = s.Aggregates
+	r.records = s.Records
+	r.duplicates = s.Duplicates
+	r.orderCount = s.OrderCount
+	return r, nil
+}
@@ -0,0 +1,155 @@
+package sales
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// stripeAPI is the base URL of the Stripe API.
+const stripeAPI = "https://api.stripe.com/v1"
+
+// stripeZeroDecimal are the currencies whose Stripe amounts are in whole
+// units, since they have no smaller ones.
+var stripeZeroDecimal = map[string]bool{
+	"BIF": true, "CLP": true, "DJF": true, "GNF": true, "JPY": true, "KMF": true,
+	"KRW": true, "MGA": true, "PYG": true, "RWF": true, "UGX": true, "VND": true,
+	"VUV": true, "XAF": true, "XOF": true, "XPF": true,
+}
+
+// StripeSource is a Source that fetches successful charges from the Stripe
+// API, one page at a time. Each charge becomes a record of one unit of the
+// product given by the charge's description, at the amount charged less
+// any refunds, in the charge's currency. Charges without a description are
+// recorded as "Stripe charge". Report.Ingest applies the report's
+// CurrencyPolicy to charges in other currencies.
+type StripeSource struct {
+	ctx   context.Context
+	key   string
+	since time.Time
+	// Client is the HTTP client used for requests; http.DefaultClient
+	// if nil.
+	Client  *http.Client
+	page    []stripeCharge
+	after   string
+	hasMore bool
+	started bool
+}
+
+type stripeCharge struct {
+	ID             string `json:"id"`
+	Amount         int    `json:"amount"`
+	AmountRefunded int    `json:"amount_refunded"`
+	Currency       string `json:"currency"`
+	Created        int64  `json:"created"`
+	Description    string `json:"description"`
+	Status         string `json:"status"`
+	BillingDetails struct {
+		Email string `json:"email"`
+		Name  string `json:"name"`
+	} `json:"billing_details"`
+}
+
+// NewStripeSource returns a source of the charges made since the given
+// time, fetched with the secret API key.
+func NewStripeSource(ctx context.Context, key string, since time.Time) *StripeSource {
+	return &StripeSource{ctx: ctx, key: key, since: since}
+}
+
+func (s *StripeSource) Name() string {
+	return "stripe"
+}
+
+func (s *StripeSource) Next() (Record, error) {
+	for len(s.page) == 0 {
+		if s.started && !s.hasMore {
+			return Record{}, io.EOF
+		}
+		if err := s.fetch(); err != nil {
+			return Record{}, err
+		}
+	}
+	c := s.page[0]
+	s.page = s.page[1:]
+	currency := strings.ToUpper(c.Currency)
+	amount := c.Amount - c.AmountRefunded
+	if stripeZeroDecimal[currency] {
+		// Stripe gives these amounts in whole units, not hundredths.
+		amount *= 100
+	}
+	product := c.Description
+	if product == "" {
+		product = "Stripe charge"
+	}
+	return Record{
+		Source:      s.Name(),
+		Channel:     "Stripe",
+		OrderID:     c.ID,
+		Customer:    strings.ToLower(c.BillingDetails.Email),
+		BillingName: c.BillingDetails.Name,
+		Product:     product,
+		Quantity:    1,
+		Price:       USDCents(amount),
+		Currency:    currency,
+		Time:        time.Unix(c.Created, 0).UTC(),
+	}, nil
+}
+
+// fetch gets the next page of successful charges.
+func (s *StripeSource) fetch() error {
+	q := url.Values{}
+	q.Set("limit", "100")
+	q.Set("created[gte]", strconv.FormatInt(s.since.Unix(), 10))
+	if s.after != "" {
+		q.Set("starting_after", s.after)
+	}
+	req, err := http.NewRequestWithContext(s.ctx, http.MethodGet, stripeAPI+"/charges?"+q.Encode(), nil)
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Authorization", "Bearer "+s.key)
+	client := s.Client
+	if client == nil {
+		client = http.DefaultClient
+	}
+	resp, err := client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		var body struct {
+			Error struct {
+				Message string `json:"message"`
+			} `json:"error"`
+		}
+		json.NewDecoder(resp.Body).Decode(&body)
+		return fmt.Errorf("fetching Stripe charges: %s: %s", resp.Status, body.Error.Message)
+	}
+	var list struct {
+		Data    []stripeCharge `json:"data"`
+		HasMore bool           `json:"has_more"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&list); err != nil {
+		return fmt.Errorf("fetching Stripe charges: %w", err)
+	}
+	s.started = true
+	s.hasMore = list.HasMore
+	s.page = s.page[:0]
+	for _, c := range list.Data {
+		if c.Status != "succeeded" {
+			continue
+		}
+		s.page = append(s.page, c)
+	}
+	if len(list.Data) > 0 {
+		s.after = list.Data[len(list.Data)-1].ID
+	}
+	return nil
+}
@@ -0,0 +1,105 @@
+package sales_test
+
+import (
+	"context"
+	"io"
+	"net/http"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// stripeStub is an http.RoundTripper that answers every request with a
+// single page of Stripe charges.
+type stripeStub string
+
+func (s stripeStub) RoundTrip(req *http.Request) (*http.Response, error) {
+	return &http.Response{
+		StatusCode: http.StatusOK,
+		Status:     "200 OK",
+		Header:     http.Header{"Content-Type": {"application/json"}},
+		Body:       io.NopCloser(strings.NewReader(string(s))),
+		Request:    req,
+	}, nil
+}
+
+const stripeCharges = `{"has_more": false, "data": [
+{"id": "ch_1", "amount": 1000, "amount_refunded": 0, "currency": "usd", "created": 1700000000, "description": "Book", "status": "succeeded"},
+{"id": "ch_2", "amount": 2000, "amount_refunded": 500, "currency": "eur", "created": 1700000000, "description": "Book", "status": "succeeded"},
+{"id": "ch_3", "amount": 500, "amount_refunded": 0, "currency": "jpy", "created": 1700000000, "description": "Sticker", "status": "succeeded"},
+{"id": "ch_4", "amount": 9900, "amount_refunded": 0, "currency": "usd", "created": 1700000000, "description": "Video", "status": "failed"}
+]}`
+
+func stripeSource() *sales.StripeSource {
+	src := sales.NewStripeSource(context.Background(), "sk_test", time.Time{})
+	src.Client = &http.Client{Transport: stripeStub(stripeCharges)}
+	return src
+}
+
+func TestStripeSourceRecordsChargesInTheirCurrency(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.CurrencyPolicy.Mode = "separate"
+	if err := r.Ingest(stripeSource()); err != nil {
+		t.Fatal(err)
+	}
+	want := map[string]sales.USD{"USD": sales.NewUSD(10), "EUR": sales.NewUSD(15), "JPY": sales.NewUSD(500)}
+	got := map[string]sales.USD{}
+	for _, c := range r.ByCurrency() {
+		got[c.Currency] += c.Revenue
+	}
+	for code, amount := range want {
+		if got[code] != amount {
+			t.Errorf("%s: want %v, got %v", code, amount, got[code])
+		}
+	}
+	if len(got) != len(want) {
+		t.Errorf("want currencies %v, got %v", want, got)
+	}
+}
+
+func TestStripeSourceConvertsChargesByCurrencyPolicy(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.CurrencyPolicy = sales.CurrencyPolicy{Mode: "convert", Rates: map[string]float64{"EUR": 1.1, "JPY": 0.01}}
+	if err := r.Ingest(stripeSource()); err != nil {
+		t.Fatal(err)
+	}
+	if got, want := r.TotalRevenue(), sales.NewUSD(10+16.5+5); got != want {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+}
+
+func TestStripeSourceRejectsChargesByCurrencyPolicy(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.CurrencyPolicy.Mode = "reject"
+	if err := r.Ingest(stripeSource()); err == nil {
+		t.Fatal("want error for charges in other currencies, got nil")
+	}
+	r = sales.NewReport()
+	r.CurrencyPolicy.Mode = "reject"
+	r.SkipBadRows = true
+	if err := r.Ingest(stripeSource()); err != nil {
+		t.Fatal(err)
+	}
+	if got, want := r.TotalRevenue(), sales.NewUSD(10); got != want {
+		t.Errorf("want revenue %v, got %v", want, got)
+	}
+	if got := len(r.Skipped()); got != 2 {
+		t.Errorf("want 2 charges skipped, got %d", got)
+	}
+}
+
+func TestStripeSourceWarnsOfOtherCurrenciesByDefault(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	if err := r.Ingest(stripeSource()); err != nil {
+		t.Fatal(err)
+	}
+	if got := len(r.Warnings()); got != 2 {
+		t.Errorf("want 2 warnings, got %d: %v", got, r.Warnings())
+	}
+}
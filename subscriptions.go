@@ -0,0 +1,137 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"regexp"
+	"sort"
+	"strings"
+	"time"
+)
+
+// ParseRecurrence returns the number of months between the charges of a
+// subscription billed as s, such as "monthly" (1) or "yearly" (12). An empty
+// s, "none", or "one-time" means a one-time purchase, and gives zero.
+func ParseRecurrence(s string) (int, error) {
+	switch strings.ToLower(strings.TrimSpace(s)) {
+	case "", "none", "one-time", "once":
+		return 0, nil
+	case "monthly", "month":
+		return 1, nil
+	case "quarterly", "quarter":
+		return 3, nil
+	case "biannually", "every 6 months", "half-yearly":
+		return 6, nil
+	case "yearly", "annually", "year":
+		return 12, nil
+	}
+	return 0, fmt.Errorf("unknown recurrence %q (want monthly, quarterly, biannually, yearly, or none)", s)
+}
+
+// SubscriptionRule says that products whose names match Pattern are
+// subscriptions, charged every Months months.
+type SubscriptionRule struct {
+	Pattern *regexp.Regexp
+	Months  int
+}
+
+// Subscriptions is a list of rules for recognising subscription charges in
+// exports that don't mark them.
+type Subscriptions []SubscriptionRule
+
+// Months returns the number of months that the charge rec pays for, or
+// zero if it's a one-time purchase. A recurrence given by the export takes
+// precedence; otherwise the first rule matching the product, if any, says.
+func (s Subscriptions) Months(rec Record) int {
+	if months, err := ParseRecurrence(rec.Recurrence); err == nil && months > 0 {
+		return months
+	}
+	for _, rule := range s {
+		if rule.Pattern.MatchString(rec.Product) {
+			return rule.Months
+		}
+	}
+	return 0
+}
+
+// mrrSlot identifies the recurring revenue from one customer in one month.
+type mrrSlot struct {
+	month    string
+	customer string
+}
+
+// RevenueSplit is the revenue in one month from one-time purchases and from
+// subscription charges, and the monthly recurring revenue (MRR): what the
+// month's share of every subscription covering it comes to, so that a
+// yearly charge counts a twelfth towards each of the twelve months it pays
+// for.
+type RevenueSplit struct {
+	Month        string
+	OneTime      USD
+	Subscription USD
+	MRR          USD
+}
+
+// addRecurring counts the sale in rec, which has a timestamp, as one-time
+// or subscription revenue, and spreads a subscription charge over the
+// months that it pays for.
+func (r *Report) addRecurring(rec Record, total USD) {
+	month := rec.Time.Format("2006-01")
+	split := r.splits[month]
+	months := r.Subscriptions.Months(rec)
+	if months == 0 {
+		split.OneTime += total
+		r.splits[month] = split
+		return
+	}
+	split.Subscription += total
+	r.splits[month] = split
+	start := time.Date(rec.Time.Year(), rec.Time.Month(), 1, 0, 0, 0, 0, time.UTC)
+	for i := 0; i < months; i++ {
+		k := mrrSlot{month: start.AddDate(0, i, 0).Format("2006-01"), customer: rec.Customer}
+		r.mrr[k] += total / USD(months)
+	}
+}
+
+// BySubscription returns the one-time revenue, subscription revenue, and
+// MRR for each month, in date order. It includes the months paid for in
+// advance by subscriptions, even if they're after the last sale. Records
+// without timestamps aren't counted.
+func (r *Report) BySubscription() []RevenueSplit {
+	months := map[string]RevenueSplit{}
+	for m, s := range r.splits {
+		months[m] = s
+	}
+	for k, amount := range r.mrr {
+		s := months[k.month]
+		s.MRR += amount
+		months[k.month] = s
+	}
+	splits := make([]RevenueSplit, 0, len(months))
+	for m, s := range months {
+		s.Month = m
+		splits = append(splits, s)
+	}
+	sort.Slice(splits, func(i, j int) bool {
+		return splits[i].Month < splits[j].Month
+	})
+	return splits
+}
+
+// WriteSubscriptions writes the one-time and subscription revenue and the
+// MRR for each month as a text table.
+func (r *Report) WriteSubscriptions(w io.Writer) error {
+	t := &table{
+		header: []string{"Month", "One-time", "Subscriptions", "MRR"},
+		right:  []bool{false, true, true, true},
+		color:  r.Color,
+	}
+	var oneTime, subscription USD
+	for _, s := range r.BySubscription() {
+		t.add(s.Month, s.OneTime.String(), s.Subscription.String(), s.MRR.String())
+		oneTime += s.OneTime
+		subscription += s.Subscription
+	}
+	t.footer = []string{"Total", oneTime.String(), subscription.String(), ""}
+	return t.write(w)
+}
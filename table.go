@@ -0,0 +1,191 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+	"strings"
+	"unicode"
+)
+
+// ANSI escape sequences used by colored text output.
+const (
+	ansiBold  = "\x1b[1m"
+	ansiRed   = "\x1b[31m"
+	ansiReset = "\x1b[0m"
+)
+
+// table lays out text in columns, padding each cell to the width of its
+// column as displayed in a terminal, so that names containing accented or
+// wide characters still line up.
+type table struct {
+	header []string
+	rows   [][]string
+	// footer is a totals row, shown after the others.
+	footer []string
+	// right marks the columns to align right, such as amounts.
+	right []bool
+	// color shows the header and footer in bold, and negative amounts in
+	// red.
+	color bool
+	// width, if positive, is the most columns the table may take up. The
+	// first column is truncated to fit.
+	width int
+}
+
+func (t *table) add(cells ...string) {
+	t.rows = append(t.rows, cells)
+}
+
+func (t *table) write(w io.Writer) error {
+	widths := make([]int, len(t.header))
+	for _, row := range append([][]string{t.header, t.footer}, t.rows...) {
+		for i, cell := range row {
+			widths[i] = max(widths[i], displayWidth(cell))
+		}
+	}
+	if t.width > 0 {
+		total := 2 * (len(widths) - 1)
+		for _, w := range widths {
+			total += w
+		}
+		if over := total - t.width; over > 0 {
+			widths[0] = max(widths[0]-over, 1)
+		}
+	}
+	var b strings.Builder
+	line := func(row []string, bold bool) {
+		if row == nil {
+			return
+		}
+		for i, cell := range row {
+			if displayWidth(cell) > widths[i] {
+				cell = truncate(cell, widths[i])
+			}
+			pad := strings.Repeat(" ", widths[i]-displayWidth(cell))
+			if t.color {
+				switch {
+				case t.right[i] && strings.HasPrefix(cell, "-"):
+					cell = ansiRed + cell + ansiReset
+				case bold:
+					cell = ansiBold + cell + ansiReset
+				}
+			}
+			if i > 0 {
+				b.WriteString("  ")
+			}
+			switch {
+			case t.right[i]:
+				b.WriteString(pad + cell)
+			case i < len(row)-1:
+				b.WriteString(cell + pad)
+			default:
+				// Don't leave trailing spaces after the last column.
+				b.WriteString(cell)
+			}
+		}
+		b.WriteString("\n")
+	}
+	line(t.header, true)
+	for _, row := range t.rows {
+		line(row, false)
+	}
+	line(t.footer, true)
+	_, err := io.WriteString(w, b.String())
+	return err
+}
+
+// truncate shortens s to at most width columns, ending it with an ellipsis.
+func truncate(s string, width int) string {
+	var b strings.Builder
+	n := 0
+	for _, r := range s {
+		w := displayWidth(string(r))
+		if n+w+1 > width {
+			break
+		}
+		b.WriteRune(r)
+		n += w
+	}
+	return b.String() + "…"
+}
+
+// displayWidth returns the number of terminal columns that s takes up,
+// counting East Asian wide characters as two columns and combining marks as
+// none.
+func displayWidth(s string) int {
+	n := 0
+	for _, r := range s {
+		switch {
+		case unicode.Is(unicode.Mn, r), unicode.Is(unicode.Me, r), r == '\u200b':
+		case isWide(r):
+			n += 2
+		default:
+			n++
+		}
+	}
+	return n
+}
+
+// isWide reports whether r is displayed two columns wide, such as CJK
+// characters and most emoji.
+func isWide(r rune) bool {
+	return r >= 0x1100 && r <= 0x115F ||
+		r >= 0x2E80 && r <= 0xA4CF && r != 0x303F ||
+		r >= 0xAC00 && r <= 0xD7A3 ||
+		r >= 0xF900 && r <= 0xFAFF ||
+		r >= 0xFE30 && r <= 0xFE4F ||
+		r >= 0xFF00 && r <= 0xFF60 ||
+		r >= 0xFFE0 && r <= 0xFFE6 ||
+		r >= 0x1F300 && r <= 0x1F64F ||
+		r >= 0x1F900 && r <= 0x1F9FF ||
+		r >= 0x20000 && r <= 0x3FFFD
+}
+
+// barWidth is the width, in columns, of the longest bar in a chart.
+const barWidth = 30
+
+// Chart selects the measure shown as a bar chart beside each row of a
+// text report, if any.
+type Chart int
+
+const (
+	// NoChart shows no bars.
+	NoChart Chart = iota
+	// ChartRevenue scales the bars to revenue.
+	ChartRevenue
+	// ChartUnits scales the bars to units sold.
+	ChartUnits
+)
+
+// ParseChart returns the chart named by s: "revenue", "units", or "" for
+// none.
+func ParseChart(s string) (Chart, error) {
+	switch s {
+	case "":
+		return NoChart, nil
+	case "revenue":
+		return ChartRevenue, nil
+	case "units":
+		return ChartUnits, nil
+	}
+	return NoChart, fmt.Errorf("unknown chart %q (want revenue or units)", s)
+}
+
+// bar returns a bar of block characters whose length is proportional to
+// v/limit, with a resolution of an eighth of a column. Non-positive values
+// have no bar.
+func bar(v, limit int64) string {
+	if v <= 0 || limit <= 0 {
+		return ""
+	}
+	eighths := int(v * barWidth * 8 / limit)
+	if eighths == 0 {
+		// Show that there's something there, however small.
+		eighths = 1
+	}
+	s := strings.Repeat("█", eighths/8)
+	if rest := eighths % 8; rest > 0 {
+		s += string([]rune("▏▎▍▌▋▊▉")[rest-1])
+	}
+	return s
+}
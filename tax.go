@@ -0,0 +1,91 @@
+package sales
+
+import (
+	"encoding/csv"
+	"io"
+	"sort"
+	"strconv"
+)
+
+// taxSlot identifies the sales to one tax jurisdiction in one calendar
+// quarter.
+type taxSlot struct {
+	quarter string
+	country string
+	region  string
+}
+
+// TaxTotal is the units and revenue from sales to customers in one
+// jurisdiction, such as a country or a US state, in one calendar quarter,
+// as needed for sales tax and VAT returns. Sales without a date have an
+// empty Quarter, and those without an address an empty Country.
+type TaxTotal struct {
+	Quarter string
+	Country string
+	Region  string
+	Tally
+}
+
+// addTax counts the sale in rec against its jurisdiction.
+func (r *Report) addTax(rec Record, total USD) {
+	var quarter string
+	if !rec.Time.IsZero() {
+		quarter = Period{Unit: Quarterly}.Label(rec.Time)
+	}
+	k := taxSlot{quarter: quarter, country: rec.Country, region: rec.Region}
+	t := r.taxes[k]
+	t.Units += rec.Quantity
+	t.Revenue += total
+	r.taxes[k] = t
+}
+
+// ByJurisdiction returns the units and revenue from each jurisdiction in
+// each calendar quarter, ordered by quarter, country, and region.
+func (r *Report) ByJurisdiction() []TaxTotal {
+	totals := make([]TaxTotal, 0, len(r.taxes))
+	for k, t := range r.taxes {
+		totals = append(totals, TaxTotal{Quarter: k.quarter, Country: k.country, Region: k.region, Tally: t})
+	}
+	sort.Slice(totals, func(i, j int) bool {
+		a, b := totals[i], totals[j]
+		if a.Quarter != b.Quarter {
+			return a.Quarter < b.Quarter
+		}
+		if a.Country != b.Country {
+			return a.Country < b.Country
+		}
+		return a.Region < b.Region
+	})
+	return totals
+}
+
+// WriteTaxReport writes the revenue from each jurisdiction in each
+// quarter as a text table.
+func (r *Report) WriteTaxReport(w io.Writer) error {
+	t := &table{
+		header: []string{"Quarter", "Country", "Region", "Units", "Revenue"},
+		right:  []bool{false, false, false, true, true},
+		color:  r.Color,
+	}
+	for _, tt := range r.ByJurisdiction() {
+		country := tt.Country
+		if country == "" {
+			country = "(unknown)"
+		}
+		t.add(tt.Quarter, country, tt.Region, strconv.Itoa(tt.Units), tt.Revenue.String())
+	}
+	t.footer = []string{"Total", "", "", strconv.Itoa(r.TotalUnits()), r.TotalRevenue().String()}
+	return t.write(w)
+}
+
+// WriteTaxCSV writes the revenue from each jurisdiction in each quarter as
+// CSV, for importing into a tax return.
+func (r *Report) WriteTaxCSV(w io.Writer) error {
+	cw := csv.NewWriter(w)
+	cw.Write([]string{"quarter", "country", "region", "units", "revenue"})
+	for _, tt := range r.ByJurisdiction() {
+		cw.Write([]string{tt.Quarter, tt.Country, tt.Region, strconv.Itoa(tt.Units), tt.Revenue.String()})
+	}
+	cw.Flush()
+	return cw.Error()
+}
@@ -0,0 +1,61 @@
+package sales_test
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+// addTaxableSales adds sales to customers in several jurisdictions and
+// quarters to r, and one with neither a date nor an address.
+func addTaxableSales(r *sales.Report) {
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Country: "US", Region: "NY", Time: date(2024, time.March, 31)})
+	r.Add(sales.Record{Product: "Book", Quantity: 2, Price: sales.NewUSD(10), Country: "US", Region: "CA", Time: date(2024, time.January, 5)})
+	r.Add(sales.Record{Product: "Video", Quantity: 1, Price: sales.NewUSD(30), Country: "GB", Time: date(2024, time.February, 1)})
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Country: "US", Region: "CA", Time: date(2024, time.April, 1)})
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(5)})
+}
+
+func TestByJurisdictionTotalsEachJurisdictionByQuarter(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	addTaxableSales(r)
+	want := []sales.TaxTotal{
+		{Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(5)}},
+		{Quarter: "2024-Q1", Country: "GB", Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(30)}},
+		{Quarter: "2024-Q1", Country: "US", Region: "CA", Tally: sales.Tally{Units: 2, Revenue: sales.NewUSD(20)}},
+		{Quarter: "2024-Q1", Country: "US", Region: "NY", Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(10)}},
+		{Quarter: "2024-Q2", Country: "US", Region: "CA", Tally: sales.Tally{Units: 1, Revenue: sales.NewUSD(10)}},
+	}
+	got := r.ByJurisdiction()
+	if len(got) != len(want) {
+		t.Fatalf("want %+v, got %+v", want, got)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("row %d: want %+v, got %+v", i, want[i], got[i])
+		}
+	}
+}
+
+func TestWriteTaxCSVWritesARowPerJurisdictionAndQuarter(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	addTaxableSales(r)
+	var b strings.Builder
+	if err := r.WriteTaxCSV(&b); err != nil {
+		t.Fatal(err)
+	}
+	want := `quarter,country,region,units,revenue
+,,,1,5.00
+2024-Q1,GB,,1,30.00
+2024-Q1,US,CA,2,20.00
+2024-Q1,US,NY,1,10.00
+2024-Q2,US,CA,1,10.00
+`
+	if got := b.String(); got != want {
+		t.Errorf("want:\n%s\ngot:\n%s", want, got)
+	}
+}
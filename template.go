@@ -0,0 +1,47 @@
+package sales
+
+import (
+	"io"
+	"path/filepath"
+	"text/template"
+	"time"
+)
+
+// TemplateData is the data available to output templates: the report's
+// products (in the order given by Sort), totals, sales by period and by
+// source, and when it was generated.
+type TemplateData struct {
+	Products     []*Product
+	TotalUnits   int
+	TotalRevenue USD
+	Periods      []PeriodTotal
+	Sources      []SourceTotal
+	Generated    time.Time
+}
+
+// TemplateData returns the data for rendering the report with a template.
+func (r *Report) TemplateData() TemplateData {
+	return TemplateData{
+		Products:     r.Products(),
+		TotalUnits:   r.TotalUnits(),
+		TotalRevenue: r.TotalRevenue(),
+		Periods:      r.ByPeriod(),
+		Sources:      r.BySource(),
+		Generated:    time.Now(),
+	}
+}
+
+// ParseTemplate reads an output template, in the syntax of the text/template
+// package, from the file at path. Amounts print as plain numbers, such as
+// 1234.50; templates can also call money to format an amount for the given
+// locale, as in {{money .TotalRevenue}}.
+func ParseTemplate(path string, loc Locale) (*template.Template, error) {
+	return template.New(filepath.Base(path)).Funcs(template.FuncMap{
+		"money": loc.Format,
+	}).ParseFiles(path)
+}
+
+// WriteTemplate renders the report with t, which is given TemplateData.
+func (r *Report) WriteTemplate(w io.Writer, t *template.Template) error {
+	return t.Execute(w, r.TemplateData())
+}
@@ -0,0 +1,283 @@
+package sales
+
+import (
+	"encoding/binary"
+	"errors"
+	"math"
+)
+
+// Thrift compact protocol type codes, as used in Parquet file metadata.
+const (
+	thriftTrue   = 1
+	thriftFalse  = 2
+	thriftByte   = 3
+	thriftI16    = 4
+	thriftI32    = 5
+	thriftI64    = 6
+	thriftDouble = 7
+	thriftBinary = 8
+	thriftList   = 9
+	thriftSet    = 10
+	thriftMap    = 11
+	thriftStruct = 12
+)
+
+// thriftWriter encodes structs in the Thrift compact protocol.
+type thriftWriter struct {
+	buf []byte
+	// last holds the ID of the last field written in each open struct,
+	// from which the next field's ID is encoded as a delta.
+	last []int16
+}
+
+func newThriftWriter() *thriftWriter {
+	return &thriftWriter{last: []int16{0}}
+}
+
+func (w *thriftWriter) uvarint(v uint64) {
+	w.buf = binary.AppendUvarint(w.buf, v)
+}
+
+func (w *thriftWriter) zigzag(v int64) {
+	w.uvarint(uint64(v<<1) ^ uint64(v>>63))
+}
+
+func (w *thriftWriter) field(id int16, typ byte) {
+	last := &w.last[len(w.last)-1]
+	if d := id - *last; d > 0 && d <= 15 {
+		w.buf = append(w.buf, byte(d)<<4|typ)
+	} else {
+		w.buf = append(w.buf, typ)
+		w.zigzag(int64(id))
+	}
+	*last = id
+}
+
+func (w *thriftWriter) i32(id int16, v int32) {
+	w.field(id, thriftI32)
+	w.zigzag(int64(v))
+}
+
+func (w *thriftWriter) i64(id int16, v int64) {
+	w.field(id, thriftI64)
+	w.zigzag(v)
+}
+
+func (w *thriftWriter) str(id int16, s string) {
+	w.field(id, thriftBinary)
+	w.uvarint(uint64(len(s)))
+	w.buf = append(w.buf, s...)
+}
+
+// list starts a list field of n elements of type elem. The elements are
+// written with the unnumbered methods: i32Elem, stringElem, or
+// structElem and end.
+func (w *thriftWriter) list(id int16, elem byte, n int) {
+	w.field(id, thriftList)
+	if n < 15 {
+		w.buf = append(w.buf, byte(n)<<4|elem)
+		return
+	}
+	w.buf = append(w.buf, 0xF0|elem)
+	w.uvarint(uint64(n))
+}
+
+func (w *thriftWriter) i32Elem(v int32) {
+	w.zigzag(int64(v))
+}
+
+func (w *thriftWriter) stringElem(s string) {
+	w.uvarint(uint64(len(s)))
+	w.buf = append(w.buf, s...)
+}
+
+// structField starts a struct-valued field, which is finished by end.
+func (w *thriftWriter) structField(id int16) {
+	w.field(id, thriftStruct)
+	w.structElem()
+}
+
+// structElem starts a struct in a list, or the top-level struct, which is
+// finished by end.
+func (w *thriftWriter) structElem() {
+	w.last = append(w.last, 0)
+}
+
+func (w *thriftWriter) end() {
+	w.buf = append(w.buf, 0)
+	w.last = w.last[:len(w.last)-1]
+}
+
+// thriftFields is a decoded Thrift struct: its field values by ID. Values
+// are int64 for integers, bool, float64, []byte for binary fields and
+// strings, []any for lists and sets, and thriftFields for structs. Maps
+// are skipped.
+type thriftFields map[int16]any
+
+func (s thriftFields) i64(id int16) int64 {
+	v, _ := s[id].(int64)
+	return v
+}
+
+func (s thriftFields) has(id int16) bool {
+	_, ok := s[id]
+	return ok
+}
+
+func (s thriftFields) bytes(id int16) []byte {
+	v, _ := s[id].([]byte)
+	return v
+}
+
+func (s thriftFields) boolean(id int16) (value, ok bool) {
+	value, ok = s[id].(bool)
+	return value, ok
+}
+
+func (s thriftFields) list(id int16) []any {
+	v, _ := s[id].([]any)
+	return v
+}
+
+func (s thriftFields) fields(id int16) thriftFields {
+	v, _ := s[id].(thriftFields)
+	return v
+}
+
+var errThrift = errors.New("malformed Thrift data")
+
+// maxThriftDepth is how deeply structs and lists may be nested. Parquet
+// metadata needs only a handful of levels, and the limit stops corrupt
+// input from recursing until the stack runs out.
+const maxThriftDepth = 64
+
+// thriftReader decodes the Thrift compact protocol, without a schema. After
+// an error, it reads only zeros, and err is set.
+type thriftReader struct {
+	buf []byte
+	pos int
+	err error
+	// depth is the number of structs, lists, and maps being read.
+	depth int
+}
+
+func (r *thriftReader) readByte() byte {
+	if r.pos >= len(r.buf) {
+		r.err = errThrift
+		return 0
+	}
+	r.pos++
+	return r.buf[r.pos-1]
+}
+
+func (r *thriftReader) uvarint() uint64 {
+	if r.err != nil {
+		return 0
+	}
+	v, n := binary.Uvarint(r.buf[r.pos:])
+	if n <= 0 {
+		r.err = errThrift
+		return 0
+	}
+	r.pos += n
+	return v
+}
+
+func (r *thriftReader) zigzag() int64 {
+	v := r.uvarint()
+	return int64(v>>1) ^ -int64(v&1)
+}
+
+// size reads a length, which can't be more than the bytes left.
+func (r *thriftReader) size() int {
+	n := r.uvarint()
+	if n > uint64(len(r.buf)-r.pos) {
+		r.err = errThrift
+		return 0
+	}
+	return int(n)
+}
+
+func (r *thriftReader) structure() thriftFields {
+	s := thriftFields{}
+	var last int16
+	for r.err == nil {
+		h := r.readByte()
+		if h == 0 {
+			break
+		}
+		id := last + int16(h>>4)
+		if h>>4 == 0 {
+			id = int16(r.zigzag())
+		}
+		last = id
+		typ := h & 0x0F
+		switch typ {
+		case thriftTrue:
+			s[id] = true
+		case thriftFalse:
+			s[id] = false
+		default:
+			s[id] = r.value(typ)
+		}
+	}
+	return s
+}
+
+func (r *thriftReader) value(typ byte) any {
+	switch typ {
+	case thriftList, thriftSet, thriftMap, thriftStruct:
+		r.depth++
+		defer func() { r.depth-- }()
+		if r.depth > maxThriftDepth {
+			r.err = errThrift
+			return nil
+		}
+	}
+	switch typ {
+	case thriftTrue, thriftFalse:
+		// In lists, booleans are a byte each.
+		return r.readByte() == thriftTrue
+	case thriftByte:
+		return int64(int8(r.readByte()))
+	case thriftI16, thriftI32, thriftI64:
+		return r.zigzag()
+	case thriftDouble:
+		if len(r.buf)-r.pos < 8 {
+			r.err = errThrift
+			return 0.0
+		}
+		r.pos += 8
+		return math.Float64frombits(binary.LittleEndian.Uint64(r.buf[r.pos-8:]))
+	case thriftBinary:
+		n := r.size()
+		r.pos += n
+		return r.buf[r.pos-n : r.pos]
+	case thriftList, thriftSet:
+		h := r.readByte()
+		n := int(h >> 4)
+		if n == 15 {
+			n = r.size()
+		}
+		var list []any
+		for i := 0; i < n && r.err == nil; i++ {
+			list = append(list, r.value(h&0x0F))
+		}
+		return list
+	case thriftMap:
+		n := r.size()
+		if n == 0 {
+			return nil
+		}
+		kv := r.readByte()
+		for i := 0; i < n && r.err == nil; i++ {
+			r.value(kv >> 4)
+			r.value(kv & 0x0F)
+		}
+		return nil
+	case thriftStruct:
+		return r.structure()
+	}
+	r.err = errThrift
+	return nil
+}
@@ -0,0 +1,98 @@
+package sales
+
+import (
+	"bytes"
+	"errors"
+	"testing"
+)
+
+func TestThriftReaderReadsWhatThriftWriterWrites(t *testing.T) {
+	t.Parallel()
+	w := newThriftWriter()
+	w.structElem()
+	w.i32(1, -7)
+	w.i64(2, 1<<40)
+	w.str(4, "schema")
+	// A jump of more than 15 field IDs needs the long form of the header.
+	w.list(30, thriftStruct, 2)
+	for _, name := range []string{"a", "b"} {
+		w.structElem()
+		w.str(1, name)
+		w.end()
+	}
+	w.structField(31)
+	w.list(1, thriftI32, 16)
+	for i := int32(0); i < 16; i++ {
+		w.i32Elem(i)
+	}
+	w.end()
+	w.end()
+	r := &thriftReader{buf: w.buf}
+	s := r.structure()
+	if r.err != nil {
+		t.Fatal(r.err)
+	}
+	if r.pos != len(w.buf) {
+		t.Errorf("want all %d bytes read, got %d", len(w.buf), r.pos)
+	}
+	if got := s.i64(1); got != -7 {
+		t.Errorf("field 1: want -7, got %d", got)
+	}
+	if got := s.i64(2); got != 1<<40 {
+		t.Errorf("field 2: want %d, got %d", int64(1<<40), got)
+	}
+	if got := string(s.bytes(4)); got != "schema" {
+		t.Errorf("field 4: want %q, got %q", "schema", got)
+	}
+	list := s.list(30)
+	if len(list) != 2 {
+		t.Fatalf("field 30: want 2 elements, got %d", len(list))
+	}
+	for i, want := range []string{"a", "b"} {
+		el, _ := list[i].(thriftFields)
+		if got := string(el.bytes(1)); got != want {
+			t.Errorf("field 30[%d]: want %q, got %q", i, want, got)
+		}
+	}
+	ints := s.fields(31).list(1)
+	if len(ints) != 16 {
+		t.Fatalf("field 31.1: want 16 elements, got %d", len(ints))
+	}
+	for i, v := range ints {
+		if v != int64(i) {
+			t.Errorf("field 31.1[%d]: want %d, got %v", i, i, v)
+		}
+	}
+}
+
+func TestThriftReaderRejectsTruncatedData(t *testing.T) {
+	t.Parallel()
+	w := newThriftWriter()
+	w.structElem()
+	w.str(1, "name")
+	w.list(2, thriftI32, 3)
+	w.i32Elem(1)
+	w.i32Elem(2)
+	w.i32Elem(300)
+	w.end()
+	for n := 0; n < len(w.buf); n++ {
+		r := &thriftReader{buf: w.buf[:n]}
+		r.structure()
+		if !errors.Is(r.err, errThrift) {
+			t.Errorf("%d of %d bytes: want errThrift, got %v", n, len(w.buf), r.err)
+		}
+	}
+}
+
+func TestThriftReaderRejectsDeeplyNestedData(t *testing.T) {
+	t.Parallel()
+	// Each byte starts a struct as field 1 of the one before, without end.
+	r := &thriftReader{buf: bytes.Repeat([]byte{1<<4 | thriftStruct}, 100_000)}
+	r.structure()
+	if !errors.Is(r.err, errThrift) {
+		t.Errorf("want errThrift, got %v", r.err)
+	}
+	if r.depth != 0 {
+		t.Errorf("want depth 0 after reading, got %d", r.depth)
+	}
+}
@@ -0,0 +1,50 @@
+package sales
+
+import (
+	"encoding/csv"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+)
+
+// periodKey identifies the sales of one product in one period.
+type periodKey struct {
+	period  string
+	product string
+}
+
+// WriteTidyCSV writes the report in long ("tidy") format, with one row per
+// period, product, and metric, so that it can be loaded into analysis tools
+// without reshaping. The metrics are "units" and "revenue". Sales from
+// summary-only sources, which carry no dates, have an empty period.
+func (r *Report) WriteTidyCSV(w io.Writer) error {
+	r.logRender("tidy")
+	totals := map[periodKey]Tally{}
+	for s, t := range r.slots {
+		k := periodKey{period: s.period, product: s.product}
+		sum := totals[k]
+		sum.Units += t.Units
+		sum.Revenue += t.Revenue
+		totals[k] = sum
+	}
+	keys := make([]periodKey, 0, len(totals))
+	for k := range totals {
+		keys = append(keys, k)
+	}
+	sort.Slice(keys, func(i, j int) bool {
+		if keys[i].period != keys[j].period {
+			return keys[i].period < keys[j].period
+		}
+		return keys[i].product < keys[j].product
+	})
+	cw := csv.NewWriter(w)
+	cw.Write([]string{"period", "product", "metric", "value"})
+	for _, k := range keys {
+		t := totals[k]
+		cw.Write([]string{k.period, k.product, "units", strconv.Itoa(t.Units)})
+		cw.Write([]string{k.period, k.product, "revenue", fmt.Sprintf("%.2f", t.Revenue.Dollars())})
+	}
+	cw.Flush()
+	return cw.Error()
+}
@@ -0,0 +1,90 @@
+package sales
+
+import (
+	"io"
+	"sort"
+	"strconv"
+	"time"
+)
+
+// Lifecycle describes when a product has been selling.
+type Lifecycle struct {
+	Product string
+	// First and Last are the days of the product's first and latest
+	// sales.
+	First, Last time.Time
+	// DaysOnSale counts the days from First to Last, inclusive, and
+	// DaysSinceLast the days from Last to the latest sale of any
+	// product in the report.
+	DaysOnSale    int
+	DaysSinceLast int
+	// Revenue is the revenue from the product's dated sales, and
+	// PerDay that revenue spread over DaysOnSale.
+	Revenue USD
+	PerDay  USD
+}
+
+// ProductTimeline returns the lifecycle of each product with dated sales,
+// longest idle first, so that products which have stopped selling stand
+// out.
+func (r *Report) ProductTimeline() []Lifecycle {
+	byProduct := map[string]*Lifecycle{}
+	var latest time.Time
+	for k, rev := range r.daily {
+		day, err := time.Parse(time.DateOnly, k.date)
+		if err != nil {
+			continue
+		}
+		l := byProduct[k.product]
+		if l == nil {
+			l = &Lifecycle{Product: k.product, First: day, Last: day}
+			byProduct[k.product] = l
+		}
+		if day.Before(l.First) {
+			l.First = day
+		}
+		if day.After(l.Last) {
+			l.Last = day
+		}
+		if day.After(latest) {
+			latest = day
+		}
+		l.Revenue += rev
+	}
+	timeline := make([]Lifecycle, 0, len(byProduct))
+	for _, l := range byProduct {
+		l.DaysOnSale = int(l.Last.Sub(l.First).Hours()/24) + 1
+		l.DaysSinceLast = int(latest.Sub(l.Last).Hours() / 24)
+		l.PerDay = l.Revenue / USD(l.DaysOnSale)
+		timeline = append(timeline, *l)
+	}
+	sort.Slice(timeline, func(i, j int) bool {
+		a, b := timeline[i], timeline[j]
+		if a.DaysSinceLast != b.DaysSinceLast {
+			return a.DaysSinceLast > b.DaysSinceLast
+		}
+		return a.Product < b.Product
+	})
+	return timeline
+}
+
+// WriteTimeline writes the product timeline as a text table.
+func (r *Report) WriteTimeline(w io.Writer) error {
+	t := &table{
+		header: []string{"Product", "First sale", "Last sale", "Days on sale", "Days idle", "Revenue", "Per day"},
+		right:  []bool{false, false, false, true, true, true, true},
+		color:  r.Color,
+	}
+	for _, l := range r.ProductTimeline() {
+		t.add(
+			l.Product,
+			l.First.Format(time.DateOnly),
+			l.Last.Format(time.DateOnly),
+			strconv.Itoa(l.DaysOnSale),
+			strconv.Itoa(l.DaysSinceLast),
+			l.Revenue.String(),
+			l.PerDay.String(),
+		)
+	}
+	return t.write(w)
+}
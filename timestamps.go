@@ -0,0 +1,56 @@
+package sales
+
+import (
+	"errors"
+	"strconv"
+	"time"
+)
+
+// builtinLayouts are the timestamp layouts tried after any configured ones.
+var builtinLayouts = []string{
+	squarespaceTime,
+	time.RFC3339,
+	"2006-01-02 15:04:05",
+	"2006-01-02T15:04:05",
+	time.DateOnly,
+}
+
+// Timestamps says how to read the timestamps in input files, and which time
+// zone to report sales in. Since different platforms write timestamps
+// differently (Squarespace in local time with an offset, Stripe in UTC or as
+// Unix times), each timestamp is tried against several layouts, so sources
+// in different formats can be read together. The zero value reads all the
+// built-in layouts, and reports each sale in the zone it was recorded in.
+type Timestamps struct {
+	// Layouts are extra layouts, in the form used by time.Parse, to try
+	// before the built-in ones: the Squarespace layout, RFC 3339,
+	// "2006-01-02 15:04:05", and "2006-01-02". A timestamp that is all
+	// digits is read as a Unix time in seconds.
+	Layouts []string
+	// InputZone is the time zone of timestamps that don't give one. Nil
+	// means UTC.
+	InputZone *time.Location
+	// Zone is the time zone that sales are reported in, which decides the
+	// day, hour, and period they are counted in. Nil means the zone each
+	// timestamp was recorded in.
+	Zone *time.Location
+}
+
+// Parse reads the timestamp s.
+func (ts Timestamps) Parse(s string) (time.Time, error) {
+	if n, err := strconv.ParseInt(s, 10, 64); err == nil {
+		return time.Unix(n, 0).UTC(), nil
+	}
+	zone := ts.InputZone
+	if zone == nil {
+		zone = time.UTC
+	}
+	for _, layouts := range [][]string{ts.Layouts, builtinLayouts} {
+		for _, layout := range layouts {
+			if t, err := time.ParseInLocation(layout, s, zone); err == nil {
+				return t, nil
+			}
+		}
+	}
+	return time.Time{}, errors.New("unrecognised timestamp format")
+}
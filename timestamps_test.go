@@ -0,0 +1,60 @@
+package sales_test
+
+import (
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func TestTimestampsParseReadsEachLayout(t *testing.T) {
+	t.Parallel()
+	newYork, err := time.LoadLocation("America/New_York")
+	if err != nil {
+		t.Skip(err)
+	}
+	ts := sales.Timestamps{Layouts: []string{"02/01/2006 15:04"}, InputZone: newYork}
+	tcs := []struct {
+		input string
+		want  time.Time
+	}{
+		{"1709294400", time.Date(2024, time.March, 1, 12, 0, 0, 0, time.UTC)},
+		{"2024-03-01 12:00:00 +0100", time.Date(2024, time.March, 1, 11, 0, 0, 0, time.UTC)},
+		{"2024-03-01T12:00:00Z", time.Date(2024, time.March, 1, 12, 0, 0, 0, time.UTC)},
+		// Timestamps without a zone are in InputZone.
+		{"2024-03-01 12:00:00", time.Date(2024, time.March, 1, 12, 0, 0, 0, newYork)},
+		{"2024-03-01T12:00:00", time.Date(2024, time.March, 1, 12, 0, 0, 0, newYork)},
+		{"2024-03-01", time.Date(2024, time.March, 1, 0, 0, 0, 0, newYork)},
+		{"01/03/2024 12:00", time.Date(2024, time.March, 1, 12, 0, 0, 0, newYork)},
+	}
+	for _, tc := range tcs {
+		got, err := ts.Parse(tc.input)
+		if err != nil {
+			t.Errorf("%q: %v", tc.input, err)
+			continue
+		}
+		if !got.Equal(tc.want) {
+			t.Errorf("%q: want %v, got %v", tc.input, tc.want, got)
+		}
+	}
+	if _, err := ts.Parse("March 1st"); err == nil {
+		t.Error("want error for unknown format, got nil")
+	}
+}
+
+func TestTimestampsZoneDecidesTheHourSalesAreCountedIn(t *testing.T) {
+	t.Parallel()
+	tokyo, err := time.LoadLocation("Asia/Tokyo")
+	if err != nil {
+		t.Skip(err)
+	}
+	r := sales.NewReport()
+	r.Timestamps.Zone = tokyo
+	r.Add(sales.Record{Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Time: time.Date(2024, time.March, 1, 23, 0, 0, 0, time.UTC)})
+	if got := r.ByHour()[8].Orders; got != 1 {
+		t.Errorf("want the sale at 08:00 in Tokyo, got %d orders then", got)
+	}
+	if got := r.ByWeekday()[time.Saturday].Orders; got != 1 {
+		t.Errorf("want the sale on Saturday in Tokyo, got %d orders then", got)
+	}
+}
@@ -0,0 +1,40 @@
+package sales
+
+import (
+	"fmt"
+	"io"
+)
+
+// Totals is the headline summary of a report.
+type Totals struct {
+	Units   int `json:"units"`
+	Revenue USD `json:"revenue"`
+	// Orders is the number of orders, counting each order ID once
+	// within its source, and each line item without an order ID as an
+	// order of its own. Orders of nothing but comps are included.
+	Orders int `json:"orders"`
+	// Fees is the total that platforms kept from sales, whether or not
+	// they have timestamps; see Record.Fee.
+	Fees USD `json:"fees"`
+}
+
+// Totals returns the total units, revenue, orders, and fees in the report.
+func (r *Report) Totals() Totals {
+	t := Totals{
+		Units:   r.TotalUnits(),
+		Revenue: r.TotalRevenue(),
+		Orders:  r.orderCount,
+	}
+	for _, fee := range r.fees {
+		t.Fees += fee
+	}
+	return t
+}
+
+// WriteTotals writes the report's Totals as text, one per line, such as
+// "Revenue  1234.50".
+func (r *Report) WriteTotals(w io.Writer) error {
+	t := r.Totals()
+	_, err := fmt.Fprintf(w, "Units    %d\nRevenue  %s\nOrders   %d\nFees     %s\n", t.Units, t.Revenue, t.Orders, t.Fees)
+	return err
+}
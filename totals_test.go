@@ -0,0 +1,67 @@
+package sales_test
+
+import (
+	"bytes"
+	"testing"
+	"time"
+
+	"github.com/bitfield/sales"
+)
+
+func TestTotalsCountFeesOfUndatedRecords(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Add(sales.Record{OrderID: "1", Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Fee: sales.NewUSD(0.59)})
+	r.Add(sales.Record{OrderID: "2", Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Fee: sales.NewUSD(0.59), Time: time.Date(2024, time.March, 1, 12, 0, 0, 0, time.UTC)})
+	if got, want := r.Totals().Fees, sales.NewUSD(1.18); got != want {
+		t.Errorf("want fees %v, got %v", want, got)
+	}
+	recs := r.Reconcile(nil, 0)
+	if len(recs) != 1 || recs[0].Month != "2024-03" || recs[0].Fees != sales.NewUSD(0.59) {
+		t.Errorf("want only the dated fee reconciled, in 2024-03, got %+v", recs)
+	}
+}
+
+func TestTotalsCountOrdersOfOnlyComps(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Comps = true
+	r.Add(sales.Record{OrderID: "1", Product: "Book", Quantity: 1, Price: sales.NewUSD(10)})
+	r.Add(sales.Record{OrderID: "2", Product: "Book", Quantity: 1})
+	r.Add(sales.Record{OrderID: "3", Product: "Book", Quantity: 1})
+	r.Add(sales.Record{OrderID: "3", Product: "Video", Quantity: 1, Price: sales.NewUSD(20)})
+	got := r.Totals()
+	if got.Orders != 3 {
+		t.Errorf("want 3 orders, got %d", got.Orders)
+	}
+	if got.Units != 2 {
+		t.Errorf("want 2 paid units, got %d", got.Units)
+	}
+	if comps := r.TotalComps(); comps != 2 {
+		t.Errorf("want 2 comps, got %d", comps)
+	}
+}
+
+func TestStateRoundTripKeepsTotals(t *testing.T) {
+	t.Parallel()
+	r := sales.NewReport()
+	r.Add(sales.Record{OrderID: "1", Product: "Book", Quantity: 2, Price: sales.NewUSD(10), Fee: sales.NewUSD(0.5)})
+	r.Add(sales.Record{OrderID: "1", Product: "Video", Quantity: 1, Price: sales.NewUSD(20)})
+	r.Add(sales.Record{OrderID: "2", Product: "Book", Quantity: 1, Price: sales.NewUSD(10), Time: time.Date(2024, time.March, 1, 12, 0, 0, 0, time.UTC)})
+	var buf bytes.Buffer
+	if err := r.WriteState(&buf); err != nil {
+		t.Fatal(err)
+	}
+	saved, err := sales.ReadState(&buf)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if want, got := r.Totals(), saved.Totals(); want != got {
+		t.Errorf("want totals %+v, got %+v", want, got)
+	}
+	// Orders already seen aren't counted again when more is added.
+	saved.Add(sales.Record{OrderID: "2", Product: "Video", Quantity: 1, Price: sales.NewUSD(20)})
+	if got := saved.Totals().Orders; got != 2 {
+		t.Errorf("want 2 orders after adding to order 2, got %d", got)
+	}
+}
@@ -0,0 +1,39 @@
+package sales
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"net/url"
+	"path"
+)
+
+// ReadURL downloads the export at rawURL and reads it into the report,
+// choosing the format from the extension of the URL's path, as ReadFile
+// does. The export is read as it downloads, without being saved to disk.
+// Any headers given, such as Authorization, are sent with the request.
+func (r *Report) ReadURL(ctx context.Context, rawURL string, header http.Header) error {
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, rawURL, nil)
+	if err != nil {
+		return err
+	}
+	for k, vs := range header {
+		req.Header[k] = vs
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return fmt.Errorf("%s: %s", rawURL, resp.Status)
+	}
+	if limit := r.Limits.MaxFileSize; limit > 0 && resp.ContentLength > limit {
+		return fmt.Errorf("%s: %w", rawURL, &LimitError{Limit: "max_file_size", Max: limit})
+	}
+	return r.readFormat(resp.Body, rawURL, path.Ext(u.Path))
+}
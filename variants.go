@@ -0,0 +1,73 @@
+package sales
+
+import (
+	"io"
+	"sort"
+	"strconv"
+)
+
+// variantSlot identifies the sales of one variant of a report row.
+type variantSlot struct {
+	product string
+	variant string
+}
+
+// VariantTotal is the units and revenue from one variant of a product, such
+// as a size or format. Sales without a variant have an empty Variant.
+type VariantTotal struct {
+	Product string
+	Variant string
+	Tally
+}
+
+// ByVariant returns the units and revenue from each variant of each
+// product, in the order of Products, and then by variant.
+func (r *Report) ByVariant() []VariantTotal {
+	order := map[string]int{}
+	for i, p := range r.Products() {
+		order[p.Name] = i
+	}
+	totals := make([]VariantTotal, 0, len(r.variants))
+	for k, t := range r.variants {
+		totals = append(totals, VariantTotal{Product: k.product, Variant: k.variant, Tally: t})
+	}
+	sort.Slice(totals, func(i, j int) bool {
+		a, b := totals[i], totals[j]
+		if a.Product != b.Product {
+			return order[a.Product] < order[b.Product]
+		}
+		return a.Variant < b.Variant
+	})
+	return totals
+}
+
+// WriteVariants writes the report as a text table in which each product's
+// row, giving its total, is followed by a row for each of its variants.
+// Products sold without variants have no variant rows.
+func (r *Report) WriteVariants(w io.Writer) error {
+	t := &table{
+		header: []string{"Product", "Variant", "Units", "Revenue"},
+		right:  []bool{false, false, true, true},
+		color:  r.Color,
+	}
+	byProduct := map[string][]VariantTotal{}
+	for _, v := range r.ByVariant() {
+		byProduct[v.Product] = append(byProduct[v.Product], v)
+	}
+	for _, p := range r.Products() {
+		t.add(p.Name, "", strconv.Itoa(p.Units), p.Revenue.String())
+		variants := byProduct[p.Name]
+		if len(variants) == 1 && variants[0].Variant == "" {
+			continue
+		}
+		for _, v := range variants {
+			name := v.Variant
+			if name == "" {
+				name = "(no variant)"
+			}
+			t.add("", name, strconv.Itoa(v.Units), v.Revenue.String())
+		}
+	}
+	t.footer = []string{"Total", "", strconv.Itoa(r.TotalUnits()), r.TotalRevenue().String()}
+	return t.write(w)
+}
@@ -0,0 +1,33 @@
+package sales
+
+import "fmt"
+
+// Warning describes something suspicious in the input that didn't stop it
+// being read, such as a zero-priced item or a duplicate order ID. Line is
+// zero if the input has no lines.
+type Warning struct {
+	File    string
+	Line    int
+	Message string
+}
+
+func (w Warning) String() string {
+	if w.Line == 0 {
+		return fmt.Sprintf("%s: %s", w.File, w.Message)
+	}
+	return fmt.Sprintf("%s:%d: %s", w.File, w.Line, w.Message)
+}
+
+// Warnings returns the warnings recorded while reading input, in the order
+// they were encountered.
+func (r *Report) Warnings() []Warning {
+	return r.warnings
+}
+
+func (r *Report) warn(file string, line int, format string, args ...any) {
+	r.warnings = append(r.warnings, Warning{
+		File:    file,
+		Line:    line,
+		Message: fmt.Sprintf(format, args...),
+	})
+}